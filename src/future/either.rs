@@ -1,7 +1,11 @@
-use {Future, Poll};
+use {Future, Poll, StartSend};
+use sink::Sink;
 
 /// Combines two different futures yielding the same item and error
 /// types into a single type.
+///
+/// Two sinks accepting the same item and error types can be combined this
+/// way as well, see `Sink::left_sink` and `Sink::right_sink`.
 #[derive(Debug)]
 pub enum Either<A, B> {
     /// First branch of the type
@@ -37,3 +41,32 @@ impl<A, B> Future for Either<A, B>
         }
     }
 }
+
+impl<A, B> Sink for Either<A, B>
+    where A: Sink,
+          B: Sink<SinkItem = A::SinkItem, SinkError = A::SinkError>
+{
+    type SinkItem = A::SinkItem;
+    type SinkError = A::SinkError;
+
+    fn start_send(&mut self, item: A::SinkItem) -> StartSend<A::SinkItem, A::SinkError> {
+        match *self {
+            Either::A(ref mut a) => a.start_send(item),
+            Either::B(ref mut b) => b.start_send(item),
+        }
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), A::SinkError> {
+        match *self {
+            Either::A(ref mut a) => a.poll_complete(),
+            Either::B(ref mut b) => b.poll_complete(),
+        }
+    }
+
+    fn close(&mut self) -> Poll<(), A::SinkError> {
+        match *self {
+            Either::A(ref mut a) => a.close(),
+            Either::B(ref mut b) => b.close(),
+        }
+    }
+}
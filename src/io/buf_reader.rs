@@ -0,0 +1,129 @@
+use std::fmt;
+use std::io::{self, BufRead, Read};
+use std::prelude::v1::*;
+
+use {Async, Poll};
+use io::AsyncRead;
+
+const DEFAULT_BUF_SIZE: usize = 8 * 1024;
+
+/// Adds buffering to any reader.
+///
+/// Reading from an I/O object one small piece at a time, as is typical for
+/// line-based protocols, can mean one system call per piece. A `BufReader`
+/// instead reads large chunks into an in-memory buffer and serves subsequent
+/// reads from there, which is much more efficient.
+///
+/// The contents of the buffer can be accessed directly with `poll_fill_buf`
+/// and `consume`, the asynchronous counterparts to `BufRead::fill_buf` and
+/// `BufRead::consume`. Note that any leftover data in the buffer is lost when
+/// the `BufReader` is dropped or turned back into the underlying reader.
+pub struct BufReader<R> {
+    inner: R,
+    buf: Box<[u8]>,
+    pos: usize,
+    cap: usize,
+}
+
+impl<R: AsyncRead> BufReader<R> {
+    /// Creates a new `BufReader` with a default buffer capacity.
+    pub fn new(inner: R) -> BufReader<R> {
+        BufReader::with_capacity(DEFAULT_BUF_SIZE, inner)
+    }
+
+    /// Creates a new `BufReader` with the specified buffer capacity.
+    pub fn with_capacity(capacity: usize, inner: R) -> BufReader<R> {
+        BufReader {
+            inner: inner,
+            buf: vec![0; capacity].into_boxed_slice(),
+            pos: 0,
+            cap: 0,
+        }
+    }
+
+    /// Attempts to return the contents of the internal buffer, filling it
+    /// with more data from the inner reader if it's empty.
+    ///
+    /// An empty slice is returned once the inner reader has reached EOF. The
+    /// returned bytes aren't consumed until `consume` is called.
+    pub fn poll_fill_buf(&mut self) -> Poll<&[u8], io::Error> {
+        if self.pos >= self.cap {
+            self.cap = try_ready!(self.inner.poll_read(&mut self.buf));
+            self.pos = 0;
+        }
+        Ok(Async::Ready(&self.buf[self.pos..self.cap]))
+    }
+
+    /// Tells this buffer that `amt` bytes have been consumed from the buffer,
+    /// so they should no longer be returned by `poll_fill_buf` or reads.
+    pub fn consume(&mut self, amt: usize) {
+        self.pos = ::std::cmp::min(self.pos + amt, self.cap);
+    }
+}
+
+impl<R> BufReader<R> {
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// It is inadvisable to directly read from the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the data currently buffered, without reading any more.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf[self.pos..self.cap]
+    }
+
+    /// Unwraps this `BufReader`, returning the underlying reader.
+    ///
+    /// Note that any leftover data in the internal buffer is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> Read for BufReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // If we don't have any buffered data and we're doing a massive read
+        // (larger than our internal buffer), bypass our internal buffer
+        // entirely.
+        if self.pos == self.cap && buf.len() >= self.buf.len() {
+            return self.inner.read(buf)
+        }
+        let nread = {
+            let mut rem = try!(self.fill_buf());
+            try!(rem.read(buf))
+        };
+        self.consume(nread);
+        Ok(nread)
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for BufReader<R> {}
+
+impl<R: AsyncRead> BufRead for BufReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        match try!(self.poll_fill_buf()) {
+            Async::Ready(buf) => Ok(buf),
+            Async::NotReady => Err(io::Error::new(io::ErrorKind::WouldBlock, "would block")),
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        BufReader::consume(self, amt)
+    }
+}
+
+impl<R: fmt::Debug> fmt::Debug for BufReader<R> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("BufReader")
+            .field("reader", &self.inner)
+            .field("buffer", &format_args!("{}/{}", self.cap - self.pos, self.buf.len()))
+            .finish()
+    }
+}
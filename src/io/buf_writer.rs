@@ -0,0 +1,142 @@
+use std::fmt;
+use std::io::{self, Write};
+use std::prelude::v1::*;
+
+use {Async, Poll};
+use io::AsyncWrite;
+
+const DEFAULT_BUF_SIZE: usize = 8 * 1024;
+
+/// Adds buffering to any writer.
+///
+/// Writing to an I/O object many small pieces at a time can mean one system
+/// call per piece. A `BufWriter` instead keeps the data in an in-memory
+/// buffer and writes it out in large chunks, once the buffer is full or when
+/// the writer is flushed.
+///
+/// Unlike `std::io::BufWriter`, buffered data isn't written out when the
+/// `BufWriter` is dropped, as that could block. It should be flushed with
+/// `poll_flush` (or `shutdown`) before being dropped, or the remaining data
+/// is lost.
+pub struct BufWriter<W> {
+    inner: W,
+    buf: Vec<u8>,
+}
+
+impl<W: AsyncWrite> BufWriter<W> {
+    /// Creates a new `BufWriter` with a default buffer capacity.
+    pub fn new(inner: W) -> BufWriter<W> {
+        BufWriter::with_capacity(DEFAULT_BUF_SIZE, inner)
+    }
+
+    /// Creates a new `BufWriter` with the specified buffer capacity.
+    pub fn with_capacity(capacity: usize, inner: W) -> BufWriter<W> {
+        BufWriter {
+            inner: inner,
+            buf: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Attempts to write all of the buffered data to the inner writer,
+    /// without flushing the inner writer itself.
+    pub fn poll_flush_buf(&mut self) -> Poll<(), io::Error> {
+        let mut written = 0;
+        let mut ret = Ok(Async::Ready(()));
+        while written < self.buf.len() {
+            match self.inner.poll_write(&self.buf[written..]) {
+                Ok(Async::Ready(0)) => {
+                    ret = Err(io::Error::new(io::ErrorKind::WriteZero,
+                                             "failed to write the buffered data"));
+                    break
+                }
+                Ok(Async::Ready(n)) => written += n,
+                Ok(Async::NotReady) => {
+                    ret = Ok(Async::NotReady);
+                    break
+                }
+                Err(e) => {
+                    ret = Err(e);
+                    break
+                }
+            }
+        }
+        self.buf.drain(..written);
+        ret
+    }
+}
+
+impl<W> BufWriter<W> {
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the data currently buffered, which hasn't been written out
+    /// yet.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Unwraps this `BufWriter`, returning the underlying writer.
+    ///
+    /// Note that any data still in the internal buffer is lost, so the
+    /// writer should be flushed first.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite> Write for BufWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.buf.len() + buf.len() > self.buf.capacity() {
+            try!(try_nb_io(self.poll_flush_buf()));
+        }
+        if buf.len() >= self.buf.capacity() {
+            self.inner.write(buf)
+        } else {
+            self.buf.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        try!(try_nb_io(self.poll_flush_buf()));
+        self.inner.flush()
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for BufWriter<W> {
+    fn poll_flush(&mut self) -> Poll<(), io::Error> {
+        try_ready!(self.poll_flush_buf());
+        self.inner.poll_flush()
+    }
+
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        try_ready!(self.poll_flush_buf());
+        self.inner.shutdown()
+    }
+}
+
+// Turns `NotReady` back into a `WouldBlock` error, for the `Write` methods.
+fn try_nb_io(res: Poll<(), io::Error>) -> io::Result<()> {
+    match try!(res) {
+        Async::Ready(()) => Ok(()),
+        Async::NotReady => Err(io::Error::new(io::ErrorKind::WouldBlock, "would block")),
+    }
+}
+
+impl<W: fmt::Debug> fmt::Debug for BufWriter<W> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("BufWriter")
+            .field("writer", &self.inner)
+            .field("buffer", &format_args!("{}/{}", self.buf.len(), self.buf.capacity()))
+            .finish()
+    }
+}
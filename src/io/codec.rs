@@ -0,0 +1,84 @@
+use std::io;
+use std::prelude::v1::*;
+
+/// Decoding of frames via buffers.
+///
+/// This trait is used when constructing an instance of `Framed`, and turns
+/// the bytes read from an I/O object into a stream of frames.
+pub trait Decoder {
+    /// The type of decoded frames.
+    type Item;
+
+    /// The type of unrecoverable frame decoding errors.
+    ///
+    /// If an individual message is ill-formed but can be ignored without
+    /// interfering with the processing of future messages, it may be more
+    /// useful to report the failure as an `Item`.
+    ///
+    /// `From<io::Error>` is required in the interest of making `Error`
+    /// suitable for returning directly from a `Framed`, which also needs to
+    /// report I/O errors.
+    type Error: From<io::Error>;
+
+    /// Attempts to decode a frame from the provided buffer of bytes.
+    ///
+    /// This method is called by `Framed` whenever bytes are ready to be
+    /// parsed. The provided buffer contains all the bytes read so far which
+    /// haven't been consumed yet.
+    ///
+    /// If the bytes look valid, but a frame isn't fully available yet, then
+    /// `Ok(None)` is returned. This indicates to `Framed` that it needs to
+    /// read some more bytes before calling this method again.
+    ///
+    /// If a frame is available, then the bytes making it up should be removed
+    /// from the front of `src`, for example with `drain`, and the frame
+    /// returned as `Ok(Some(frame))`. This method will be called again before
+    /// reading more bytes, so only one frame needs to be decoded at a time.
+    ///
+    /// Finally, if the bytes in the buffer are malformed, then an error is
+    /// returned, which ends the stream of frames.
+    fn decode(&mut self, src: &mut Vec<u8>) -> Result<Option<Self::Item>, Self::Error>;
+
+    /// A default method available to be called when there are no more bytes
+    /// available to be read from the underlying I/O.
+    ///
+    /// This method defaults to calling `decode` and returns an error if
+    /// `Ok(None)` is returned while there is unconsumed data in `src`.
+    /// Typically this doesn't need to be implemented unless the framing
+    /// protocol differs near the end of the stream, for example when the
+    /// last frame doesn't need a terminator.
+    fn decode_eof(&mut self, src: &mut Vec<u8>) -> Result<Option<Self::Item>, Self::Error> {
+        match try!(self.decode(src)) {
+            Some(frame) => Ok(Some(frame)),
+            None => {
+                if src.is_empty() {
+                    Ok(None)
+                } else {
+                    Err(io::Error::new(io::ErrorKind::Other,
+                                       "bytes remaining on stream").into())
+                }
+            }
+        }
+    }
+}
+
+/// Encoding of frames via buffers.
+///
+/// This trait is used when constructing an instance of `Framed`, and turns a
+/// sink of frames into the bytes written to an I/O object.
+pub trait Encoder {
+    /// The type of items consumed by the `Encoder`.
+    type Item;
+
+    /// The type of encoding errors.
+    ///
+    /// `Framed` requires `Encoder`s errors to implement `From<io::Error>` in
+    /// the interest of letting it return `Error`s directly.
+    type Error: From<io::Error>;
+
+    /// Encodes a frame, appending its bytes to the buffer provided.
+    ///
+    /// The buffer is written out to the I/O object by `Framed`, once enough
+    /// data has accumulated or when the sink is flushed.
+    fn encode(&mut self, item: Self::Item, dst: &mut Vec<u8>) -> Result<(), Self::Error>;
+}
@@ -0,0 +1,108 @@
+use std::io;
+use std::prelude::v1::*;
+
+use {Async, Future, Poll};
+use io::{AsyncRead, AsyncWrite};
+
+/// A future which will copy all data from a reader into a writer.
+///
+/// Created by the `copy` function, this future will resolve to the number of
+/// bytes copied or an error if one happens.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct Copy<R, W> {
+    reader: Option<R>,
+    read_done: bool,
+    writer: Option<W>,
+    pos: usize,
+    cap: usize,
+    amt: u64,
+    buf: Box<[u8]>,
+}
+
+/// Creates a future which represents copying all the bytes from one object to
+/// another.
+///
+/// The returned future will copy all the bytes read from `reader` into the
+/// `writer` specified, and then flush the writer. Partial reads and writes are
+/// handled by buffering the data in between, so the whole of every read is
+/// eventually written out. This future will only complete once the `reader`
+/// has hit EOF and all bytes have been written to and flushed from the
+/// `writer` provided.
+///
+/// On success the number of bytes copied is returned, along with the reader
+/// and writer so they can be used again. If an error happens while reading or
+/// writing, then the future resolves to that error and the remaining data is
+/// lost.
+///
+/// # Examples
+///
+/// ```
+/// use futures::Future;
+/// use futures::io;
+///
+/// let reader: &[u8] = b"hello world";
+/// let (amt, _, writer) = io::copy(reader, Vec::new()).wait().unwrap();
+/// assert_eq!(amt, 11);
+/// assert_eq!(writer, b"hello world");
+/// ```
+pub fn copy<R, W>(reader: R, writer: W) -> Copy<R, W>
+    where R: AsyncRead,
+          W: AsyncWrite,
+{
+    Copy {
+        reader: Some(reader),
+        read_done: false,
+        writer: Some(writer),
+        amt: 0,
+        pos: 0,
+        cap: 0,
+        buf: Box::new([0; 2048]),
+    }
+}
+
+impl<R, W> Future for Copy<R, W>
+    where R: AsyncRead,
+          W: AsyncWrite,
+{
+    type Item = (u64, R, W);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(u64, R, W), io::Error> {
+        loop {
+            // If our buffer is empty, then we need to read some data to
+            // continue.
+            if self.pos == self.cap && !self.read_done {
+                let reader = self.reader.as_mut().expect("cannot poll Copy twice");
+                let n = try_ready!(reader.poll_read(&mut self.buf));
+                if n == 0 {
+                    self.read_done = true;
+                } else {
+                    self.pos = 0;
+                    self.cap = n;
+                }
+            }
+
+            // If our buffer has some data, let's write it out!
+            while self.pos < self.cap {
+                let writer = self.writer.as_mut().unwrap();
+                let n = try_ready!(writer.poll_write(&self.buf[self.pos..self.cap]));
+                if n == 0 {
+                    return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                              "write zero byte into writer"))
+                }
+                self.pos += n;
+                self.amt += n as u64;
+            }
+
+            // If we've written all the data and we've seen EOF, flush out the
+            // data and finish the transfer.
+            if self.pos == self.cap && self.read_done {
+                try_ready!(self.writer.as_mut().unwrap().poll_flush());
+                let reader = self.reader.take().unwrap();
+                let writer = self.writer.take().unwrap();
+                return Ok(Async::Ready((self.amt, reader, writer)))
+            }
+        }
+    }
+}
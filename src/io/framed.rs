@@ -0,0 +1,234 @@
+use std::fmt;
+use std::io;
+use std::prelude::v1::*;
+
+use {Async, AsyncSink, Poll, Sink, StartSend, Stream};
+use io::{AsyncRead, AsyncWrite, Decoder, Encoder};
+
+const INITIAL_CAPACITY: usize = 8 * 1024;
+const BACKPRESSURE_BOUNDARY: usize = INITIAL_CAPACITY;
+
+/// A unified `Stream` and `Sink` interface to an underlying I/O object, using
+/// the `Encoder` and `Decoder` traits to encode and decode frames.
+///
+/// Created by the `framed` function.
+pub struct Framed<T, U> {
+    inner: T,
+    codec: U,
+    rd: Vec<u8>,
+    wr: Vec<u8>,
+    eof: bool,
+    is_readable: bool,
+}
+
+/// Provides a `Stream` and `Sink` interface for reading and writing to an
+/// I/O object, using a codec to turn bytes into frames and back.
+///
+/// Raw I/O objects work with byte sequences, but higher-level code usually
+/// wants to batch these into meaningful chunks, called "frames". This
+/// function layers framing on top of an I/O object, by using the `Decoder`
+/// and `Encoder` traits of `codec` to handle encoding and decoding of frames.
+///
+/// The returned `Framed` takes care of buffering: bytes are read until the
+/// decoder finds a complete frame, so frames may span several reads, and
+/// encoded frames are buffered until enough have accumulated or the sink is
+/// flushed. Once the I/O object reaches EOF, `Decoder::decode_eof` is used
+/// to decode the remaining bytes before the stream ends.
+///
+/// If you want to work more directly with the streams and sink, consider
+/// calling `split` on the `Framed` returned by this method, which will break
+/// it into separate objects, allowing them to interact more easily.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Cursor;
+/// use futures::{Future, Sink, Stream};
+/// use futures::io::{self, Decoder, Encoder};
+///
+/// // Frames are single bytes, which are doubled on the way out.
+/// struct Bytes;
+///
+/// impl Decoder for Bytes {
+///     type Item = u8;
+///     type Error = std::io::Error;
+///
+///     fn decode(&mut self, src: &mut Vec<u8>) -> Result<Option<u8>, Self::Error> {
+///         if src.is_empty() {
+///             Ok(None)
+///         } else {
+///             Ok(Some(src.remove(0)))
+///         }
+///     }
+/// }
+///
+/// impl Encoder for Bytes {
+///     type Item = u8;
+///     type Error = std::io::Error;
+///
+///     fn encode(&mut self, item: u8, dst: &mut Vec<u8>) -> Result<(), Self::Error> {
+///         dst.push(item * 2);
+///         Ok(())
+///     }
+/// }
+///
+/// let frames = io::framed(Cursor::new(vec![1, 2, 3]), Bytes);
+/// let (frames, _) = frames.into_future().wait().ok().unwrap();
+/// assert_eq!(frames, Some(1));
+///
+/// let transport = io::framed(Cursor::new(Vec::new()), Bytes);
+/// let transport = transport.send(4).wait().unwrap();
+/// assert_eq!(transport.into_inner().into_inner(), [8]);
+/// ```
+pub fn framed<T, U>(inner: T, codec: U) -> Framed<T, U>
+    where T: AsyncRead + AsyncWrite,
+          U: Decoder + Encoder,
+{
+    Framed {
+        inner: inner,
+        codec: codec,
+        rd: Vec::with_capacity(INITIAL_CAPACITY),
+        wr: Vec::with_capacity(INITIAL_CAPACITY),
+        eof: false,
+        is_readable: false,
+    }
+}
+
+impl<T, U> Framed<T, U> {
+    /// Returns a reference to the underlying I/O object.
+    ///
+    /// Note that care should be taken to not tamper with the underlying
+    /// stream of data coming in, as it may corrupt the stream of frames
+    /// otherwise being worked with.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying I/O object.
+    ///
+    /// Note that care should be taken to not tamper with the underlying
+    /// stream of data coming in, as it may corrupt the stream of frames
+    /// otherwise being worked with.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Returns a reference to the codec.
+    pub fn codec(&self) -> &U {
+        &self.codec
+    }
+
+    /// Returns a mutable reference to the codec.
+    pub fn codec_mut(&mut self) -> &mut U {
+        &mut self.codec
+    }
+
+    /// Consumes the `Framed`, returning its underlying I/O object.
+    ///
+    /// Note that any data in the read and write buffers is lost.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T, U> Stream for Framed<T, U>
+    where T: AsyncRead,
+          U: Decoder,
+{
+    type Item = U::Item;
+    type Error = U::Error;
+
+    fn poll(&mut self) -> Poll<Option<U::Item>, U::Error> {
+        loop {
+            // Repeatedly call `decode` or `decode_eof` as long as it is
+            // "readable". Readable is defined as not having returned `None`.
+            // If the upstream has returned EOF, and the decoder is no longer
+            // readable, it can be assumed that the decoder will never become
+            // readable again, at which point the stream is terminated.
+            if self.is_readable {
+                if self.eof {
+                    let frame = try!(self.codec.decode_eof(&mut self.rd));
+                    return Ok(Async::Ready(frame))
+                }
+                if let Some(frame) = try!(self.codec.decode(&mut self.rd)) {
+                    return Ok(Async::Ready(Some(frame)))
+                }
+                self.is_readable = false;
+            }
+
+            // Otherwise, try to read more data and try again. Make sure
+            // we've got room for at least one byte to read to ensure that we
+            // don't get a spurious 0 that looks like EOF.
+            let len = self.rd.len();
+            if self.rd.capacity() - len < 1024 {
+                self.rd.reserve(1024);
+            }
+            let cap = self.rd.capacity();
+            self.rd.resize(cap, 0);
+            let res = self.inner.poll_read(&mut self.rd[len..]);
+            let n = match res {
+                Ok(Async::Ready(n)) => n,
+                _ => 0,
+            };
+            self.rd.truncate(len + n);
+            if try_ready!(res) == 0 {
+                self.eof = true;
+            }
+
+            self.is_readable = true;
+        }
+    }
+}
+
+impl<T, U> Sink for Framed<T, U>
+    where T: AsyncWrite,
+          U: Encoder,
+{
+    type SinkItem = U::Item;
+    type SinkError = U::Error;
+
+    fn start_send(&mut self, item: U::Item) -> StartSend<U::Item, U::Error> {
+        // If the buffer is already over 8KiB, then attempt to flush it. If
+        // after flushing it's *still* over 8KiB, then apply backpressure
+        // (reject the send).
+        if self.wr.len() >= BACKPRESSURE_BOUNDARY {
+            try!(self.poll_complete());
+
+            if self.wr.len() >= BACKPRESSURE_BOUNDARY {
+                return Ok(AsyncSink::NotReady(item))
+            }
+        }
+
+        try!(self.codec.encode(item, &mut self.wr));
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), U::Error> {
+        while !self.wr.is_empty() {
+            let n = try_ready!(self.inner.poll_write(&self.wr));
+            if n == 0 {
+                return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                          "failed to write frame to transport").into())
+            }
+            self.wr.drain(..n);
+        }
+
+        // Try flushing the underlying IO
+        try_ready!(self.inner.poll_flush());
+        Ok(Async::Ready(()))
+    }
+
+    fn close(&mut self) -> Poll<(), U::Error> {
+        try_ready!(self.poll_complete());
+        Ok(try!(self.inner.shutdown()))
+    }
+}
+
+impl<T: fmt::Debug, U: fmt::Debug> fmt::Debug for Framed<T, U> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Framed")
+            .field("io", &self.inner)
+            .field("codec", &self.codec)
+            .finish()
+    }
+}
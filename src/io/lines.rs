@@ -0,0 +1,107 @@
+use std::io::{self, BufRead};
+use std::mem;
+use std::prelude::v1::*;
+
+use {Async, Poll, Stream};
+use io::AsyncRead;
+
+/// A stream of the lines of a buffered reader.
+///
+/// Created by the `lines` function.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Lines<A> {
+    io: A,
+    line: Vec<u8>,
+}
+
+/// Creates a new stream from the I/O object given representing the lines of
+/// input that are found on `A`.
+///
+/// This method takes an asynchronous I/O object, `a`, and returns a `Stream`
+/// of lines that the object contains. The returned stream will reach its end
+/// once `a` reaches EOF. The last line is yielded even if it isn't
+/// terminated by a newline.
+///
+/// Lines are split on `\n`, and a `\r` right before it is removed too, so
+/// both Unix and CRLF line endings are handled. Lines which aren't valid
+/// UTF-8 result in an error of kind `InvalidData`.
+///
+/// The object has to be buffered, typically by wrapping it in a `BufReader`.
+///
+/// # Examples
+///
+/// ```
+/// use futures::{Future, Stream};
+/// use futures::io;
+///
+/// let reader: &[u8] = b"hello\r\nworld\n!";
+/// let lines = io::lines(io::BufReader::new(reader));
+/// let lines = lines.collect().wait().unwrap();
+/// assert_eq!(lines, ["hello", "world", "!"]);
+/// ```
+pub fn lines<A>(a: A) -> Lines<A>
+    where A: AsyncRead + BufRead,
+{
+    Lines {
+        io: a,
+        line: Vec::new(),
+    }
+}
+
+impl<A> Lines<A> {
+    /// Returns the underlying I/O object.
+    ///
+    /// Note that any partial line which has been read but not yet yielded is
+    /// lost.
+    pub fn into_inner(self) -> A {
+        self.io
+    }
+}
+
+impl<A> Stream for Lines<A>
+    where A: AsyncRead + BufRead,
+{
+    type Item = String;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Option<String>, io::Error> {
+        loop {
+            let (done, used) = {
+                let available = try_nb!(self.io.fill_buf());
+                match available.iter().position(|b| *b == b'\n') {
+                    Some(i) => {
+                        self.line.extend_from_slice(&available[..i + 1]);
+                        (true, i + 1)
+                    }
+                    None => {
+                        self.line.extend_from_slice(available);
+                        (available.is_empty(), available.len())
+                    }
+                }
+            };
+            self.io.consume(used);
+            if done {
+                break
+            }
+        }
+
+        // We've hit EOF without any more data.
+        if self.line.is_empty() {
+            return Ok(Async::Ready(None))
+        }
+
+        if self.line.ends_with(b"\n") {
+            self.line.pop();
+            if self.line.ends_with(b"\r") {
+                self.line.pop();
+            }
+        }
+        let line = mem::replace(&mut self.line, Vec::new());
+        match String::from_utf8(line) {
+            Ok(line) => Ok(Async::Ready(Some(line))),
+            Err(_) => Err(io::Error::new(io::ErrorKind::InvalidData,
+                                         "line is not valid UTF-8")),
+        }
+    }
+}
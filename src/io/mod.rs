@@ -0,0 +1,181 @@
+//! Asynchronous I/O
+//!
+//! This module contains the `AsyncRead` and `AsyncWrite` traits, which are
+//! the "async" versions of `std::io::Read` and `std::io::Write`. They're
+//! implemented by I/O objects such as sockets and pipes, typically provided
+//! by an event loop, and allow combinators working with bytes to be shared
+//! across the ecosystem.
+//!
+//! The asynchronous traits build on the ones from the standard library: an
+//! object which isn't ready to be read from or written to returns an error
+//! of kind `WouldBlock`, after having arranged for the current task to be
+//! notified once it becomes ready. The `poll_*` methods translate such
+//! errors into `Async::NotReady`, so they can be used in futures directly.
+//!
+//! On top of these traits, this module provides futures for common tasks
+//! such as `copy`, `read_exact` and `write_all`, buffering with `BufReader`
+//! and `BufWriter`, and `framed`, which turns an I/O object into a `Stream`
+//! and `Sink` of frames given a `Decoder` and `Encoder`.
+//!
+//! This module is only available when the `use_std` feature of this library
+//! is activated, and it is activated by default.
+
+use std::boxed::Box;
+use std::io::{self, Read, Write};
+use std::prelude::v1::*;
+
+use {Async, Poll};
+
+pub use self::buf_reader::BufReader;
+pub use self::buf_writer::BufWriter;
+pub use self::codec::{Decoder, Encoder};
+pub use self::copy::{copy, Copy};
+pub use self::framed::{framed, Framed};
+pub use self::lines::{lines, Lines};
+pub use self::read_exact::{read_exact, ReadExact};
+pub use self::read_to_end::{read_to_end, ReadToEnd};
+pub use self::split::{split, ReadHalf, WriteHalf};
+pub use self::write_all::{write_all, WriteAll};
+
+/// A convenience macro for working with `io::Result<T>` from the `Read` and
+/// `Write` traits.
+///
+/// This macro takes `io::Result<T>` as input, and returns `T` as the output.
+/// If the input type is of the `Err` variant, then `Poll::NotReady` is
+/// returned if it indicates `WouldBlock` or otherwise `Err` is returned.
+#[macro_export]
+macro_rules! try_nb {
+    ($e:expr) => (match $e {
+        Ok(t) => t,
+        Err(ref e) if e.kind() == ::std::io::ErrorKind::WouldBlock => {
+            return Ok($crate::Async::NotReady)
+        }
+        Err(e) => return Err(e.into()),
+    })
+}
+
+mod buf_reader;
+mod buf_writer;
+mod codec;
+mod copy;
+mod framed;
+mod lines;
+mod read_exact;
+mod read_to_end;
+mod split;
+mod write_all;
+
+/// Read bytes asynchronously.
+///
+/// This trait inherits from `std::io::Read` and indicates that an I/O object
+/// is **non-blocking**, meaning that it will return an error of kind
+/// `WouldBlock` instead of blocking the current thread when bytes aren't
+/// available yet. In that case the object must also have arranged for the
+/// current task to be notified once it becomes readable, which means that
+/// reading from it should only ever happen from within the context of a task.
+pub trait AsyncRead: Read {
+    /// Attempts to read some bytes into `buf`, returning how many were read.
+    ///
+    /// This is the same as `Read::read`, except that a `WouldBlock` error is
+    /// translated into `Async::NotReady`. As with `read`, reading zero bytes
+    /// into a non-empty buffer indicates the end of the stream.
+    fn poll_read(&mut self, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        Ok(Async::Ready(try_nb!(self.read(buf))))
+    }
+}
+
+/// Writes bytes asynchronously.
+///
+/// This trait inherits from `std::io::Write` and indicates that an I/O
+/// object is **non-blocking**, meaning that it will return an error of kind
+/// `WouldBlock` instead of blocking the current thread when it can't accept
+/// more bytes yet. In that case the object must also have arranged for the
+/// current task to be notified once it becomes writable, which means that
+/// writing to it should only ever happen from within the context of a task.
+pub trait AsyncWrite: Write {
+    /// Attempts to write some bytes from `buf`, returning how many were
+    /// written.
+    ///
+    /// This is the same as `Write::write`, except that a `WouldBlock` error
+    /// is translated into `Async::NotReady`.
+    fn poll_write(&mut self, buf: &[u8]) -> Poll<usize, io::Error> {
+        Ok(Async::Ready(try_nb!(self.write(buf))))
+    }
+
+    /// Attempts to flush the object, ensuring that any buffered data reaches
+    /// its destination.
+    ///
+    /// This is the same as `Write::flush`, except that a `WouldBlock` error
+    /// is translated into `Async::NotReady`.
+    fn poll_flush(&mut self) -> Poll<(), io::Error> {
+        Ok(Async::Ready(try_nb!(self.flush())))
+    }
+
+    /// Initiates or attempts to shut down this writer, returning success
+    /// once the I/O connection has been completely shut down.
+    ///
+    /// This is intended for objects which need to do work before they're
+    /// dropped, such as sending a TLS close notification, and is the
+    /// asynchronous counterpart to closing a file. Once this has returned
+    /// `Ready`, no more data should be written. By default this simply
+    /// flushes the object.
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        self.poll_flush()
+    }
+}
+
+impl<'a, T: ?Sized + AsyncRead> AsyncRead for &'a mut T {
+    fn poll_read(&mut self, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        (**self).poll_read(buf)
+    }
+}
+
+impl<T: ?Sized + AsyncRead> AsyncRead for Box<T> {
+    fn poll_read(&mut self, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        (**self).poll_read(buf)
+    }
+}
+
+impl<'a> AsyncRead for &'a [u8] {}
+
+impl<T: AsRef<[u8]>> AsyncRead for io::Cursor<T> {}
+
+impl AsyncRead for io::Empty {}
+
+impl AsyncRead for io::Repeat {}
+
+impl<'a, T: ?Sized + AsyncWrite> AsyncWrite for &'a mut T {
+    fn poll_write(&mut self, buf: &[u8]) -> Poll<usize, io::Error> {
+        (**self).poll_write(buf)
+    }
+
+    fn poll_flush(&mut self) -> Poll<(), io::Error> {
+        (**self).poll_flush()
+    }
+
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        (**self).shutdown()
+    }
+}
+
+impl<T: ?Sized + AsyncWrite> AsyncWrite for Box<T> {
+    fn poll_write(&mut self, buf: &[u8]) -> Poll<usize, io::Error> {
+        (**self).poll_write(buf)
+    }
+
+    fn poll_flush(&mut self) -> Poll<(), io::Error> {
+        (**self).poll_flush()
+    }
+
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        (**self).shutdown()
+    }
+}
+
+impl AsyncWrite for Vec<u8> {}
+
+impl<'a> AsyncWrite for io::Cursor<&'a mut [u8]> {}
+
+impl AsyncWrite for io::Cursor<Vec<u8>> {}
+
+impl AsyncWrite for io::Sink {}
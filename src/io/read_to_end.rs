@@ -0,0 +1,105 @@
+use std::io;
+use std::mem;
+use std::prelude::v1::*;
+
+use {Async, Future, Poll};
+use io::AsyncRead;
+
+/// A future which can be used to easily read the entire contents of a stream
+/// into a vector.
+///
+/// Created by the `read_to_end` function.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct ReadToEnd<A> {
+    state: State<A>,
+}
+
+#[derive(Debug)]
+enum State<A> {
+    Reading {
+        a: A,
+        buf: Vec<u8>,
+    },
+    Empty,
+}
+
+/// Creates a future which will read all the bytes associated with the I/O
+/// object `A` into the buffer provided.
+///
+/// In the case of an error the buffer and the object will be discarded, with
+/// the error yielded. In the case of success both the object and the buffer
+/// will be returned, with all data read from the stream appended to the
+/// buffer.
+///
+/// The buffer's capacity is grown as needed, doubling it whenever it's full,
+/// so that reading a stream of `n` bytes only reallocates `O(log n)` times.
+/// As the whole stream is buffered in memory, this should only be used on
+/// streams whose length is known to be bounded.
+///
+/// # Examples
+///
+/// ```
+/// use futures::Future;
+/// use futures::io;
+///
+/// let reader: &[u8] = b"hello world";
+/// let (_, buf) = io::read_to_end(reader, b"> ".to_vec()).wait().unwrap();
+/// assert_eq!(buf, b"> hello world");
+/// ```
+pub fn read_to_end<A>(a: A, buf: Vec<u8>) -> ReadToEnd<A>
+    where A: AsyncRead,
+{
+    ReadToEnd {
+        state: State::Reading {
+            a: a,
+            buf: buf,
+        },
+    }
+}
+
+// Reads until EOF, making sure that `buf` only ever grows by the bytes which
+// were actually read, even when the read doesn't complete.
+fn read_to_end_internal<A: AsyncRead>(a: &mut A, buf: &mut Vec<u8>) -> Poll<(), io::Error> {
+    loop {
+        let len = buf.len();
+        if buf.capacity() - len < 32 {
+            buf.reserve(32);
+        }
+        let cap = buf.capacity();
+        buf.resize(cap, 0);
+
+        let res = a.poll_read(&mut buf[len..]);
+        let n = match res {
+            Ok(Async::Ready(n)) => n,
+            _ => 0,
+        };
+        buf.truncate(len + n);
+        match try!(res) {
+            Async::Ready(0) => return Ok(Async::Ready(())),
+            Async::Ready(_) => {}
+            Async::NotReady => return Ok(Async::NotReady),
+        }
+    }
+}
+
+impl<A> Future for ReadToEnd<A>
+    where A: AsyncRead,
+{
+    type Item = (A, Vec<u8>);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(A, Vec<u8>), io::Error> {
+        match self.state {
+            State::Reading { ref mut a, ref mut buf } => {
+                try_ready!(read_to_end_internal(a, buf));
+            }
+            State::Empty => panic!("poll ReadToEnd after it's done"),
+        }
+
+        match mem::replace(&mut self.state, State::Empty) {
+            State::Reading { a, buf } => Ok(Async::Ready((a, buf))),
+            State::Empty => panic!(),
+        }
+    }
+}
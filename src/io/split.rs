@@ -0,0 +1,124 @@
+use std::io::{self, Read, Write};
+
+use {Async, Poll};
+use io::{AsyncRead, AsyncWrite};
+use sync::BiLock;
+
+/// The readable half of an object returned from `io::split`.
+#[derive(Debug)]
+pub struct ReadHalf<T> {
+    handle: BiLock<T>,
+}
+
+/// The writable half of an object returned from `io::split`.
+#[derive(Debug)]
+pub struct WriteHalf<T> {
+    handle: BiLock<T>,
+}
+
+/// Splits a single value implementing `AsyncRead + AsyncWrite` into separate
+/// `AsyncRead` and `AsyncWrite` handles.
+///
+/// The two halves are backed by a `BiLock`, so they can be owned and used by
+/// different tasks, for example to read from and write to a socket at the
+/// same time. A half which finds the object locked by the other one returns
+/// `WouldBlock`, and its task is notified once the lock is released.
+///
+/// The original object can be recovered with `ReadHalf::unsplit`.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Cursor;
+/// use futures::Future;
+/// use futures::io;
+///
+/// let (reader, writer) = io::split(Cursor::new(vec![0; 4]));
+/// let (writer, _) = io::write_all(writer, b"ab").wait().unwrap();
+/// let (reader, buf) = io::read_exact(reader, [0; 2]).wait().unwrap();
+/// assert_eq!(buf, [0, 0]);
+///
+/// let cursor = reader.unsplit(writer);
+/// assert_eq!(cursor.into_inner(), [b'a', b'b', 0, 0]);
+/// ```
+pub fn split<T: AsyncRead + AsyncWrite>(t: T) -> (ReadHalf<T>, WriteHalf<T>) {
+    let (a, b) = BiLock::new(t);
+    (ReadHalf { handle: a }, WriteHalf { handle: b })
+}
+
+fn would_block() -> io::Error {
+    io::Error::new(io::ErrorKind::WouldBlock, "would block")
+}
+
+impl<T> ReadHalf<T> {
+    /// Reunites this half with the corresponding `WriteHalf`, returning the
+    /// original object.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `w` didn't come from the same call to
+    /// `io::split` as this half.
+    pub fn unsplit(self, w: WriteHalf<T>) -> T {
+        match self.handle.reunite(w.handle) {
+            Ok(t) => t,
+            Err(_) => panic!("unrelated `io::ReadHalf` and `io::WriteHalf` passed to unsplit"),
+        }
+    }
+}
+
+impl<T: AsyncRead> Read for ReadHalf<T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.handle.poll_lock() {
+            Async::Ready(mut l) => l.read(buf),
+            Async::NotReady => Err(would_block()),
+        }
+    }
+}
+
+impl<T: AsyncRead> AsyncRead for ReadHalf<T> {
+    fn poll_read(&mut self, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        match self.handle.poll_lock() {
+            Async::Ready(mut l) => l.poll_read(buf),
+            Async::NotReady => Ok(Async::NotReady),
+        }
+    }
+}
+
+impl<T: AsyncWrite> Write for WriteHalf<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.handle.poll_lock() {
+            Async::Ready(mut l) => l.write(buf),
+            Async::NotReady => Err(would_block()),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.handle.poll_lock() {
+            Async::Ready(mut l) => l.flush(),
+            Async::NotReady => Err(would_block()),
+        }
+    }
+}
+
+impl<T: AsyncWrite> AsyncWrite for WriteHalf<T> {
+    fn poll_write(&mut self, buf: &[u8]) -> Poll<usize, io::Error> {
+        match self.handle.poll_lock() {
+            Async::Ready(mut l) => l.poll_write(buf),
+            Async::NotReady => Ok(Async::NotReady),
+        }
+    }
+
+    fn poll_flush(&mut self) -> Poll<(), io::Error> {
+        match self.handle.poll_lock() {
+            Async::Ready(mut l) => l.poll_flush(),
+            Async::NotReady => Ok(Async::NotReady),
+        }
+    }
+
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        match self.handle.poll_lock() {
+            Async::Ready(mut l) => l.shutdown(),
+            Async::NotReady => Ok(Async::NotReady),
+        }
+    }
+}
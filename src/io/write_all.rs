@@ -0,0 +1,90 @@
+use std::io;
+use std::mem;
+
+use {Async, Future, Poll};
+use io::AsyncWrite;
+
+/// A future used to write the entire contents of some data to a stream.
+///
+/// Created by the `write_all` function.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct WriteAll<A, T> {
+    state: State<A, T>,
+}
+
+#[derive(Debug)]
+enum State<A, T> {
+    Writing {
+        a: A,
+        buf: T,
+        pos: usize,
+    },
+    Empty,
+}
+
+/// Creates a future that will write the entire contents of the buffer `buf`
+/// into the object `a`.
+///
+/// The returned future will not complete until all the data has been written,
+/// however many writes that takes. It will resolve to both the I/O object and
+/// the buffer once the write is completed, so that both can be reused.
+///
+/// A write which doesn't accept any bytes is considered an error, and causes
+/// the future to resolve to an error of kind `WriteZero`. Note that the data
+/// isn't flushed, which is left to the caller.
+///
+/// # Examples
+///
+/// ```
+/// use futures::Future;
+/// use futures::io;
+///
+/// let (writer, _) = io::write_all(Vec::new(), b"hello").wait().unwrap();
+/// assert_eq!(writer, b"hello");
+/// ```
+pub fn write_all<A, T>(a: A, buf: T) -> WriteAll<A, T>
+    where A: AsyncWrite,
+          T: AsRef<[u8]>,
+{
+    WriteAll {
+        state: State::Writing {
+            a: a,
+            buf: buf,
+            pos: 0,
+        },
+    }
+}
+
+fn zero_write() -> io::Error {
+    io::Error::new(io::ErrorKind::WriteZero, "zero-length write")
+}
+
+impl<A, T> Future for WriteAll<A, T>
+    where A: AsyncWrite,
+          T: AsRef<[u8]>,
+{
+    type Item = (A, T);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(A, T), io::Error> {
+        match self.state {
+            State::Writing { ref mut a, ref buf, ref mut pos } => {
+                let buf = buf.as_ref();
+                while *pos < buf.len() {
+                    let n = try_ready!(a.poll_write(&buf[*pos..]));
+                    *pos += n;
+                    if n == 0 {
+                        return Err(zero_write())
+                    }
+                }
+            }
+            State::Empty => panic!("poll a WriteAll after it's done"),
+        }
+
+        match mem::replace(&mut self.state, State::Empty) {
+            State::Writing { a, buf, .. } => Ok(Async::Ready((a, buf))),
+            State::Empty => panic!(),
+        }
+    }
+}
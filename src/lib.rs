@@ -200,11 +200,15 @@ if_std! {
     mod lock;
     mod task_impl;
     mod stack;
+    mod sync_impl;
 
     pub mod task;
     pub mod executor;
+    #[macro_use]
+    pub mod io;
     pub mod sync;
     pub mod unsync;
+    pub mod timer;
 
     #[doc(hidden)]
     #[deprecated(since = "0.1.4", note = "use sync::oneshot::channel instead")]
@@ -0,0 +1,146 @@
+use std::mem;
+use std::prelude::v1::*;
+use std::time::Duration;
+
+use {Poll, Async, Future, StartSend, AsyncSink};
+use sink::Sink;
+use timer::Timer;
+
+/// Sink for the `Sink::batch` combinator, which coalesces values into
+/// batches before sending them to the underlying sink.
+#[derive(Debug)]
+#[must_use = "sinks do nothing unless polled"]
+pub struct Batch<S, T, U>
+    where T: Timer,
+{
+    sink: S,
+    timer: T,
+    capacity: usize,
+    max_delay: Duration,
+    // Values waiting for the current batch to fill up.
+    items: Vec<U>,
+    // Started when the first value of the current batch comes in.
+    sleep: Option<T::Sleep>,
+    // A batch which the underlying sink wasn't ready to accept yet.
+    pending: Option<Vec<U>>,
+}
+
+pub fn new<S, T, U>(sink: S, capacity: usize, max_delay: Duration, timer: T)
+                    -> Batch<S, T, U>
+    where S: Sink<SinkItem = Vec<U>>,
+          T: Timer,
+          S::SinkError: From<<T::Sleep as Future>::Error>,
+{
+    assert!(capacity > 0);
+
+    Batch {
+        sink: sink,
+        timer: timer,
+        capacity: capacity,
+        max_delay: max_delay,
+        items: Vec::with_capacity(capacity),
+        sleep: None,
+        pending: None,
+    }
+}
+
+impl<S, T, U> Batch<S, T, U>
+    where T: Timer,
+{
+    /// Get a shared reference to the inner sink.
+    pub fn get_ref(&self) -> &S {
+        &self.sink
+    }
+
+    /// Get a mutable reference to the inner sink.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    /// Consumes this combinator, returning the underlying sink.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> S {
+        self.sink
+    }
+}
+
+impl<S, T, U> Batch<S, T, U>
+    where S: Sink<SinkItem = Vec<U>>,
+          T: Timer,
+          S::SinkError: From<<T::Sleep as Future>::Error>,
+{
+    // Sends batches to the underlying sink once they're full, once their
+    // deadline has passed or, if `force` is set, right away. Returns `Ready`
+    // once there's no batch waiting to be sent.
+    fn try_send(&mut self, force: bool) -> Poll<(), S::SinkError> {
+        loop {
+            if let Some(batch) = self.pending.take() {
+                if let AsyncSink::NotReady(batch) = try!(self.sink.start_send(batch)) {
+                    self.pending = Some(batch);
+                    return Ok(Async::NotReady)
+                }
+            }
+
+            if self.items.is_empty() {
+                return Ok(Async::Ready(()))
+            }
+
+            let due = force || self.items.len() >= self.capacity || match self.sleep {
+                Some(ref mut sleep) => try!(sleep.poll()).is_ready(),
+                None => true,
+            };
+            if !due {
+                return Ok(Async::Ready(()))
+            }
+            self.sleep = None;
+            let batch = mem::replace(&mut self.items, Vec::with_capacity(self.capacity));
+            self.pending = Some(batch);
+        }
+    }
+}
+
+impl<S, T, U> Sink for Batch<S, T, U>
+    where S: Sink<SinkItem = Vec<U>>,
+          T: Timer,
+          S::SinkError: From<<T::Sleep as Future>::Error>,
+{
+    type SinkItem = U;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: U) -> StartSend<U, S::SinkError> {
+        try!(self.try_send(false));
+        if self.items.len() >= self.capacity {
+            return Ok(AsyncSink::NotReady(item))
+        }
+
+        if self.items.is_empty() {
+            self.sleep = Some(self.timer.sleep(self.max_delay));
+        }
+        self.items.push(item);
+        if self.items.len() >= self.capacity {
+            try!(self.try_send(false));
+        }
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        try!(self.try_send(false));
+        // always propagate `poll_complete` downward to attempt to make progress
+        try_ready!(self.sink.poll_complete());
+
+        if self.items.is_empty() && self.pending.is_none() {
+            Ok(Async::Ready(()))
+        } else {
+            // The sleep has been polled, so we'll be woken up once the
+            // current batch is due.
+            Ok(Async::NotReady)
+        }
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        try_ready!(self.try_send(true));
+        self.sink.close()
+    }
+}
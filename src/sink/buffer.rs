@@ -75,8 +75,12 @@ impl<S: Sink> Sink for Buffer<S> {
     type SinkError = S::SinkError;
 
     fn start_send(&mut self, item: Self::SinkItem) -> StartSend<Self::SinkItem, Self::SinkError> {
+        if self.cap == 0 {
+            return self.sink.start_send(item);
+        }
+
         try!(self.try_empty_buffer());
-        if self.buf.len() > self.cap {
+        if self.buf.len() >= self.cap {
             return Ok(AsyncSink::NotReady(item));
         }
         self.buf.push_back(item);
@@ -0,0 +1,54 @@
+use core::marker;
+
+use {Poll, Async, StartSend, AsyncSink};
+use sink::Sink;
+
+/// Sink that discards all values sent to it.
+///
+/// This sink is created by the `sink::drain` function.
+#[derive(Debug)]
+#[must_use = "sinks do nothing unless polled"]
+pub struct Drain<T> {
+    _marker: marker::PhantomData<fn(T)>,
+}
+
+/// Creates a sink which accepts and discards every value sent to it, and is
+/// always ready.
+///
+/// This is useful as a destination for a stream which is only driven for its
+/// side effects, or to measure the throughput of a pipeline without any
+/// actual output.
+///
+/// # Examples
+///
+/// ```
+/// use futures::{Future, Stream};
+/// use futures::sink;
+/// use futures::stream;
+///
+/// let mut seen = Vec::new();
+/// let numbers = stream::iter_ok::<_, ()>(vec![1, 2, 3])
+///     .map(|x| { seen.push(x); x });
+/// numbers.forward(sink::drain()).wait().unwrap();
+/// assert_eq!(seen, vec![1, 2, 3]);
+/// ```
+pub fn drain<T>() -> Drain<T> {
+    Drain { _marker: marker::PhantomData }
+}
+
+impl<T> Sink for Drain<T> {
+    type SinkItem = T;
+    type SinkError = (); // Change this to ! once it stabilizes
+
+    fn start_send(&mut self, _item: T) -> StartSend<T, ()> {
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), ()> {
+        Ok(Async::Ready(()))
+    }
+
+    fn close(&mut self) -> Poll<(), ()> {
+        Ok(Async::Ready(()))
+    }
+}
@@ -0,0 +1,156 @@
+use core::fmt;
+
+use {Poll, Async, StartSend, AsyncSink};
+use sink::Sink;
+
+/// Sink for the `Sink::fanout` combinator, which clones each value into two
+/// sinks.
+#[must_use = "sinks do nothing unless polled"]
+pub struct Fanout<A, B>
+    where A: Sink,
+          B: Sink,
+{
+    left: Downstream<A>,
+    right: Downstream<B>,
+}
+
+// One of the sinks of a `Fanout`, along with the value it has yet to accept.
+#[derive(Debug)]
+struct Downstream<S>
+    where S: Sink,
+{
+    sink: S,
+    pending: Option<S::SinkItem>,
+}
+
+pub fn new<A, B>(left: A, right: B) -> Fanout<A, B>
+    where A: Sink,
+          A::SinkItem: Clone,
+          B: Sink<SinkItem = A::SinkItem, SinkError = A::SinkError>,
+{
+    Fanout {
+        left: Downstream { sink: left, pending: None },
+        right: Downstream { sink: right, pending: None },
+    }
+}
+
+impl<S> Downstream<S>
+    where S: Sink,
+{
+    fn is_ready(&self) -> bool {
+        self.pending.is_none()
+    }
+
+    fn send(&mut self, item: S::SinkItem) -> Result<(), S::SinkError> {
+        debug_assert!(self.pending.is_none());
+        if let AsyncSink::NotReady(item) = try!(self.sink.start_send(item)) {
+            self.pending = Some(item);
+        }
+        Ok(())
+    }
+
+    fn keep_sending(&mut self) -> Result<(), S::SinkError> {
+        match self.pending.take() {
+            Some(item) => self.send(item),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<A, B> Fanout<A, B>
+    where A: Sink,
+          B: Sink,
+{
+    /// Get a shared reference to the inner sinks.
+    pub fn get_ref(&self) -> (&A, &B) {
+        (&self.left.sink, &self.right.sink)
+    }
+
+    /// Get a mutable reference to the inner sinks.
+    pub fn get_mut(&mut self) -> (&mut A, &mut B) {
+        (&mut self.left.sink, &mut self.right.sink)
+    }
+
+    /// Consumes this combinator, returning the underlying sinks.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> (A, B) {
+        (self.left.sink, self.right.sink)
+    }
+}
+
+impl<A, B> Fanout<A, B>
+    where A: Sink,
+          A::SinkItem: Clone,
+          B: Sink<SinkItem = A::SinkItem, SinkError = A::SinkError>,
+{
+    // Retries the values which either sink wasn't ready for yet, returning
+    // `Ready` once both have accepted theirs.
+    fn keep_sending(&mut self) -> Poll<(), A::SinkError> {
+        try!(self.left.keep_sending());
+        try!(self.right.keep_sending());
+        if self.left.is_ready() && self.right.is_ready() {
+            Ok(Async::Ready(()))
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+}
+
+impl<A, B> Sink for Fanout<A, B>
+    where A: Sink,
+          A::SinkItem: Clone,
+          B: Sink<SinkItem = A::SinkItem, SinkError = A::SinkError>,
+{
+    type SinkItem = A::SinkItem;
+    type SinkError = A::SinkError;
+
+    fn start_send(&mut self, item: A::SinkItem) -> StartSend<A::SinkItem, A::SinkError> {
+        // A new value is only accepted once both sinks have accepted the
+        // previous one, so neither of them can fall behind by more than one.
+        if try!(self.keep_sending()).is_not_ready() {
+            return Ok(AsyncSink::NotReady(item))
+        }
+        try!(self.left.send(item.clone()));
+        try!(self.right.send(item));
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), A::SinkError> {
+        let me_ready = try!(self.keep_sending());
+        // always propagate `poll_complete` downward to attempt to make progress
+        let left_ready = try!(self.left.sink.poll_complete()).is_ready();
+        let right_ready = try!(self.right.sink.poll_complete()).is_ready();
+        if me_ready.is_ready() && left_ready && right_ready {
+            Ok(Async::Ready(()))
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+
+    fn close(&mut self) -> Poll<(), A::SinkError> {
+        try_ready!(self.keep_sending());
+        let left_ready = try!(self.left.sink.close()).is_ready();
+        let right_ready = try!(self.right.sink.close()).is_ready();
+        if left_ready && right_ready {
+            Ok(Async::Ready(()))
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+}
+
+impl<A, B> fmt::Debug for Fanout<A, B>
+    where A: Sink + fmt::Debug,
+          A::SinkItem: fmt::Debug,
+          B: Sink + fmt::Debug,
+          B::SinkItem: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Fanout")
+            .field("left", &self.left)
+            .field("right", &self.right)
+            .finish()
+    }
+}
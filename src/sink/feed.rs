@@ -0,0 +1,59 @@
+use {Poll, Async, Future, AsyncSink};
+use sink::Sink;
+
+/// Future for the `Sink::feed` combinator, which sends a value to a sink
+/// without flushing it.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct Feed<S: Sink> {
+    sink: Option<S>,
+    item: Option<S::SinkItem>,
+}
+
+pub fn new<S: Sink>(sink: S, item: S::SinkItem) -> Feed<S> {
+    Feed {
+        sink: Some(sink),
+        item: Some(item),
+    }
+}
+
+impl<S: Sink> Feed<S> {
+    /// Get a shared reference to the inner sink.
+    pub fn get_ref(&self) -> &S {
+        self.sink.as_ref().take().expect("Attempted Feed::get_ref after completion")
+    }
+
+    /// Get a mutable reference to the inner sink.
+    pub fn get_mut(&mut self) -> &mut S {
+        self.sink.as_mut().take().expect("Attempted Feed::get_mut after completion")
+    }
+
+    fn sink_mut(&mut self) -> &mut S {
+        self.sink.as_mut().take().expect("Attempted to poll Feed after completion")
+    }
+
+    fn take_sink(&mut self) -> S {
+        self.sink.take().expect("Attempted to poll Feed after completion")
+    }
+}
+
+impl<S: Sink> Future for Feed<S> {
+    type Item = S;
+    type Error = S::SinkError;
+
+    fn poll(&mut self) -> Poll<S, S::SinkError> {
+        if let Some(item) = self.item.take() {
+            if let AsyncSink::NotReady(item) = try!(self.sink_mut().start_send(item)) {
+                self.item = Some(item);
+
+                // make sure the sink keeps making progress on the items it
+                // has already accepted, so that it can make room for this one
+                try!(self.sink_mut().poll_complete());
+                return Ok(Async::NotReady)
+            }
+        }
+
+        // the item has been accepted, and flushing is left to the caller
+        Ok(Async::Ready(self.take_sink()))
+    }
+}
@@ -9,23 +9,42 @@
 //! [online]: https://tokio.rs/docs/getting-started/streams-and-sinks/
 
 use {IntoFuture, Poll, StartSend};
+use future::Either;
 use stream::Stream;
 
+if_std! {
+    use std::time::Duration;
+
+    use Future;
+    use timer::Timer;
+}
+
 mod with;
+mod with_flat_map;
 // mod with_map;
 // mod with_filter;
 // mod with_filter_map;
+mod drain;
+mod fanout;
+mod feed;
 mod flush;
 mod from_err;
 mod send;
 mod send_all;
 mod map_err;
+mod unfold;
 
 if_std! {
+    mod batch;
     mod buffer;
+    mod rate_limit;
+    mod retry;
     mod wait;
 
+    pub use self::batch::Batch;
     pub use self::buffer::Buffer;
+    pub use self::rate_limit::RateLimit;
+    pub use self::retry::{retry, Retry};
     pub use self::wait::Wait;
 
     // TODO: consider expanding this via e.g. FromIterator
@@ -49,6 +68,26 @@ if_std! {
         }
     }
 
+    impl<T> Sink for ::std::collections::VecDeque<T> {
+        type SinkItem = T;
+        type SinkError = (); // Change this to ! once it stabilizes
+
+        fn start_send(&mut self, item: Self::SinkItem)
+                      -> StartSend<Self::SinkItem, Self::SinkError>
+        {
+            self.push_back(item);
+            Ok(::AsyncSink::Ready)
+        }
+
+        fn poll_complete(&mut self) -> Poll<(), Self::SinkError> {
+            Ok(::Async::Ready(()))
+        }
+
+        fn close(&mut self) -> Poll<(), Self::SinkError> {
+            Ok(::Async::Ready(()))
+        }
+    }
+
     /// A type alias for `Box<Stream + Send>`
     pub type BoxSink<T, E> = ::std::boxed::Box<Sink<SinkItem = T, SinkError = E> +
                                                ::core::marker::Send>;
@@ -73,11 +112,16 @@ if_std! {
 }
 
 pub use self::with::With;
+pub use self::with_flat_map::WithFlatMap;
+pub use self::drain::{drain, Drain};
+pub use self::fanout::Fanout;
+pub use self::feed::Feed;
 pub use self::flush::Flush;
 pub use self::send::Send;
 pub use self::send_all::SendAll;
 pub use self::map_err::SinkMapErr;
 pub use self::from_err::SinkFromErr;
+pub use self::unfold::{unfold, Unfold};
 
 /// A `Sink` is a value into which other values can be sent, asynchronously.
 ///
@@ -315,6 +359,26 @@ pub trait Sink {
         with::new(self, f)
     }
 
+    /// Composes a function *in front of* the sink, which expands each value
+    /// into any number of values.
+    ///
+    /// This adapter produces a new sink that passes each value through the
+    /// given function `f`, which produces a *stream*. All items of that stream
+    /// are then sent to `self` in order, before the next value is accepted.
+    /// This is useful for example to split up a large message into several
+    /// frames. If the stream produces an error, that error is returned by the
+    /// new sink.
+    ///
+    /// Note that this function consumes the given sink, returning a wrapped
+    /// version, much like `Iterator::flat_map`.
+    fn with_flat_map<U, F, St>(self, f: F) -> WithFlatMap<Self, U, F, St>
+        where F: FnMut(U) -> St,
+              St: Stream<Item = Self::SinkItem, Error = Self::SinkError>,
+              Self: Sized
+    {
+        with_flat_map::new(self, f)
+    }
+
     /*
     fn with_map<U, F>(self, f: F) -> WithMap<Self, U, F>
         where F: FnMut(U) -> Self::SinkItem,
@@ -329,6 +393,61 @@ pub trait Sink {
               Self: Sized;
      */
 
+    /// Fans out items to multiple sinks.
+    ///
+    /// This adapter clones each incoming item and forwards it to both this as
+    /// well as the other sink at the same time. A new item is only accepted
+    /// once both sinks have accepted the previous one, so the fanout sink is
+    /// only ready when both of the downstream sinks are. Flushing and closing
+    /// the fanout sink flushes and closes both of them.
+    ///
+    /// More than two sinks can be fed by nesting calls to `fanout`.
+    fn fanout<S>(self, other: S) -> Fanout<Self, S>
+        where Self::SinkItem: Clone,
+              S: Sink<SinkItem = Self::SinkItem, SinkError = Self::SinkError>,
+              Self: Sized
+    {
+        fanout::new(self, other)
+    }
+
+    /// Wraps this sink in the `A` variant of an `Either`.
+    ///
+    /// `Either` implements `Sink` when both of its variants do, so this can be
+    /// used together with `right_sink` to pick one of two different kinds of
+    /// sinks at runtime, without boxing them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::{Future, Sink};
+    /// use futures::sync::mpsc;
+    ///
+    /// let (tx, _rx) = mpsc::channel(1);
+    /// let log_to_channel = true;
+    /// let sink = if log_to_channel {
+    ///     tx.sink_map_err(|_| ()).left_sink()
+    /// } else {
+    ///     Vec::new().right_sink()
+    /// };
+    /// sink.send("hello").wait().unwrap();
+    /// ```
+    fn left_sink<B>(self) -> Either<Self, B>
+        where B: Sink<SinkItem = Self::SinkItem, SinkError = Self::SinkError>,
+              Self: Sized
+    {
+        Either::A(self)
+    }
+
+    /// Wraps this sink in the `B` variant of an `Either`.
+    ///
+    /// This is the counterpart of `left_sink`.
+    fn right_sink<A>(self) -> Either<A, Self>
+        where A: Sink<SinkItem = Self::SinkItem, SinkError = Self::SinkError>,
+              Self: Sized
+    {
+        Either::B(self)
+    }
+
     /// Transforms the error returned by the sink.
     fn sink_map_err<F, E>(self, f: F) -> SinkMapErr<Self, F>
         where F: FnOnce(Self::SinkError) -> E,
@@ -351,9 +470,11 @@ pub trait Sink {
     /// Adds a fixed-size buffer to the current sink.
     ///
     /// The resulting sink will buffer up to `amt` items when the underlying
-    /// sink is unwilling to accept additional items. Calling `poll_complete` on
-    /// the buffered sink will attempt to both empty the buffer and complete
-    /// processing on the underlying sink.
+    /// sink is unwilling to accept additional items, and buffered items are
+    /// pushed into the underlying sink whenever a new item is sent. Calling
+    /// `poll_complete` on the buffered sink will attempt to both empty the
+    /// buffer and complete processing on the underlying sink. With an `amt`
+    /// of zero, items are sent straight to the underlying sink.
     ///
     /// Note that this function consumes the given sink, returning a wrapped
     /// version, much like `Iterator::map`.
@@ -367,6 +488,59 @@ pub trait Sink {
         buffer::new(self, amt)
     }
 
+    /// Coalesces values into batches of up to `capacity` values, which are
+    /// sent to the underlying sink as vectors.
+    ///
+    /// A batch is sent as soon as it's full, or once `max_delay` has passed
+    /// since its first value came in, whichever happens first. The deadline
+    /// is tracked with a sleep future from `timer`, so `poll_complete` only
+    /// completes once every batch has been sent, and in the meantime arranges
+    /// for the current task to be woken up when the deadline passes. Closing
+    /// the sink sends any partial batch straight away.
+    ///
+    /// This method is only available when the `use_std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `capacity` is zero.
+    #[cfg(feature = "use_std")]
+    fn batch<U, T>(self, capacity: usize, max_delay: Duration, timer: T) -> Batch<Self, T, U>
+        where Self: Sink<SinkItem = ::std::vec::Vec<U>> + Sized,
+              T: Timer,
+              Self::SinkError: From<<T::Sleep as Future>::Error>,
+    {
+        batch::new(self, capacity, max_delay, timer)
+    }
+
+    /// Limits the rate at which values are sent to the underlying sink using
+    /// a token bucket.
+    ///
+    /// The bucket holds up to `burst` tokens and starts out full. Sending a
+    /// value takes a token out of the bucket, and while the bucket isn't full
+    /// a token is added back every `period`, as measured by `timer`. When the
+    /// bucket is empty, `start_send` returns `NotReady` until a token is
+    /// available, pushing backpressure onto the producer rather than
+    /// buffering values.
+    ///
+    /// Errors from the timer are converted with `From` and returned from
+    /// `start_send`.
+    ///
+    /// This method is only available when the `use_std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `burst` is zero.
+    #[cfg(feature = "use_std")]
+    fn rate_limit<T>(self, period: Duration, burst: usize, timer: T) -> RateLimit<Self, T>
+        where T: Timer,
+              Self::SinkError: From<<T::Sleep as Future>::Error>,
+              Self: Sized
+    {
+        rate_limit::new(self, period, burst, timer)
+    }
+
     /// A future that completes when the sink has finished processing all
     /// pending requests.
     ///
@@ -383,8 +557,8 @@ pub trait Sink {
     /// into the sink, including flushing.
     ///
     /// Note that, **because of the flushing requirement, it is usually better
-    /// to batch together items to send via `send_all`, rather than flushing
-    /// between each item.**
+    /// to batch together items to send via `send_all` or `feed`, rather than
+    /// flushing between each item.**
     ///
     /// On completion, the sink is returned.
     fn send(self, item: Self::SinkItem) -> Send<Self>
@@ -393,6 +567,22 @@ pub trait Sink {
         send::new(self, item)
     }
 
+    /// A future that completes once the given item has been accepted by the
+    /// sink, without flushing it.
+    ///
+    /// Unlike `send`, the returned future doesn't wait for the sink to fully
+    /// process the item, so several items can be fed into the sink back to
+    /// back, followed by a single `flush`. While the sink isn't ready to
+    /// accept the item, the future does drive `poll_complete` to let the sink
+    /// make room for it.
+    ///
+    /// On completion, the sink is returned.
+    fn feed(self, item: Self::SinkItem) -> Feed<Self>
+        where Self: Sized
+    {
+        feed::new(self, item)
+    }
+
     /// A future that completes after the given stream has been fully processed
     /// into the sink, including flushing.
     ///
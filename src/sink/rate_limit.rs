@@ -0,0 +1,135 @@
+use std::time::Duration;
+
+use {Poll, Async, Future, StartSend, AsyncSink};
+use sink::Sink;
+use stream::Stream;
+use timer::Timer;
+
+/// Sink for the `Sink::rate_limit` combinator, which limits the rate at which
+/// values are sent to the underlying sink using a token bucket.
+#[derive(Debug)]
+#[must_use = "sinks do nothing unless polled"]
+pub struct RateLimit<S, T>
+    where T: Timer,
+{
+    sink: S,
+    timer: T,
+    period: Duration,
+    burst: usize,
+    tokens: usize,
+    refill: Option<T::Sleep>,
+}
+
+pub fn new<S, T>(sink: S, period: Duration, burst: usize, timer: T) -> RateLimit<S, T>
+    where S: Sink,
+          T: Timer,
+          S::SinkError: From<<T::Sleep as Future>::Error>,
+{
+    assert!(burst > 0);
+
+    RateLimit {
+        sink: sink,
+        timer: timer,
+        period: period,
+        burst: burst,
+        tokens: burst,
+        refill: None,
+    }
+}
+
+impl<S, T> RateLimit<S, T>
+    where T: Timer,
+{
+    /// Get a shared reference to the inner sink.
+    pub fn get_ref(&self) -> &S {
+        &self.sink
+    }
+
+    /// Get a mutable reference to the inner sink.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    /// Consumes this combinator, returning the underlying sink.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> S {
+        self.sink
+    }
+}
+
+impl<S, T> RateLimit<S, T>
+    where S: Sink,
+          T: Timer,
+          S::SinkError: From<<T::Sleep as Future>::Error>,
+{
+    // Add a token back into the bucket every `period`, for as long as the
+    // bucket isn't full.
+    fn refill(&mut self) -> Result<(), S::SinkError> {
+        loop {
+            if let Some(ref mut refill) = self.refill {
+                match refill.poll() {
+                    Ok(Async::Ready(())) => self.tokens += 1,
+                    Ok(Async::NotReady) => return Ok(()),
+                    Err(e) => {
+                        self.refill = None;
+                        return Err(From::from(e))
+                    }
+                }
+            }
+            self.refill = None;
+            if self.tokens >= self.burst {
+                return Ok(())
+            }
+            self.refill = Some(self.timer.sleep(self.period));
+        }
+    }
+}
+
+// Forwarding impl of Stream from the underlying sink
+impl<S, T> Stream for RateLimit<S, T>
+    where S: Stream,
+          T: Timer,
+{
+    type Item = S::Item;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<S::Item>, S::Error> {
+        self.sink.poll()
+    }
+}
+
+impl<S, T> Sink for RateLimit<S, T>
+    where S: Sink,
+          T: Timer,
+          S::SinkError: From<<T::Sleep as Future>::Error>,
+{
+    type SinkItem = S::SinkItem;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: S::SinkItem) -> StartSend<S::SinkItem, S::SinkError> {
+        try!(self.refill());
+        if self.tokens == 0 {
+            return Ok(AsyncSink::NotReady(item))
+        }
+
+        let ret = try!(self.sink.start_send(item));
+        if ret.is_ready() {
+            self.tokens -= 1;
+            // Start the refill timer right away so that we'll be woken up
+            // once a token is available again.
+            try!(self.refill());
+        }
+        Ok(ret)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        try!(self.refill());
+        self.sink.poll_complete()
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        self.sink.close()
+    }
+}
@@ -0,0 +1,249 @@
+use std::fmt;
+use std::prelude::v1::*;
+
+use {IntoFuture, Future, Poll, Async, StartSend, AsyncSink};
+use sink::Sink;
+
+/// Sink for the `sink::retry` function, which rebuilds the underlying sink
+/// whenever it fails.
+#[must_use = "sinks do nothing unless polled"]
+pub struct Retry<F, P, Fut>
+    where Fut: IntoFuture,
+          Fut::Item: Sink,
+{
+    factory: F,
+    policy: P,
+    state: State<Fut::Item, Fut::Future>,
+    // Values accepted since the last successful flush, which are sent again
+    // if the sink has to be rebuilt.
+    in_flight: Vec<<Fut::Item as Sink>::SinkItem>,
+    // How many of the values in `in_flight` the current sink has accepted.
+    sent: usize,
+}
+
+impl<F, P, Fut> fmt::Debug for Retry<F, P, Fut>
+    where F: fmt::Debug,
+          P: fmt::Debug,
+          Fut: IntoFuture,
+          Fut::Future: fmt::Debug,
+          Fut::Item: Sink + fmt::Debug,
+          <Fut::Item as Sink>::SinkItem: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Retry")
+            .field("factory", &self.factory)
+            .field("policy", &self.policy)
+            .field("state", &self.state)
+            .field("in_flight", &self.in_flight)
+            .field("sent", &self.sent)
+            .finish()
+    }
+}
+
+#[derive(Debug)]
+enum State<S, Fut> {
+    /// Waiting for the factory to produce a new sink
+    Connecting(Fut),
+
+    /// Sending values to a sink produced by the factory
+    Connected(S),
+}
+
+/// Creates a `Sink` which transparently rebuilds the underlying sink when it
+/// fails.
+///
+/// The underlying sink is produced by `factory`, which is called right away
+/// and then again every time the sink needs to be rebuilt. Whenever sending
+/// to or flushing the underlying sink fails, `policy` is called with the
+/// error. If it returns `true` the failed sink is dropped, a new one is
+/// requested from `factory`, and every value sent since the last successful
+/// flush is replayed into it. If it returns `false` the error is returned
+/// instead.
+///
+/// Values are kept around until they have been flushed, so they must be
+/// `Clone` and the sink should be flushed regularly. Errors of the futures
+/// returned by `factory` aren't retried and are returned by the sink; the
+/// sink can't be used any more afterwards.
+///
+/// # Examples
+///
+/// ```
+/// use futures::{Future, Sink};
+/// use futures::sink;
+/// use futures::stream;
+///
+/// let mut attempts = 0;
+/// let sink = sink::retry(|| Ok::<_, ()>(Vec::new()), move |_: &()| {
+///     attempts += 1;
+///     attempts <= 3
+/// });
+/// let (sink, _) = sink.send_all(stream::iter_ok(vec![1, 2, 3])).wait().unwrap();
+/// assert_eq!(sink.get_ref(), Some(&vec![1, 2, 3]));
+/// ```
+pub fn retry<F, P, Fut>(mut factory: F, policy: P) -> Retry<F, P, Fut>
+    where F: FnMut() -> Fut,
+          P: FnMut(&Fut::Error) -> bool,
+          Fut: IntoFuture,
+          Fut::Item: Sink<SinkError = Fut::Error>,
+          <Fut::Item as Sink>::SinkItem: Clone,
+{
+    let future = factory().into_future();
+    Retry {
+        factory: factory,
+        policy: policy,
+        state: State::Connecting(future),
+        in_flight: Vec::new(),
+        sent: 0,
+    }
+}
+
+impl<F, P, Fut> Retry<F, P, Fut>
+    where Fut: IntoFuture,
+          Fut::Item: Sink,
+{
+    /// Get a shared reference to the inner sink.
+    ///
+    /// Returns `None` while the sink is being rebuilt.
+    pub fn get_ref(&self) -> Option<&Fut::Item> {
+        match self.state {
+            State::Connected(ref sink) => Some(sink),
+            State::Connecting(_) => None,
+        }
+    }
+
+    /// Get a mutable reference to the inner sink.
+    ///
+    /// Returns `None` while the sink is being rebuilt.
+    pub fn get_mut(&mut self) -> Option<&mut Fut::Item> {
+        match self.state {
+            State::Connected(ref mut sink) => Some(sink),
+            State::Connecting(_) => None,
+        }
+    }
+
+    /// Consumes this combinator, returning the underlying sink.
+    ///
+    /// Returns `None` if the sink was being rebuilt. Note that this discards
+    /// values which haven't been flushed yet, so care should be taken to
+    /// avoid losing data when this is called.
+    pub fn into_inner(self) -> Option<Fut::Item> {
+        match self.state {
+            State::Connected(sink) => Some(sink),
+            State::Connecting(_) => None,
+        }
+    }
+}
+
+impl<F, P, Fut> Retry<F, P, Fut>
+    where F: FnMut() -> Fut,
+          P: FnMut(&Fut::Error) -> bool,
+          Fut: IntoFuture,
+          Fut::Item: Sink<SinkError = Fut::Error>,
+          <Fut::Item as Sink>::SinkItem: Clone,
+{
+    // Starts rebuilding the sink if the policy allows it, otherwise returns
+    // the error.
+    fn reconnect(&mut self, e: Fut::Error) -> Result<(), Fut::Error> {
+        if !(self.policy)(&e) {
+            return Err(e)
+        }
+        self.state = State::Connecting((self.factory)().into_future());
+        self.sent = 0;
+        Ok(())
+    }
+
+    // Waits for a sink to be available and makes sure it has accepted every
+    // value in `in_flight`.
+    fn poll_sink(&mut self) -> Poll<(), Fut::Error> {
+        loop {
+            let sink = match self.state {
+                State::Connecting(ref mut future) => try_ready!(future.poll()),
+                State::Connected(ref mut sink) => {
+                    let res = replay(sink, &self.in_flight, &mut self.sent);
+                    match res {
+                        Ok(ready) => return Ok(ready),
+                        Err(e) => {
+                            try!(self.reconnect(e));
+                            continue
+                        }
+                    }
+                }
+            };
+            self.state = State::Connected(sink);
+        }
+    }
+
+    fn connected(&mut self) -> &mut Fut::Item {
+        match self.state {
+            State::Connected(ref mut sink) => sink,
+            State::Connecting(_) => panic!("sink is not connected"),
+        }
+    }
+}
+
+fn replay<S>(sink: &mut S, items: &[S::SinkItem], sent: &mut usize)
+             -> Poll<(), S::SinkError>
+    where S: Sink,
+          S::SinkItem: Clone,
+{
+    while *sent < items.len() {
+        if try!(sink.start_send(items[*sent].clone())).is_not_ready() {
+            return Ok(Async::NotReady)
+        }
+        *sent += 1;
+    }
+    Ok(Async::Ready(()))
+}
+
+impl<F, P, Fut> Sink for Retry<F, P, Fut>
+    where F: FnMut() -> Fut,
+          P: FnMut(&Fut::Error) -> bool,
+          Fut: IntoFuture,
+          Fut::Item: Sink<SinkError = Fut::Error>,
+          <Fut::Item as Sink>::SinkItem: Clone,
+{
+    type SinkItem = <Fut::Item as Sink>::SinkItem;
+    type SinkError = Fut::Error;
+
+    fn start_send(&mut self, item: Self::SinkItem)
+                  -> StartSend<Self::SinkItem, Fut::Error> {
+        if try!(self.poll_sink()).is_not_ready() {
+            return Ok(AsyncSink::NotReady(item))
+        }
+        self.in_flight.push(item);
+        // The value is now in `in_flight`, so if the sink isn't ready for it
+        // yet it'll be sent on the next call.
+        try!(self.poll_sink());
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), Fut::Error> {
+        loop {
+            try_ready!(self.poll_sink());
+            match self.connected().poll_complete() {
+                Ok(Async::Ready(())) => {
+                    self.in_flight.clear();
+                    self.sent = 0;
+                    return Ok(Async::Ready(()))
+                }
+                Ok(Async::NotReady) => return Ok(Async::NotReady),
+                Err(e) => try!(self.reconnect(e)),
+            }
+        }
+    }
+
+    fn close(&mut self) -> Poll<(), Fut::Error> {
+        loop {
+            try_ready!(self.poll_sink());
+            match self.connected().close() {
+                Ok(Async::Ready(())) => {
+                    self.in_flight.clear();
+                    self.sent = 0;
+                    return Ok(Async::Ready(()))
+                }
+                Ok(Async::NotReady) => return Ok(Async::NotReady),
+                Err(e) => try!(self.reconnect(e)),
+            }
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use core::marker::PhantomData;
+
+use {IntoFuture, Future, Poll, Async, StartSend, AsyncSink};
+use sink::Sink;
+
+/// Sink for the `sink::unfold` function, which handles every value with a
+/// closure that threads some state through.
+#[derive(Debug)]
+#[must_use = "sinks do nothing unless polled"]
+pub struct Unfold<T, F, Fut, It>
+    where Fut: IntoFuture,
+{
+    f: F,
+    state: Option<T>,
+    future: Option<Fut::Future>,
+    _phantom: PhantomData<fn(It)>,
+}
+
+/// Creates a `Sink` from a seed state and a closure which handles each value
+/// sent to the sink.
+///
+/// For every value sent to the returned sink, `f` is called with the
+/// current state and the value, and returns a future. Once that future
+/// resolves, its result becomes the state for the next value. The sink only
+/// accepts a new value once the future for the previous one has completed,
+/// and flushing the sink waits for the last future to complete.
+///
+/// If one of the futures fails, its error is returned by the sink, and the
+/// sink can't be used any more afterwards.
+///
+/// This makes it easy to build one-off sinks out of asynchronous operations,
+/// such as making a request for each item, without implementing `Sink` by
+/// hand.
+///
+/// # Examples
+///
+/// ```
+/// use futures::{Future, Sink};
+/// use futures::sink;
+/// use futures::stream;
+///
+/// let sum = sink::unfold(0, |total, x: u32| Ok::<_, ()>(total + x));
+/// let (sum, _) = sum.send_all(stream::iter_ok(vec![1, 2, 3])).wait().unwrap();
+/// assert_eq!(sum.get_ref(), Some(&6));
+/// ```
+pub fn unfold<T, F, Fut, It>(init: T, f: F) -> Unfold<T, F, Fut, It>
+    where F: FnMut(T, It) -> Fut,
+          Fut: IntoFuture<Item = T>,
+{
+    Unfold {
+        f: f,
+        state: Some(init),
+        future: None,
+        _phantom: PhantomData,
+    }
+}
+
+impl<T, F, Fut, It> Unfold<T, F, Fut, It>
+    where Fut: IntoFuture,
+{
+    /// Get a shared reference to the current state.
+    ///
+    /// Returns `None` while a value is being processed, or if processing a
+    /// value failed.
+    pub fn get_ref(&self) -> Option<&T> {
+        self.state.as_ref()
+    }
+
+    /// Get a mutable reference to the current state.
+    ///
+    /// Returns `None` while a value is being processed, or if processing a
+    /// value failed.
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        self.state.as_mut()
+    }
+
+    /// Consumes this sink, returning the current state.
+    ///
+    /// Returns `None` if a value was still being processed, or if processing
+    /// a value failed.
+    pub fn into_inner(self) -> Option<T> {
+        self.state
+    }
+}
+
+impl<T, F, Fut, It> Unfold<T, F, Fut, It>
+    where F: FnMut(T, It) -> Fut,
+          Fut: IntoFuture<Item = T>,
+{
+    // Drives the future for the previous value, if any, to completion.
+    fn poll(&mut self) -> Poll<(), Fut::Error> {
+        let state = match self.future {
+            Some(ref mut future) => {
+                match future.poll() {
+                    Ok(Async::Ready(state)) => state,
+                    Ok(Async::NotReady) => return Ok(Async::NotReady),
+                    Err(e) => {
+                        self.future = None;
+                        return Err(e)
+                    }
+                }
+            }
+            None => return Ok(Async::Ready(())),
+        };
+        self.future = None;
+        self.state = Some(state);
+        Ok(Async::Ready(()))
+    }
+}
+
+impl<T, F, Fut, It> Sink for Unfold<T, F, Fut, It>
+    where F: FnMut(T, It) -> Fut,
+          Fut: IntoFuture<Item = T>,
+{
+    type SinkItem = It;
+    type SinkError = Fut::Error;
+
+    fn start_send(&mut self, item: It) -> StartSend<It, Fut::Error> {
+        if try!(self.poll()).is_not_ready() {
+            return Ok(AsyncSink::NotReady(item))
+        }
+        let state = self.state.take().expect("cannot use unfold sink after an error");
+        self.future = Some((self.f)(state, item).into_future());
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), Fut::Error> {
+        self.poll()
+    }
+
+    fn close(&mut self) -> Poll<(), Fut::Error> {
+        self.poll()
+    }
+}
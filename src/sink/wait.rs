@@ -47,4 +47,16 @@ impl<S: Sink> Wait<S> {
     pub fn flush(&mut self) -> Result<(), S::SinkError> {
         self.sink.wait_flush()
     }
+
+    /// Closes this sink, blocking the current thread until it's entirely
+    /// closed.
+    ///
+    /// This function will call the underlying sink's `close` method until it
+    /// returns that it's ready to proceed, giving the sink the chance to
+    /// flush any buffered data and run its shutdown logic. If the method
+    /// returns `NotReady` the current thread will be blocked until it's
+    /// otherwise ready to proceed.
+    pub fn close(&mut self) -> Result<(), S::SinkError> {
+        self.sink.wait_close()
+    }
 }
@@ -0,0 +1,121 @@
+use core::marker::PhantomData;
+
+use {Poll, Async, StartSend, AsyncSink};
+use sink::Sink;
+use stream::Stream;
+
+/// Sink for the `Sink::with_flat_map` combinator, expanding each value into a
+/// stream of values which are all pushed into the underlying sink.
+#[derive(Debug)]
+#[must_use = "sinks do nothing unless polled"]
+pub struct WithFlatMap<S, U, F, St>
+    where S: Sink,
+{
+    sink: S,
+    f: F,
+    stream: Option<St>,
+    buffer: Option<S::SinkItem>,
+    _phantom: PhantomData<fn(U)>,
+}
+
+pub fn new<S, U, F, St>(sink: S, f: F) -> WithFlatMap<S, U, F, St>
+    where S: Sink,
+          F: FnMut(U) -> St,
+          St: Stream<Item = S::SinkItem, Error = S::SinkError>,
+{
+    WithFlatMap {
+        sink: sink,
+        f: f,
+        stream: None,
+        buffer: None,
+        _phantom: PhantomData,
+    }
+}
+
+// Forwarding impl of Stream from the underlying sink
+impl<S, U, F, St> Stream for WithFlatMap<S, U, F, St>
+    where S: Stream + Sink,
+{
+    type Item = S::Item;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<S::Item>, S::Error> {
+        self.sink.poll()
+    }
+}
+
+impl<S, U, F, St> WithFlatMap<S, U, F, St>
+    where S: Sink,
+          F: FnMut(U) -> St,
+          St: Stream<Item = S::SinkItem, Error = S::SinkError>,
+{
+    /// Get a shared reference to the inner sink.
+    pub fn get_ref(&self) -> &S {
+        &self.sink
+    }
+
+    /// Get a mutable reference to the inner sink.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    /// Consumes this combinator, returning the underlying sink.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> S {
+        self.sink
+    }
+
+    // Pushes the items of the current stream into the underlying sink,
+    // returning `Ready` once the stream is exhausted.
+    fn poll(&mut self) -> Poll<(), S::SinkError> {
+        loop {
+            if let Some(item) = self.buffer.take() {
+                if let AsyncSink::NotReady(item) = try!(self.sink.start_send(item)) {
+                    self.buffer = Some(item);
+                    return Ok(Async::NotReady)
+                }
+            }
+
+            let next = match self.stream {
+                Some(ref mut stream) => try_ready!(stream.poll()),
+                None => return Ok(Async::Ready(())),
+            };
+            match next {
+                Some(item) => self.buffer = Some(item),
+                None => self.stream = None,
+            }
+        }
+    }
+}
+
+impl<S, U, F, St> Sink for WithFlatMap<S, U, F, St>
+    where S: Sink,
+          F: FnMut(U) -> St,
+          St: Stream<Item = S::SinkItem, Error = S::SinkError>,
+{
+    type SinkItem = U;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: U) -> StartSend<U, S::SinkError> {
+        if try!(self.poll()).is_not_ready() {
+            return Ok(AsyncSink::NotReady(item))
+        }
+        self.stream = Some((self.f)(item));
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        // poll ourselves first, to push data downward
+        let me_ready = try!(self.poll());
+        // always propagate `poll_complete` downward to attempt to make progress
+        try_ready!(self.sink.poll_complete());
+        Ok(me_ready)
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        try_ready!(self.poll());
+        self.sink.close()
+    }
+}
@@ -0,0 +1,55 @@
+use {Async, Future, IntoFuture, Poll};
+use stream::Stream;
+
+/// A future which checks whether a predicate is satisfied by all of the items
+/// of a stream.
+///
+/// This structure is returned by the `Stream::all` method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct All<S, P, R> where R: IntoFuture {
+    stream: S,
+    pred: P,
+    pending: Option<R::Future>,
+}
+
+pub fn new<S, P, R>(s: S, pred: P) -> All<S, P, R>
+    where S: Stream,
+          P: FnMut(S::Item) -> R,
+          R: IntoFuture<Item=bool, Error=S::Error>,
+{
+    All {
+        stream: s,
+        pred: pred,
+        pending: None,
+    }
+}
+
+impl<S, P, R> Future for All<S, P, R>
+    where S: Stream,
+          P: FnMut(S::Item) -> R,
+          R: IntoFuture<Item=bool, Error=S::Error>,
+{
+    type Item = bool;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<bool, S::Error> {
+        loop {
+            if let Some(mut fut) = self.pending.take() {
+                match try!(fut.poll()) {
+                    Async::Ready(false) => return Ok(Async::Ready(false)),
+                    Async::Ready(_) => {}
+                    Async::NotReady => {
+                        self.pending = Some(fut);
+                        return Ok(Async::NotReady)
+                    }
+                }
+            }
+
+            match try_ready!(self.stream.poll()) {
+                Some(e) => self.pending = Some((self.pred)(e).into_future()),
+                None => return Ok(Async::Ready(true)),
+            }
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use std::mem;
+use std::prelude::v1::*;
+
+use {Async, Poll};
+use stream::{Stream, Fuse};
+
+/// An adaptor that batches up elements in a vector until their combined
+/// weight reaches a limit.
+///
+/// This is created by the `Stream::batch_weighted` method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct BatchWeighted<S, F>
+    where S: Stream
+{
+    items: Vec<S::Item>,
+    weight: usize,
+    max_weight: usize,
+    err: Option<S::Error>,
+    stream: Fuse<S>,
+    f: F,
+}
+
+pub fn new<S, F>(s: S, max_weight: usize, f: F) -> BatchWeighted<S, F>
+    where S: Stream,
+          F: FnMut(&S::Item) -> usize,
+{
+    assert!(max_weight > 0);
+
+    BatchWeighted {
+        items: Vec::new(),
+        weight: 0,
+        max_weight: max_weight,
+        err: None,
+        stream: super::fuse::new(s),
+        f: f,
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+impl<S, F> ::sink::Sink for BatchWeighted<S, F>
+    where S: ::sink::Sink + Stream
+{
+    type SinkItem = S::SinkItem;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: S::SinkItem) -> ::StartSend<S::SinkItem, S::SinkError> {
+        self.stream.start_send(item)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.poll_complete()
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.close()
+    }
+}
+
+impl<S, F> BatchWeighted<S, F> where S: Stream {
+    fn take(&mut self) -> Vec<S::Item> {
+        self.weight = 0;
+        mem::replace(&mut self.items, Vec::new())
+    }
+
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &S {
+        self.stream.get_ref()
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> &mut S {
+        self.stream.get_mut()
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> S {
+        self.stream.into_inner()
+    }
+}
+
+impl<S, F> Stream for BatchWeighted<S, F>
+    where S: Stream,
+          F: FnMut(&S::Item) -> usize,
+{
+    type Item = Vec<S::Item>;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        if let Some(err) = self.err.take() {
+            return Err(err)
+        }
+
+        // An item which overflowed the previous batch may fill the next one
+        // all by itself, in which case it's yielded without waiting on the
+        // stream.
+        if self.items.len() > 0 && self.weight >= self.max_weight {
+            return Ok(Some(self.take()).into())
+        }
+
+        loop {
+            match self.stream.poll() {
+                Ok(Async::NotReady) => return Ok(Async::NotReady),
+
+                // If this item would take the batch over the limit then the
+                // batch so far is yielded and the item starts the next one.
+                // Otherwise it's added, and the batch is yielded once it has
+                // reached the limit exactly.
+                Ok(Async::Ready(Some(item))) => {
+                    let weight = (self.f)(&item);
+                    if self.items.len() > 0 &&
+                       self.weight.saturating_add(weight) > self.max_weight {
+                        let batch = self.take();
+                        self.weight = weight;
+                        self.items.push(item);
+                        return Ok(Some(batch).into())
+                    }
+                    self.weight = self.weight.saturating_add(weight);
+                    self.items.push(item);
+                    if self.weight >= self.max_weight {
+                        return Ok(Some(self.take()).into())
+                    }
+                }
+
+                Ok(Async::Ready(None)) => {
+                    return if self.items.len() > 0 {
+                        Ok(Some(self.take()).into())
+                    } else {
+                        Ok(Async::Ready(None))
+                    }
+                }
+
+                // If we've got buffered items be sure to return them first,
+                // we'll defer our error for later.
+                Err(e) => {
+                    if self.items.len() == 0 {
+                        return Err(e)
+                    } else {
+                        self.err = Some(e);
+                        return Ok(Some(self.take()).into())
+                    }
+                }
+            }
+        }
+    }
+}
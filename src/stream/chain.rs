@@ -54,4 +54,20 @@ impl<S1, S2> Stream for Chain<S1, S2>
             };
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.state {
+            State::First(ref s1, ref s2) => {
+                let (lower1, upper1) = s1.size_hint();
+                let (lower2, upper2) = s2.size_hint();
+                let upper = match (upper1, upper2) {
+                    (Some(a), Some(b)) => a.checked_add(b),
+                    _ => None,
+                };
+                (lower1.saturating_add(lower2), upper)
+            }
+            State::Second(ref s2) => s2.size_hint(),
+            State::Temp => (0, Some(0)),
+        }
+    }
 }
@@ -132,4 +132,21 @@ impl<S> Stream for Chunks<S>
             }
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let cap = self.items.capacity();
+        let buffered = self.items.len();
+        let err = if self.err.is_some() { 1 } else { 0 };
+        let (lower, upper) = self.stream.size_hint();
+
+        // Every chunk but the last one is full, and each chunk holds at least
+        // one item.
+        let lower = lower.saturating_add(buffered);
+        let lower = lower / cap + if lower % cap > 0 { 1 } else { 0 };
+        let upper = upper.and_then(|n| {
+            let partial = if buffered > 0 { 1 } else { 0 };
+            n.checked_add(partial + err)
+        });
+        (lower.saturating_add(err), upper)
+    }
 }
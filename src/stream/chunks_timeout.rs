@@ -0,0 +1,171 @@
+use std::mem;
+use std::prelude::v1::*;
+use std::time::Duration;
+
+use {Async, Future, Poll};
+use stream::{Stream, Fuse};
+use timer::Timer;
+
+/// An adaptor that chunks up elements in a vector, yielding them once either
+/// enough items have been buffered or a timeout has elapsed.
+///
+/// This is created by the `Stream::chunks_timeout` method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct ChunksTimeout<S, T>
+    where S: Stream,
+          T: Timer,
+{
+    items: Vec<S::Item>,
+    err: Option<S::Error>,
+    stream: Fuse<S>,
+    timer: T,
+    duration: Duration,
+    sleep: Option<T::Sleep>,
+}
+
+pub fn new<S, T>(s: S, capacity: usize, duration: Duration, timer: T) -> ChunksTimeout<S, T>
+    where S: Stream,
+          T: Timer,
+          S::Error: From<<T::Sleep as Future>::Error>,
+{
+    assert!(capacity > 0);
+
+    ChunksTimeout {
+        items: Vec::with_capacity(capacity),
+        err: None,
+        stream: super::fuse::new(s),
+        timer: timer,
+        duration: duration,
+        sleep: None,
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+impl<S, T> ::sink::Sink for ChunksTimeout<S, T>
+    where S: ::sink::Sink + Stream,
+          T: Timer,
+{
+    type SinkItem = S::SinkItem;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: S::SinkItem) -> ::StartSend<S::SinkItem, S::SinkError> {
+        self.stream.start_send(item)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.poll_complete()
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.close()
+    }
+}
+
+impl<S, T> ChunksTimeout<S, T>
+    where S: Stream,
+          T: Timer,
+{
+    fn take(&mut self) -> Vec<S::Item> {
+        let cap = self.items.capacity();
+        self.sleep = None;
+        mem::replace(&mut self.items, Vec::with_capacity(cap))
+    }
+
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &S {
+        self.stream.get_ref()
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> &mut S {
+        self.stream.get_mut()
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> S {
+        self.stream.into_inner()
+    }
+}
+
+impl<S, T> Stream for ChunksTimeout<S, T>
+    where S: Stream,
+          T: Timer,
+          S::Error: From<<T::Sleep as Future>::Error>,
+{
+    type Item = Vec<S::Item>;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        if let Some(err) = self.err.take() {
+            return Err(err)
+        }
+
+        let cap = self.items.capacity();
+        loop {
+            match self.stream.poll() {
+                Ok(Async::NotReady) => break,
+
+                // Push the item into the buffer, starting the timeout if this
+                // is the first item of a new chunk, and check whether the
+                // buffer is full.
+                Ok(Async::Ready(Some(item))) => {
+                    if self.items.is_empty() {
+                        self.sleep = Some(self.timer.sleep(self.duration));
+                    }
+                    self.items.push(item);
+                    if self.items.len() >= cap {
+                        return Ok(Some(self.take()).into())
+                    }
+                }
+
+                // Since the underlying stream ran out of values, return what we
+                // have buffered, if we have anything.
+                Ok(Async::Ready(None)) => {
+                    return if self.items.is_empty() {
+                        Ok(Async::Ready(None))
+                    } else {
+                        Ok(Some(self.take()).into())
+                    }
+                }
+
+                // If we've got buffered items be sure to return them first,
+                // we'll defer our error for later.
+                Err(e) => {
+                    if self.items.is_empty() {
+                        return Err(e)
+                    } else {
+                        self.err = Some(e);
+                        return Ok(Some(self.take()).into())
+                    }
+                }
+            }
+        }
+
+        if self.items.is_empty() {
+            return Ok(Async::NotReady)
+        }
+
+        // If the timer previously failed there's nothing to wait on, so the
+        // partial chunk is flushed straight away.
+        if let Some(ref mut sleep) = self.sleep {
+            match sleep.poll() {
+                Ok(Async::Ready(())) => {}
+                Ok(Async::NotReady) => return Ok(Async::NotReady),
+                Err(e) => {
+                    self.sleep = None;
+                    return Err(From::from(e))
+                }
+            }
+        }
+        Ok(Some(self.take()).into())
+    }
+}
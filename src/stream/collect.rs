@@ -5,41 +5,60 @@ use std::mem;
 use {Future, Poll, Async};
 use stream::Stream;
 
-/// A future which collects all of the values of a stream into a vector.
+/// A future which collects all of the values of a stream into a collection,
+/// a vector by default.
 ///
-/// This future is created by the `Stream::collect` method.
+/// This future is created by the `Stream::collect` and `Stream::collect_as`
+/// methods.
 #[derive(Debug)]
 #[must_use = "streams do nothing unless polled"]
-pub struct Collect<S> where S: Stream {
+pub struct Collect<S, C = Vec<<S as Stream>::Item>> where S: Stream {
     stream: S,
-    items: Vec<S::Item>,
+    items: C,
 }
 
-pub fn new<S>(s: S) -> Collect<S>
+pub fn new<S, C>(s: S) -> Collect<S, C>
     where S: Stream,
+          C: Default + Extend<S::Item>,
 {
     Collect {
         stream: s,
-        items: Vec::new(),
+        items: C::default(),
     }
 }
 
-impl<S: Stream> Collect<S> {
-    fn finish(&mut self) -> Vec<S::Item> {
-        mem::replace(&mut self.items, Vec::new())
+// Vectors are common enough that they get to reserve space for the items of
+// the stream up front, based on its size hint.
+pub fn new_vec<S>(s: S) -> Collect<S>
+    where S: Stream,
+{
+    let (lower, _) = s.size_hint();
+    Collect {
+        stream: s,
+        items: Vec::with_capacity(lower),
+    }
+}
+
+impl<S, C> Collect<S, C>
+    where S: Stream,
+          C: Default + Extend<S::Item>,
+{
+    fn finish(&mut self) -> C {
+        mem::replace(&mut self.items, C::default())
     }
 }
 
-impl<S> Future for Collect<S>
+impl<S, C> Future for Collect<S, C>
     where S: Stream,
+          C: Default + Extend<S::Item>,
 {
-    type Item = Vec<S::Item>;
+    type Item = C;
     type Error = S::Error;
 
-    fn poll(&mut self) -> Poll<Vec<S::Item>, S::Error> {
+    fn poll(&mut self) -> Poll<C, S::Error> {
         loop {
             match self.stream.poll() {
-                Ok(Async::Ready(Some(e))) => self.items.push(e),
+                Ok(Async::Ready(Some(e))) => self.items.extend(Some(e)),
                 Ok(Async::Ready(None)) => return Ok(Async::Ready(self.finish())),
                 Ok(Async::NotReady) => return Ok(Async::NotReady),
                 Err(e) => {
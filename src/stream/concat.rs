@@ -60,6 +60,69 @@ impl<S> Future for Concat<S>
     }
 }
 
+/// A stream combinator to concatenate the results of a stream into the first
+/// yielded item, or a default value if the stream is empty.
+///
+/// This structure is produced by the `Stream::concat2` method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Concat2<S>
+    where S: Stream,
+{
+    stream: S,
+    extend: Inner<S::Item>,
+}
+
+pub fn new2<S>(s: S) -> Concat2<S>
+    where S: Stream,
+          S::Item: Extend<<<S as Stream>::Item as IntoIterator>::Item> + IntoIterator + Default,
+{
+    Concat2 {
+        stream: s,
+        extend: Inner::First,
+    }
+}
+
+impl<S> Future for Concat2<S>
+    where S: Stream,
+          S::Item: Extend<<<S as Stream>::Item as IntoIterator>::Item> + IntoIterator + Default,
+
+{
+    type Item = S::Item;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            match self.stream.poll() {
+                Ok(Async::Ready(Some(i))) => {
+                    match self.extend {
+                        Inner::First => {
+                            self.extend = Inner::Extending(i);
+                        },
+                        Inner::Extending(ref mut e) => {
+                            e.extend(i);
+                        },
+                        Inner::Done => unreachable!(),
+                    }
+                },
+                Ok(Async::Ready(None)) => {
+                    let res = match mem::replace(&mut self.extend, Inner::Done) {
+                        Inner::First => Default::default(),
+                        Inner::Extending(e) => e,
+                        Inner::Done => panic!("cannot poll Concat2 again"),
+                    };
+                    return Ok(Async::Ready(res))
+                },
+                Ok(Async::NotReady) => return Ok(Async::NotReady),
+                Err(e) => {
+                    self.extend.take();
+                    return Err(e)
+                }
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 enum Inner<E> {
     First,
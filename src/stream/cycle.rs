@@ -0,0 +1,100 @@
+use {Poll, Async};
+use stream::Stream;
+
+/// A stream combinator which repeats the underlying stream endlessly.
+///
+/// This structure is produced by the `Stream::cycle` method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Cycle<S> {
+    orig: S,
+    stream: S,
+    empty: bool,
+}
+
+pub fn new<S>(s: S) -> Cycle<S>
+    where S: Stream + Clone,
+{
+    Cycle {
+        orig: s.clone(),
+        stream: s,
+        empty: true,
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+impl<S> ::sink::Sink for Cycle<S>
+    where S: ::sink::Sink
+{
+    type SinkItem = S::SinkItem;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: S::SinkItem) -> ::StartSend<S::SinkItem, S::SinkError> {
+        self.stream.start_send(item)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.poll_complete()
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.close()
+    }
+}
+
+impl<S> Cycle<S> {
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S> Stream for Cycle<S>
+    where S: Stream + Clone,
+{
+    type Item = S::Item;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<S::Item>, S::Error> {
+        loop {
+            match self.stream.poll() {
+                Ok(Async::Ready(Some(item))) => {
+                    self.empty = false;
+                    return Ok(Async::Ready(Some(item)))
+                }
+                Err(e) => {
+                    self.empty = false;
+                    return Err(e)
+                }
+                Ok(Async::Ready(None)) => {
+                    // Restarting a stream which produced nothing at all would
+                    // just spin forever, so that ends the cycle instead.
+                    if self.empty {
+                        return Ok(Async::Ready(None))
+                    }
+                    self.stream = self.orig.clone();
+                    self.empty = true;
+                }
+                Ok(Async::NotReady) => return Ok(Async::NotReady),
+            }
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use {Async, Future, Poll};
+use stream::{Stream, Fuse};
+use timer::Timer;
+
+/// A stream combinator which only yields an item once the underlying stream
+/// has been quiet for a period of time.
+///
+/// This structure is produced by the `Stream::debounce` method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Debounce<S, T>
+    where S: Stream,
+          T: Timer,
+{
+    stream: Fuse<S>,
+    timer: T,
+    duration: Duration,
+    item: Option<S::Item>,
+    sleep: Option<T::Sleep>,
+}
+
+pub fn new<S, T>(s: S, duration: Duration, timer: T) -> Debounce<S, T>
+    where S: Stream,
+          T: Timer,
+          S::Error: From<<T::Sleep as Future>::Error>,
+{
+    Debounce {
+        stream: s.fuse(),
+        timer: timer,
+        duration: duration,
+        item: None,
+        sleep: None,
+    }
+}
+
+impl<S, T> Debounce<S, T>
+    where S: Stream,
+          T: Timer,
+{
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &S {
+        self.stream.get_ref()
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> &mut S {
+        self.stream.get_mut()
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> S {
+        self.stream.into_inner()
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+impl<S, T> ::sink::Sink for Debounce<S, T>
+    where S: ::sink::Sink + Stream,
+          T: Timer,
+{
+    type SinkItem = S::SinkItem;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: S::SinkItem) -> ::StartSend<S::SinkItem, S::SinkError> {
+        self.stream.start_send(item)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.poll_complete()
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.close()
+    }
+}
+
+impl<S, T> Stream for Debounce<S, T>
+    where S: Stream,
+          T: Timer,
+          S::Error: From<<T::Sleep as Future>::Error>,
+{
+    type Item = S::Item;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<S::Item>, S::Error> {
+        // Drain everything the underlying stream has ready, restarting the
+        // quiet period on every new item. Errors are not debounced and are
+        // passed through immediately.
+        loop {
+            match try!(self.stream.poll()) {
+                Async::Ready(Some(item)) => {
+                    self.item = Some(item);
+                    self.sleep = Some(self.timer.sleep(self.duration));
+                }
+                Async::Ready(None) => {
+                    self.sleep = None;
+                    return Ok(Async::Ready(self.item.take()))
+                }
+                Async::NotReady => break,
+            }
+        }
+
+        if self.item.is_none() {
+            return Ok(Async::NotReady)
+        }
+
+        // If the timer previously failed there's nothing to wait on, so the
+        // pending item is flushed straight away.
+        if let Some(ref mut sleep) = self.sleep {
+            match sleep.poll() {
+                Ok(Async::Ready(())) => {}
+                Ok(Async::NotReady) => return Ok(Async::NotReady),
+                Err(e) => {
+                    self.sleep = None;
+                    return Err(From::from(e))
+                }
+            }
+        }
+        self.sleep = None;
+        Ok(Async::Ready(self.item.take()))
+    }
+}
@@ -0,0 +1,180 @@
+use {Async, Poll};
+use stream::Stream;
+
+/// A stream combinator which suppresses consecutive duplicate items, as
+/// determined by a closure.
+///
+/// This structure is produced by the `Stream::dedup_by` method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct DedupBy<S, F>
+    where S: Stream,
+{
+    stream: S,
+    same: F,
+    last: Option<S::Item>,
+}
+
+/// A stream combinator which suppresses consecutive duplicate items.
+///
+/// This structure is produced by the `Stream::dedup` method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Dedup<S>
+    where S: Stream,
+{
+    inner: DedupBy<S, fn(&S::Item, &S::Item) -> bool>,
+}
+
+pub fn new_by<S, F>(s: S, same: F) -> DedupBy<S, F>
+    where S: Stream,
+          S::Item: Clone,
+          F: FnMut(&S::Item, &S::Item) -> bool,
+{
+    DedupBy {
+        stream: s,
+        same: same,
+        last: None,
+    }
+}
+
+pub fn new<S>(s: S) -> Dedup<S>
+    where S: Stream,
+          S::Item: Clone + PartialEq,
+{
+    Dedup {
+        inner: new_by(s, <S::Item as PartialEq>::eq),
+    }
+}
+
+impl<S, F> DedupBy<S, F>
+    where S: Stream,
+{
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S> Dedup<S>
+    where S: Stream,
+{
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &S {
+        self.inner.get_ref()
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> &mut S {
+        self.inner.get_mut()
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> S {
+        self.inner.into_inner()
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+impl<S, F> ::sink::Sink for DedupBy<S, F>
+    where S: ::sink::Sink + Stream,
+{
+    type SinkItem = S::SinkItem;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: S::SinkItem) -> ::StartSend<S::SinkItem, S::SinkError> {
+        self.stream.start_send(item)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.poll_complete()
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.close()
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+impl<S> ::sink::Sink for Dedup<S>
+    where S: ::sink::Sink + Stream,
+{
+    type SinkItem = S::SinkItem;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: S::SinkItem) -> ::StartSend<S::SinkItem, S::SinkError> {
+        self.inner.start_send(item)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        self.inner.poll_complete()
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        self.inner.close()
+    }
+}
+
+impl<S, F> Stream for DedupBy<S, F>
+    where S: Stream,
+          S::Item: Clone,
+          F: FnMut(&S::Item, &S::Item) -> bool,
+{
+    type Item = S::Item;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<S::Item>, S::Error> {
+        loop {
+            let item = match try_ready!(self.stream.poll()) {
+                Some(item) => item,
+                None => return Ok(Async::Ready(None)),
+            };
+            let duplicate = match self.last {
+                Some(ref last) => (self.same)(last, &item),
+                None => false,
+            };
+            if !duplicate {
+                self.last = Some(item.clone());
+                return Ok(Async::Ready(Some(item)))
+            }
+        }
+    }
+}
+
+impl<S> Stream for Dedup<S>
+    where S: Stream,
+          S::Item: Clone + PartialEq,
+{
+    type Item = S::Item;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<S::Item>, S::Error> {
+        self.inner.poll()
+    }
+}
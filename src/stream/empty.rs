@@ -15,6 +15,14 @@ pub struct Empty<T, E> {
 /// Creates a stream which contains no elements.
 ///
 /// The returned stream will always return `Ready(None)` when polled.
+///
+/// ```rust
+/// use futures::*;
+///
+/// let mut stream = stream::empty::<i32, ()>();
+/// assert_eq!(Ok(Async::Ready(None)), stream.poll());
+/// assert_eq!(Ok(Async::Ready(None)), stream.poll());
+/// ```
 pub fn empty<T, E>() -> Empty<T, E> {
     Empty { _data: marker::PhantomData }
 }
@@ -26,4 +34,8 @@ impl<T, E> Stream for Empty<T, E> {
     fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
         Ok(Async::Ready(None))
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(0))
+    }
 }
@@ -0,0 +1,190 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::mem;
+use std::prelude::v1::*;
+use std::sync::{Arc, Mutex};
+
+use {Async, Poll};
+use stream::{Stream, Fuse};
+use task::{self, Task};
+
+/// What a `FanOut` stream does with a new item when one of its subscribers
+/// already has a full buffer.
+///
+/// This is passed to the `Stream::fan_out` method.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LagPolicy {
+    /// Stop pulling items from the underlying stream until the lagging
+    /// subscriber has made room in its buffer.
+    Block,
+    /// Drop the oldest item in the lagging subscriber's buffer to make room
+    /// for the new one.
+    DropOldest,
+}
+
+/// One of the handles of a stream which has been fanned out to several
+/// subscribers, each of which receives every item.
+///
+/// These handles are produced by the `Stream::fan_out` method.
+#[must_use = "streams do nothing unless polled"]
+pub struct FanOut<S>
+    where S: Stream,
+{
+    id: usize,
+    inner: Arc<Mutex<Inner<S>>>,
+}
+
+struct Inner<S>
+    where S: Stream,
+{
+    stream: Fuse<S>,
+    // One buffer per subscriber, or `None` if that subscriber was dropped.
+    buffers: Vec<Option<VecDeque<Result<S::Item, S::Error>>>>,
+    capacity: usize,
+    policy: LagPolicy,
+    // Tasks which were blocked, either on a full buffer or on the underlying
+    // stream, keyed by the id of the subscriber which was polled. All of
+    // these are woken whenever a new item arrives or room is made in a
+    // buffer.
+    blocked: HashMap<usize, Task>,
+}
+
+pub fn new<S>(s: S, n: usize, capacity: usize, policy: LagPolicy) -> Vec<FanOut<S>>
+    where S: Stream,
+          S::Item: Clone,
+          S::Error: Clone,
+{
+    assert!(capacity > 0);
+
+    let inner = Arc::new(Mutex::new(Inner {
+        stream: super::fuse::new(s),
+        buffers: (0..n).map(|_| Some(VecDeque::with_capacity(capacity))).collect(),
+        capacity: capacity,
+        policy: policy,
+        blocked: HashMap::new(),
+    }));
+    (0..n).map(|id| {
+        FanOut {
+            id: id,
+            inner: inner.clone(),
+        }
+    }).collect()
+}
+
+impl<S> Inner<S>
+    where S: Stream,
+{
+    fn unpark_blocked(&mut self) {
+        for (_, task) in mem::replace(&mut self.blocked, HashMap::new()) {
+            task.unpark();
+        }
+    }
+
+    // Blocks the current task, replacing any task previously blocked on the
+    // same subscriber so that repeated polls don't accumulate.
+    fn park(&mut self, id: usize) {
+        self.blocked.insert(id, task::park());
+    }
+}
+
+impl<S> Inner<S>
+    where S: Stream,
+          S::Item: Clone,
+          S::Error: Clone,
+{
+    fn poll(&mut self, id: usize) -> Poll<Option<S::Item>, S::Error> {
+        loop {
+            let next = self.buffers[id].as_mut().and_then(|b| b.pop_front());
+            if let Some(next) = next {
+                self.unpark_blocked();
+                return next.map(|item| Async::Ready(Some(item)))
+            }
+
+            if self.stream.is_done() {
+                return Ok(Async::Ready(None))
+            }
+
+            if self.policy == LagPolicy::Block {
+                let capacity = self.capacity;
+                let full = self.buffers.iter().any(|b| {
+                    b.as_ref().map(|b| b.len() >= capacity).unwrap_or(false)
+                });
+                if full {
+                    self.park(id);
+                    return Ok(Async::NotReady)
+                }
+            }
+
+            let next = match self.stream.poll() {
+                Ok(Async::Ready(Some(item))) => Ok(item),
+                Ok(Async::Ready(None)) => {
+                    self.unpark_blocked();
+                    return Ok(Async::Ready(None))
+                }
+                Ok(Async::NotReady) => {
+                    self.park(id);
+                    return Ok(Async::NotReady)
+                }
+                Err(e) => Err(e),
+            };
+            for buffer in self.buffers.iter_mut() {
+                if let Some(ref mut buffer) = *buffer {
+                    if buffer.len() >= self.capacity {
+                        buffer.pop_front();
+                    }
+                    buffer.push_back(next.clone());
+                }
+            }
+            self.unpark_blocked();
+        }
+    }
+}
+
+impl<S> FanOut<S>
+    where S: Stream,
+{
+    /// Returns the number of items buffered for this subscriber.
+    pub fn len(&self) -> usize {
+        let inner = self.inner.lock().unwrap();
+        inner.buffers[self.id].as_ref().map(|b| b.len()).unwrap_or(0)
+    }
+
+    /// Returns `true` if no items are buffered for this subscriber.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<S> Stream for FanOut<S>
+    where S: Stream,
+          S::Item: Clone,
+          S::Error: Clone,
+{
+    type Item = S::Item;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<S::Item>, S::Error> {
+        self.inner.lock().unwrap().poll(self.id)
+    }
+}
+
+impl<S> Drop for FanOut<S>
+    where S: Stream,
+{
+    fn drop(&mut self) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.buffers[self.id] = None;
+            inner.unpark_blocked();
+        }
+    }
+}
+
+impl<S> fmt::Debug for FanOut<S>
+    where S: Stream,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("FanOut")
+            .field("id", &self.id)
+            .finish()
+    }
+}
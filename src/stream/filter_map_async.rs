@@ -0,0 +1,109 @@
+use {IntoFuture, Future, Poll, Async};
+use stream::Stream;
+
+/// A stream combinator which filters and maps the values of a stream with an
+/// asynchronous computation.
+///
+/// This structure is produced by the `Stream::filter_map_async` method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct FilterMapAsync<S, F, U>
+    where U: IntoFuture,
+{
+    stream: S,
+    future: Option<U::Future>,
+    f: F,
+}
+
+pub fn new<S, F, U, B>(s: S, f: F) -> FilterMapAsync<S, F, U>
+    where S: Stream,
+          F: FnMut(S::Item) -> U,
+          U: IntoFuture<Item=Option<B>, Error=S::Error>,
+{
+    FilterMapAsync {
+        stream: s,
+        future: None,
+        f: f,
+    }
+}
+
+impl<S, F, U> FilterMapAsync<S, F, U>
+    where U: IntoFuture,
+{
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+impl<S, F, U: IntoFuture> ::sink::Sink for FilterMapAsync<S, F, U>
+    where S: ::sink::Sink
+{
+    type SinkItem = S::SinkItem;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: S::SinkItem) -> ::StartSend<S::SinkItem, S::SinkError> {
+        self.stream.start_send(item)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.poll_complete()
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.close()
+    }
+}
+
+impl<S, F, U, B> Stream for FilterMapAsync<S, F, U>
+    where S: Stream,
+          F: FnMut(S::Item) -> U,
+          U: IntoFuture<Item=Option<B>, Error=S::Error>,
+{
+    type Item = B;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<B>, S::Error> {
+        loop {
+            if self.future.is_none() {
+                let item = match try_ready!(self.stream.poll()) {
+                    None => return Ok(Async::Ready(None)),
+                    Some(e) => e,
+                };
+                self.future = Some((self.f)(item).into_future());
+            }
+            assert!(self.future.is_some());
+            match self.future.as_mut().unwrap().poll() {
+                Ok(Async::Ready(Some(e))) => {
+                    self.future = None;
+                    return Ok(Async::Ready(Some(e)))
+                }
+                Ok(Async::Ready(None)) => self.future = None,
+                Err(e) => {
+                    self.future = None;
+                    return Err(e)
+                }
+                Ok(Async::NotReady) => return Ok(Async::NotReady)
+            }
+        }
+    }
+}
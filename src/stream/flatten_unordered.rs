@@ -0,0 +1,141 @@
+use std::fmt;
+
+use {Async, Poll};
+use stream::{Stream, Fuse, SelectAll};
+
+/// A combinator used to flatten a stream-of-streams into one stream of
+/// elements, polling a number of inner streams concurrently.
+///
+/// This combinator is created by the `Stream::flatten_unordered` method.
+#[must_use = "streams do nothing unless polled"]
+pub struct FlattenUnordered<S>
+    where S: Stream,
+          S::Item: Stream,
+{
+    stream: Fuse<S>,
+    inner: SelectAll<S::Item>,
+    limit: usize,
+}
+
+impl<S> fmt::Debug for FlattenUnordered<S>
+    where S: Stream + fmt::Debug,
+          S::Item: Stream + fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("FlattenUnordered")
+            .field("stream", &self.stream)
+            .field("inner", &self.inner)
+            .field("limit", &self.limit)
+            .finish()
+    }
+}
+
+pub fn new<S>(s: S, limit: usize) -> FlattenUnordered<S>
+    where S: Stream,
+          S::Item: Stream,
+          <S::Item as Stream>::Error: From<S::Error>,
+{
+    assert!(limit > 0);
+
+    FlattenUnordered {
+        stream: super::fuse::new(s),
+        inner: SelectAll::new(),
+        limit: limit,
+    }
+}
+
+impl<S> FlattenUnordered<S>
+    where S: Stream,
+          S::Item: Stream,
+{
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &S {
+        self.stream.get_ref()
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> &mut S {
+        self.stream.get_mut()
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> S {
+        self.stream.into_inner()
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+impl<S> ::sink::Sink for FlattenUnordered<S>
+    where S: ::sink::Sink + Stream,
+          S::Item: Stream,
+{
+    type SinkItem = S::SinkItem;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: S::SinkItem) -> ::StartSend<S::SinkItem, S::SinkError> {
+        self.stream.start_send(item)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.poll_complete()
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.close()
+    }
+}
+
+impl<S> Stream for FlattenUnordered<S>
+    where S: Stream,
+          S::Item: Stream,
+          <S::Item as Stream>::Error: From<S::Error>,
+{
+    type Item = <S::Item as Stream>::Item;
+    type Error = <S::Item as Stream>::Error;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        loop {
+            // First up, pull in as many inner streams as we're allowed to run
+            // at once.
+            let mut full = false;
+            loop {
+                if self.inner.len() >= self.limit {
+                    full = true;
+                    break
+                }
+                match try!(self.stream.poll()) {
+                    Async::Ready(Some(s)) => self.inner.push(s),
+                    Async::Ready(None) |
+                    Async::NotReady => break,
+                }
+            }
+
+            match try!(self.inner.poll()) {
+                Async::Ready(Some(item)) => return Ok(Async::Ready(Some(item))),
+                Async::Ready(None) |
+                Async::NotReady => {}
+            }
+
+            // If an inner stream finished while we were at the limit then the
+            // outer stream hasn't been polled yet, so there may be another
+            // inner stream ready to take its place.
+            if full && self.inner.len() < self.limit {
+                continue
+            }
+
+            return Ok(if self.inner.is_empty() && self.stream.is_done() {
+                Async::Ready(None)
+            } else {
+                Async::NotReady
+            })
+        }
+    }
+}
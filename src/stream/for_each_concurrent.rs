@@ -0,0 +1,183 @@
+use std::fmt;
+
+use {Async, Future, IntoFuture, Poll};
+use stream::{Stream, Fuse, FuturesUnordered};
+
+/// A stream combinator which executes a unit closure over each item on a
+/// stream, running up to a given number of the resulting futures at once.
+///
+/// This structure is returned by the `Stream::for_each_concurrent` method.
+#[must_use = "futures do nothing unless polled"]
+pub struct ForEachConcurrent<S, F, U>
+    where S: Stream,
+          U: IntoFuture,
+{
+    stream: Fuse<S>,
+    f: F,
+    futures: FuturesUnordered<U::Future>,
+    limit: usize,
+}
+
+impl<S, F, U> fmt::Debug for ForEachConcurrent<S, F, U>
+    where S: Stream + fmt::Debug,
+          U: IntoFuture,
+          U::Future: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("ForEachConcurrent")
+            .field("stream", &self.stream)
+            .field("futures", &self.futures)
+            .field("limit", &self.limit)
+            .finish()
+    }
+}
+
+pub fn new<S, F, U>(s: S, limit: usize, f: F) -> ForEachConcurrent<S, F, U>
+    where S: Stream,
+          F: FnMut(S::Item) -> U,
+          U: IntoFuture<Item = (), Error = S::Error>,
+{
+    assert!(limit > 0);
+
+    ForEachConcurrent {
+        stream: super::fuse::new(s),
+        f: f,
+        futures: FuturesUnordered::new(),
+        limit: limit,
+    }
+}
+
+impl<S, F, U> Future for ForEachConcurrent<S, F, U>
+    where S: Stream,
+          F: FnMut(S::Item) -> U,
+          U: IntoFuture<Item = (), Error = S::Error>,
+{
+    type Item = ();
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<(), S::Error> {
+        loop {
+            // First up, start running as many futures as we're allowed to.
+            while self.futures.len() < self.limit {
+                match try!(self.stream.poll()) {
+                    Async::Ready(Some(item)) => {
+                        self.futures.push((self.f)(item).into_future());
+                    }
+                    Async::Ready(None) |
+                    Async::NotReady => break,
+                }
+            }
+
+            match try!(self.futures.poll()) {
+                // A future finished, which may have freed up room for another
+                // one, so go back to the stream.
+                Async::Ready(Some(())) => {}
+                Async::Ready(None) => {
+                    return Ok(if self.stream.is_done() {
+                        Async::Ready(())
+                    } else {
+                        Async::NotReady
+                    })
+                }
+                Async::NotReady => return Ok(Async::NotReady),
+            }
+        }
+    }
+}
+
+/// A stream combinator which executes a fallible unit closure over each item
+/// on a stream, running up to a given number of the resulting futures at
+/// once, and which waits for running futures to complete after an error.
+///
+/// This structure is returned by the `Stream::try_for_each_concurrent`
+/// method.
+#[must_use = "futures do nothing unless polled"]
+pub struct TryForEachConcurrent<S, F, U>
+    where S: Stream,
+          U: IntoFuture,
+{
+    stream: Fuse<S>,
+    f: F,
+    futures: FuturesUnordered<U::Future>,
+    limit: usize,
+    err: Option<S::Error>,
+}
+
+impl<S, F, U> fmt::Debug for TryForEachConcurrent<S, F, U>
+    where S: Stream + fmt::Debug,
+          S::Error: fmt::Debug,
+          U: IntoFuture,
+          U::Future: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("TryForEachConcurrent")
+            .field("stream", &self.stream)
+            .field("futures", &self.futures)
+            .field("limit", &self.limit)
+            .field("err", &self.err)
+            .finish()
+    }
+}
+
+pub fn try_new<S, F, U>(s: S, limit: usize, f: F) -> TryForEachConcurrent<S, F, U>
+    where S: Stream,
+          F: FnMut(S::Item) -> U,
+          U: IntoFuture<Item = (), Error = S::Error>,
+{
+    assert!(limit > 0);
+
+    TryForEachConcurrent {
+        stream: super::fuse::new(s),
+        f: f,
+        futures: FuturesUnordered::new(),
+        limit: limit,
+        err: None,
+    }
+}
+
+impl<S, F, U> Future for TryForEachConcurrent<S, F, U>
+    where S: Stream,
+          F: FnMut(S::Item) -> U,
+          U: IntoFuture<Item = (), Error = S::Error>,
+{
+    type Item = ();
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<(), S::Error> {
+        loop {
+            // Once an error has happened no more items are pulled from the
+            // stream, but the running futures are still driven to completion.
+            while self.err.is_none() && self.futures.len() < self.limit {
+                match self.stream.poll() {
+                    Ok(Async::Ready(Some(item))) => {
+                        self.futures.push((self.f)(item).into_future());
+                    }
+                    Ok(Async::Ready(None)) |
+                    Ok(Async::NotReady) => break,
+                    Err(e) => self.err = Some(e),
+                }
+            }
+
+            match self.futures.poll() {
+                Ok(Async::Ready(Some(()))) => {}
+                // Only the first error is kept, later ones are dropped.
+                Err(e) => {
+                    if self.err.is_none() {
+                        self.err = Some(e);
+                    }
+                }
+                Ok(Async::Ready(None)) => {
+                    if let Some(e) = self.err.take() {
+                        return Err(e)
+                    }
+                    return Ok(if self.stream.is_done() {
+                        Async::Ready(())
+                    } else {
+                        Async::NotReady
+                    })
+                }
+                Ok(Async::NotReady) => return Ok(Async::NotReady),
+            }
+        }
+    }
+}
@@ -59,6 +59,10 @@ impl<S: Stream, E: From<S::Error>> Stream for FromErr<S, E> {
         };
         e.map_err(From::from)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
 }
 
 // Forwarding impl of Sink from the underlying stream
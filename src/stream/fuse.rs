@@ -52,6 +52,14 @@ impl<S: Stream> Stream for Fuse<S> {
             r
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            self.stream.size_hint()
+        }
+    }
 }
 
 impl<S> Fuse<S> {
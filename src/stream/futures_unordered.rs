@@ -3,28 +3,62 @@ use stream::Stream;
 use poll::Poll;
 use Async;
 use stack::{Stack, Drain};
+use std::mem;
 use std::sync::Arc;
 use task::{self, UnparkEvent};
 
 use std::prelude::v1::*;
 
-/// An adaptor for a stream of futures to execute the futures concurrently, if
-/// possible, delivering results as they become available.
+/// A set of futures which may complete in any order.
 ///
-/// This adaptor will return their results in the order that they complete.
-/// This is created by the `futures` method.
+/// This structure is optimized to manage a large number of futures. Futures
+/// managed by `FuturesUnordered` will only be polled when they generate
+/// notifications. This reduces the required amount of work needed to coordinate
+/// large numbers of futures.
 ///
+/// When a `FuturesUnordered` is first created, it does not contain any futures.
+/// Calling `poll` in this state will result in `Ok(Async::Ready(None))` to be
+/// returned. Futures are submitted to the set using `push`; however, the
+/// future will **not** be polled at this point. `FuturesUnordered` will only
+/// poll managed futures when `FuturesUnordered::poll` is called. As such, it
+/// is important to call `poll` after pushing new futures.
+///
+/// If `FuturesUnordered::poll` returns `Ok(Async::Ready(None))` this means that
+/// the set is currently not managing any futures. A future may be submitted
+/// to the set at a later time. At that point, a call to
+/// `FuturesUnordered::poll` will either return the future's resolved value
+/// **or** `Ok(Async::NotReady)` if the future has not yet completed.
+///
+/// Note that you can create a ready-made `FuturesUnordered` via the
+/// `futures_unordered` function in the `stream` module, or you can start with
+/// an empty set with the `FuturesUnordered::new` constructor.
 #[derive(Debug)]
 #[must_use = "streams do nothing unless polled"]
 pub struct FuturesUnordered<F>
     where F: Future
 {
-    futures: Vec<Option<F>>,
+    // A slab of futures that are being executed. Each slot in this vector is
+    // either an active future or a pointer to the next empty slot, which gives
+    // O(1) insertion and removal. If `next_slot` points to the end of the
+    // vector then there are no free slots and the vector is grown.
+    futures: Vec<Slot<F>>,
+    next_slot: usize,
+
+    // Notifications generated by the futures above, each identified by its
+    // index in the slab. See `BufferUnordered` for more information.
     stack: Arc<Stack<usize>>,
     pending: Option<Drain<usize>>,
+
+    // Number of futures currently in the slab
     active: usize,
 }
 
+#[derive(Debug)]
+enum Slot<T> {
+    Next(usize),
+    Data(T),
+}
+
 /// Converts a list of futures into a `Stream` of results from the futures.
 ///
 /// This function will take an list of futures (e.g. a vector, an iterator,
@@ -32,50 +66,92 @@ pub struct FuturesUnordered<F>
 /// available on the futures internally, in the order that they become
 /// available. This function is similar to `buffer_unordered` in that it may
 /// return items in a different order than in the list specified.
+///
+/// Note that the returned set can also be used to dynamically push more
+/// futures into the set as they become available.
 pub fn futures_unordered<I>(futures: I) -> FuturesUnordered<<I::Item as IntoFuture>::Future>
     where I: IntoIterator,
           I::Item: IntoFuture
 {
-    let futures = futures.into_iter()
-                         .map(IntoFuture::into_future)
-                         .map(Some)
-                         .collect::<Vec<_>>();
-    let stack = Arc::new(Stack::new());
-    for i in 0..futures.len() {
-        stack.push(i);
-    }
-    FuturesUnordered {
-        active: futures.len(),
-        futures: futures,
-        pending: None,
-        stack: stack,
+    let mut set = FuturesUnordered::new();
+    for future in futures {
+        set.push(future.into_future());
     }
+    set
 }
 
 impl<F> FuturesUnordered<F>
     where F: Future
 {
+    /// Constructs a new, empty `FuturesUnordered`
+    ///
+    /// The returned `FuturesUnordered` does not contain any futures and, in this
+    /// state, `FuturesUnordered::poll` will return `Ok(Async::Ready(None))`.
+    pub fn new() -> FuturesUnordered<F> {
+        FuturesUnordered {
+            futures: Vec::new(),
+            next_slot: 0,
+            stack: Arc::new(Stack::new()),
+            pending: None,
+            active: 0,
+        }
+    }
+
+    /// Returns the number of futures contained in the set.
+    ///
+    /// This represents the total number of in-flight futures.
+    pub fn len(&self) -> usize {
+        self.active
+    }
+
+    /// Returns `true` if the set contains no futures
+    pub fn is_empty(&self) -> bool {
+        self.active == 0
+    }
+
+    /// Push a future into the set.
+    ///
+    /// This function submits the given future to the set for managing. This
+    /// function will not call `poll` on the submitted future. The caller must
+    /// ensure that `FuturesUnordered::poll` is called in order to receive task
+    /// notifications.
+    pub fn push(&mut self, future: F) {
+        let idx = self.next_slot;
+        if idx == self.futures.len() {
+            self.futures.push(Slot::Data(future));
+            self.next_slot += 1;
+        } else {
+            match mem::replace(&mut self.futures[idx], Slot::Data(future)) {
+                Slot::Next(next) => self.next_slot = next,
+                Slot::Data(_) => panic!("slab pointed at an occupied slot"),
+            }
+        }
+        self.active += 1;
+
+        // Make sure the new future is polled the next time we are
+        self.stack.push(idx);
+    }
+
     fn poll_pending(&mut self, mut drain: Drain<usize>)
                     -> Option<Poll<Option<F::Item>, F::Error>> {
         while let Some(id) = drain.next() {
-            // If this future was already done just skip the notification
-            if self.futures[id].is_none() {
-                continue
-            }
-            let event = UnparkEvent::new(self.stack.clone(), id);
-            let ret = match task::with_unpark_event(event, || {
-                self.futures[id]
-                    .as_mut()
-                    .unwrap()
-                    .poll()
-            }) {
-                Ok(Async::NotReady) => continue,
-                Ok(Async::Ready(val)) => Ok(Async::Ready(Some(val))),
-                Err(e) => Err(e),
+            let ret = match self.futures[id] {
+                Slot::Data(ref mut f) => {
+                    let event = UnparkEvent::new(self.stack.clone(), id);
+                    match task::with_unpark_event(event, || f.poll()) {
+                        Ok(Async::NotReady) => continue,
+                        Ok(Async::Ready(val)) => Ok(Async::Ready(Some(val))),
+                        Err(e) => Err(e),
+                    }
+                }
+
+                // If this future was already done just skip the notification
+                Slot::Next(_) => continue,
             };
             self.pending = Some(drain);
             self.active -= 1;
-            self.futures[id] = None;
+            self.futures[id] = Slot::Next(self.next_slot);
+            self.next_slot = id;
             return Some(ret)
         }
         None
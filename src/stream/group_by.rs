@@ -0,0 +1,298 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::hash::Hash;
+use std::mem;
+use std::prelude::v1::*;
+use std::sync::{Arc, Mutex};
+
+use {Async, Poll};
+use stream::{Stream, Fuse};
+use task::{self, Task};
+
+/// A stream combinator which splits a stream into sub-streams of items which
+/// share the same key.
+///
+/// Each time an item with a key that hasn't been seen yet (or whose group has
+/// since been dropped) is produced, this stream yields a new `Group` along
+/// with its key. All further items with that key are routed to that group.
+///
+/// This structure is produced by the `Stream::group_by` method.
+#[must_use = "streams do nothing unless polled"]
+pub struct GroupBy<S, K, F>
+    where S: Stream,
+          K: Eq + Hash,
+{
+    inner: Arc<Mutex<Inner<S, K, F>>>,
+}
+
+// The id used by the `GroupBy` handle when it's blocked, groups get theirs
+// from `Inner::next_id`.
+const OUTER_ID: usize = 0;
+
+/// A sub-stream of the items of a `GroupBy` stream which share the same key.
+///
+/// This structure is produced by polling a `GroupBy` stream.
+#[must_use = "streams do nothing unless polled"]
+pub struct Group<S, K, F>
+    where S: Stream,
+          K: Eq + Hash,
+{
+    key: K,
+    id: usize,
+    inner: Arc<Mutex<Inner<S, K, F>>>,
+}
+
+struct Inner<S, K, F>
+    where S: Stream,
+          K: Eq + Hash,
+{
+    stream: Fuse<S>,
+    f: F,
+
+    // Keys of all groups which are still alive.
+    groups: HashSet<K>,
+
+    // The single item which has been pulled from the underlying stream but
+    // not yet taken by the handle it belongs to. While this is occupied no
+    // further items are pulled, which is what provides backpressure.
+    pending: Option<(Target<K>, S::Item)>,
+
+    // Tasks which were blocked, either on `pending` or on the underlying
+    // stream, keyed by the id of the handle which was polled. All of these
+    // are woken whenever anything changes.
+    blocked: HashMap<usize, Task>,
+    next_id: usize,
+
+    outer_dropped: bool,
+}
+
+enum Target<K> {
+    // The item starts a new group, which still needs to be handed out by the
+    // `GroupBy` stream.
+    New(K),
+    // The item belongs to an existing group.
+    Group(K),
+}
+
+pub fn new<S, K, F>(s: S, f: F) -> GroupBy<S, K, F>
+    where S: Stream,
+          F: FnMut(&S::Item) -> K,
+          K: Clone + Eq + Hash,
+{
+    GroupBy {
+        inner: Arc::new(Mutex::new(Inner {
+            stream: super::fuse::new(s),
+            f: f,
+            groups: HashSet::new(),
+            pending: None,
+            blocked: HashMap::new(),
+            next_id: OUTER_ID + 1,
+            outer_dropped: false,
+        })),
+    }
+}
+
+impl<S, K, F> Inner<S, K, F>
+    where S: Stream,
+          K: Eq + Hash,
+{
+    fn unpark_blocked(&mut self) {
+        for (_, task) in mem::replace(&mut self.blocked, HashMap::new()) {
+            task.unpark();
+        }
+    }
+
+    // Blocks the current task, replacing any task previously blocked on the
+    // same handle so that repeated polls don't accumulate.
+    fn park(&mut self, id: usize) {
+        self.blocked.insert(id, task::park());
+    }
+}
+
+impl<S, K, F> Inner<S, K, F>
+    where S: Stream,
+          F: FnMut(&S::Item) -> K,
+          K: Clone + Eq + Hash,
+{
+
+    // Makes sure there's an item in `pending`, pulling one from the
+    // underlying stream if necessary.
+    fn fill(&mut self, id: usize) -> Poll<Option<()>, S::Error> {
+        while self.pending.is_none() {
+            let item = match self.stream.poll() {
+                Ok(Async::Ready(Some(item))) => item,
+                // The underlying stream only notifies the last task to poll
+                // it, so make sure every other handle finds out too.
+                Ok(Async::Ready(None)) => {
+                    self.unpark_blocked();
+                    return Ok(Async::Ready(None))
+                }
+                Ok(Async::NotReady) => {
+                    self.park(id);
+                    return Ok(Async::NotReady)
+                }
+                Err(e) => {
+                    self.unpark_blocked();
+                    return Err(e)
+                }
+            };
+            let key = (self.f)(&item);
+            let target = if self.groups.contains(&key) {
+                Target::Group(key)
+            } else if !self.outer_dropped {
+                self.groups.insert(key.clone());
+                Target::New(key)
+            } else {
+                // Nobody is around to receive this item, so it's dropped.
+                continue
+            };
+            self.pending = Some((target, item));
+            self.unpark_blocked();
+        }
+        Ok(Async::Ready(Some(())))
+    }
+
+    fn poll_outer(&mut self) -> Poll<Option<K>, S::Error> {
+        if try_ready!(self.fill(OUTER_ID)).is_none() {
+            return Ok(Async::Ready(None))
+        }
+        match self.pending.take() {
+            Some((Target::New(key), item)) => {
+                // The first item stays behind for the new group.
+                self.pending = Some((Target::Group(key.clone()), item));
+                self.unpark_blocked();
+                Ok(Async::Ready(Some(key)))
+            }
+            other => {
+                self.pending = other;
+                self.park(OUTER_ID);
+                Ok(Async::NotReady)
+            }
+        }
+    }
+
+    fn poll_group(&mut self, key: &K, id: usize) -> Poll<Option<S::Item>, S::Error> {
+        if try_ready!(self.fill(id)).is_none() {
+            return Ok(Async::Ready(None))
+        }
+        match self.pending.take() {
+            Some((Target::Group(ref k), item)) if k == key => {
+                self.unpark_blocked();
+                Ok(Async::Ready(Some(item)))
+            }
+            other => {
+                self.pending = other;
+                self.park(id);
+                Ok(Async::NotReady)
+            }
+        }
+    }
+}
+
+impl<S, K, F> Stream for GroupBy<S, K, F>
+    where S: Stream,
+          F: FnMut(&S::Item) -> K,
+          K: Clone + Eq + Hash,
+{
+    type Item = (K, Group<S, K, F>);
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, S::Error> {
+        let mut inner = self.inner.lock().unwrap();
+        let key = match try_ready!(inner.poll_outer()) {
+            Some(key) => key,
+            None => return Ok(Async::Ready(None)),
+        };
+        let id = inner.next_id;
+        inner.next_id += 1;
+        let group = Group {
+            key: key.clone(),
+            id: id,
+            inner: self.inner.clone(),
+        };
+        Ok(Async::Ready(Some((key, group))))
+    }
+}
+
+impl<S, K, F> Drop for GroupBy<S, K, F>
+    where S: Stream,
+          K: Eq + Hash,
+{
+    fn drop(&mut self) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.outer_dropped = true;
+            // A group which was never handed out can't receive any items, so
+            // its key is forgotten along with its first item.
+            match inner.pending.take() {
+                Some((Target::New(key), _)) => {
+                    inner.groups.remove(&key);
+                }
+                other => inner.pending = other,
+            }
+            inner.unpark_blocked();
+        }
+    }
+}
+
+impl<S, K, F> fmt::Debug for GroupBy<S, K, F>
+    where S: Stream,
+          K: Eq + Hash,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("GroupBy")
+            .finish()
+    }
+}
+
+impl<S, K, F> Group<S, K, F>
+    where S: Stream,
+          K: Eq + Hash,
+{
+    /// Returns the key shared by all items of this group.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+}
+
+impl<S, K, F> Stream for Group<S, K, F>
+    where S: Stream,
+          F: FnMut(&S::Item) -> K,
+          K: Clone + Eq + Hash,
+{
+    type Item = S::Item;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<S::Item>, S::Error> {
+        self.inner.lock().unwrap().poll_group(&self.key, self.id)
+    }
+}
+
+impl<S, K, F> Drop for Group<S, K, F>
+    where S: Stream,
+          K: Eq + Hash,
+{
+    fn drop(&mut self) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.groups.remove(&self.key);
+            let ours = match inner.pending {
+                Some((Target::Group(ref k), _)) => *k == self.key,
+                _ => false,
+            };
+            if ours {
+                inner.pending = None;
+            }
+            inner.unpark_blocked();
+        }
+    }
+}
+
+impl<S, K, F> fmt::Debug for Group<S, K, F>
+    where S: Stream,
+          K: Eq + Hash + fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Group")
+            .field("key", &self.key)
+            .finish()
+    }
+}
@@ -0,0 +1,96 @@
+use {Async, Poll};
+use stream::Stream;
+
+/// A stream combinator which runs a closure if the stream is dropped before
+/// it has finished.
+///
+/// This is produced by the `Stream::inspect_drop` method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct InspectDrop<S, F>
+    where F: FnOnce(),
+{
+    stream: S,
+    // Taken once the stream has finished, so that the closure doesn't run.
+    f: Option<F>,
+}
+
+pub fn new<S, F>(s: S, f: F) -> InspectDrop<S, F>
+    where S: Stream,
+          F: FnOnce(),
+{
+    InspectDrop {
+        stream: s,
+        f: Some(f),
+    }
+}
+
+impl<S, F> InspectDrop<S, F>
+    where F: FnOnce(),
+{
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+impl<S, F> ::sink::Sink for InspectDrop<S, F>
+    where S: ::sink::Sink,
+          F: FnOnce(),
+{
+    type SinkItem = S::SinkItem;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: S::SinkItem) -> ::StartSend<S::SinkItem, S::SinkError> {
+        self.stream.start_send(item)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.poll_complete()
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.close()
+    }
+}
+
+impl<S, F> Stream for InspectDrop<S, F>
+    where S: Stream,
+          F: FnOnce(),
+{
+    type Item = S::Item;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<S::Item>, S::Error> {
+        let next = try_ready!(self.stream.poll());
+        if next.is_none() {
+            self.f = None;
+        }
+        Ok(Async::Ready(next))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+impl<S, F> Drop for InspectDrop<S, F>
+    where F: FnOnce(),
+{
+    fn drop(&mut self) {
+        if let Some(f) = self.f.take() {
+            f();
+        }
+    }
+}
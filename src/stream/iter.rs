@@ -4,7 +4,7 @@ use stream::Stream;
 /// A stream which is just a shim over an underlying instance of `Iterator`.
 ///
 /// This stream will never block and is always ready.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[must_use = "streams do nothing unless polled"]
 pub struct Iter<I> {
     iter: I,
@@ -16,6 +16,9 @@ pub struct Iter<I> {
 /// Iterators in Rust don't express the ability to block, so this adapter simply
 /// always calls `iter.next()` and returns that.
 ///
+/// This function is equivalent to `iter_result`. For iterators which can't
+/// fail, `iter_ok` avoids having to wrap each item in `Ok`.
+///
 /// ```rust
 /// use futures::*;
 ///
@@ -46,4 +49,8 @@ impl<I, T, E> Stream for Iter<I>
             None => Ok(Async::Ready(None)),
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
 }
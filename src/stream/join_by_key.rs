@@ -0,0 +1,172 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use {Async, Poll};
+use stream::{Stream, Fuse};
+
+/// A stream which pairs up items from two streams which share the same key.
+///
+/// This stream is produced by the `stream::join_by_key` function.
+#[must_use = "streams do nothing unless polled"]
+pub struct JoinByKey<S1, S2, K, F>
+    where S1: Stream,
+{
+    left: Fuse<S1>,
+    right: Fuse<S2>,
+    // Items from each side which haven't found a partner yet, oldest first.
+    left_pending: VecDeque<(K, S1::Item)>,
+    right_pending: VecDeque<(K, S1::Item)>,
+    f: F,
+    window: usize,
+}
+
+/// Joins two streams by pairing up items which share the same key, as
+/// computed by `f`.
+///
+/// Every item from either stream is matched with the oldest unmatched item
+/// from the other stream with an equal key, and the two are yielded as a
+/// `(left, right)` tuple. Each item is part of at most one pair. Items which
+/// don't have a partner yet are held on to, but only the `window` most
+/// recent unmatched items of each stream are kept: older ones are discarded
+/// and will never be joined.
+///
+/// The returned stream finishes once both streams have finished, or once
+/// one of them has finished and none of its items are still waiting for a
+/// partner. Errors from either stream are passed through immediately.
+///
+/// # Panics
+///
+/// This function will panic if `window` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use futures::{Future, Stream};
+/// use futures::stream;
+///
+/// let left = stream::iter_ok::<_, ()>(vec![(1, "a"), (2, "b"), (3, "c")]);
+/// let right = stream::iter_ok(vec![(3, "x"), (1, "y")]);
+/// let joined = stream::join_by_key(left, right, |&(k, _)| k, 10);
+/// assert_eq!(joined.collect().wait(),
+///            Ok(vec![((1, "a"), (1, "y")), ((3, "c"), (3, "x"))]));
+/// ```
+pub fn join_by_key<S1, S2, K, F>(left: S1, right: S2, f: F, window: usize)
+                                 -> JoinByKey<S1, S2, K, F>
+    where S1: Stream,
+          S2: Stream<Item = S1::Item, Error = S1::Error>,
+          F: FnMut(&S1::Item) -> K,
+          K: Eq,
+{
+    assert!(window > 0);
+
+    JoinByKey {
+        left: super::fuse::new(left),
+        right: super::fuse::new(right),
+        left_pending: VecDeque::new(),
+        right_pending: VecDeque::new(),
+        f: f,
+        window: window,
+    }
+}
+
+// Pairs `item` with the oldest item in `other` which has the same key, or
+// buffers it in `pending` if there's no such item.
+fn join<K, T>(key: K,
+              item: T,
+              pending: &mut VecDeque<(K, T)>,
+              other: &mut VecDeque<(K, T)>,
+              window: usize) -> Option<(T, T)>
+    where K: Eq,
+{
+    match other.iter().position(|&(ref k, _)| *k == key) {
+        Some(i) => other.remove(i).map(|(_, partner)| (item, partner)),
+        None => {
+            if pending.len() >= window {
+                pending.pop_front();
+            }
+            pending.push_back((key, item));
+            None
+        }
+    }
+}
+
+impl<S1, S2, K, F> JoinByKey<S1, S2, K, F>
+    where S1: Stream,
+          S2: Stream,
+{
+    // Once one side has finished and has nothing left waiting for a partner,
+    // or both sides have finished, no further pairs can be produced.
+    fn is_finished(&self) -> bool {
+        let left_done = self.left.is_done();
+        let right_done = self.right.is_done();
+        (left_done && right_done) ||
+            (left_done && self.left_pending.is_empty()) ||
+            (right_done && self.right_pending.is_empty())
+    }
+}
+
+impl<S1, S2, K, F> Stream for JoinByKey<S1, S2, K, F>
+    where S1: Stream,
+          S2: Stream<Item = S1::Item, Error = S1::Error>,
+          F: FnMut(&S1::Item) -> K,
+          K: Eq,
+{
+    type Item = (S1::Item, S1::Item);
+    type Error = S1::Error;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, S1::Error> {
+        loop {
+            if self.is_finished() {
+                return Ok(Async::Ready(None))
+            }
+
+            let mut progress = false;
+
+            if let Async::Ready(Some(item)) = try!(self.left.poll()) {
+                progress = true;
+                let key = (self.f)(&item);
+                let window = self.window;
+                let pair = join(key, item, &mut self.left_pending,
+                                &mut self.right_pending, window);
+                if let Some(pair) = pair {
+                    return Ok(Async::Ready(Some(pair)))
+                }
+            }
+
+            if let Async::Ready(Some(item)) = try!(self.right.poll()) {
+                progress = true;
+                let key = (self.f)(&item);
+                let window = self.window;
+                let pair = join(key, item, &mut self.right_pending,
+                                &mut self.left_pending, window);
+                if let Some((right, left)) = pair {
+                    return Ok(Async::Ready(Some((left, right))))
+                }
+            }
+
+            if !progress {
+                if self.is_finished() {
+                    return Ok(Async::Ready(None))
+                }
+                return Ok(Async::NotReady)
+            }
+        }
+    }
+}
+
+impl<S1, S2, K, F> fmt::Debug for JoinByKey<S1, S2, K, F>
+    where S1: Stream + fmt::Debug,
+          S1::Item: fmt::Debug,
+          S2: fmt::Debug,
+          K: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("JoinByKey")
+            .field("left", &self.left)
+            .field("right", &self.right)
+            .field("left_pending", &self.left_pending)
+            .field("right_pending", &self.right_pending)
+            .field("window", &self.window)
+            .finish()
+    }
+}
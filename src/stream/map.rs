@@ -78,4 +78,8 @@ impl<S, F, U> Stream for Map<S, F>
         let option = try_ready!(self.stream.poll());
         Ok(Async::Ready(option.map(&mut self.f)))
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
 }
@@ -77,4 +77,8 @@ impl<S, F, U> Stream for MapErr<S, F>
     fn poll(&mut self) -> Poll<Option<S::Item>, U> {
         self.stream.poll().map_err(&mut self.f)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
 }
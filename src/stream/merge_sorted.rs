@@ -0,0 +1,118 @@
+use std::fmt;
+use std::prelude::v1::*;
+
+use {Async, Poll};
+use stream::{Stream, Fuse};
+
+/// A stream which merges several individually ordered streams into a single
+/// ordered stream.
+///
+/// This stream is produced by the `stream::merge_sorted` function.
+#[must_use = "streams do nothing unless polled"]
+pub struct MergeSorted<S, K, F>
+    where S: Stream,
+{
+    streams: Vec<Fuse<S>>,
+    // The next item of each stream, along with its key, once it's been
+    // pulled.
+    peeked: Vec<Option<(K, S::Item)>>,
+    f: F,
+}
+
+/// Merges several streams, each of which is already ordered by the key
+/// computed with `f`, into one stream which is ordered by that key as a
+/// whole.
+///
+/// The returned stream looks ahead by one item on each of the underlying
+/// streams, so it can only yield an item once every stream which hasn't
+/// finished yet has an item ready. Items with equal keys are yielded in the
+/// order of the streams they came from.
+///
+/// Errors from any of the streams are passed through immediately, and the
+/// stream which produced the error keeps being polled afterwards.
+///
+/// # Examples
+///
+/// ```
+/// use futures::{Future, Stream};
+/// use futures::stream;
+///
+/// let a = stream::iter_ok::<_, ()>(vec![1, 4, 5]);
+/// let b = stream::iter_ok(vec![2, 3, 6]);
+/// let merged = stream::merge_sorted(vec![a, b], |&x| x);
+/// assert_eq!(merged.collect().wait(), Ok(vec![1, 2, 3, 4, 5, 6]));
+/// ```
+pub fn merge_sorted<I, K, F>(streams: I, f: F) -> MergeSorted<I::Item, K, F>
+    where I: IntoIterator,
+          I::Item: Stream,
+          F: FnMut(&<I::Item as Stream>::Item) -> K,
+          K: Ord,
+{
+    let streams = streams.into_iter()
+                         .map(super::fuse::new)
+                         .collect::<Vec<_>>();
+    MergeSorted {
+        peeked: streams.iter().map(|_| None).collect(),
+        streams: streams,
+        f: f,
+    }
+}
+
+impl<S, K, F> Stream for MergeSorted<S, K, F>
+    where S: Stream,
+          F: FnMut(&S::Item) -> K,
+          K: Ord,
+{
+    type Item = S::Item;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<S::Item>, S::Error> {
+        // Make sure every stream which is still running has an item waiting,
+        // otherwise there's no telling which item comes next.
+        let mut ready = true;
+        for (stream, peeked) in self.streams.iter_mut().zip(&mut self.peeked) {
+            if peeked.is_some() || stream.is_done() {
+                continue
+            }
+            match try!(stream.poll()) {
+                Async::Ready(Some(item)) => {
+                    *peeked = Some(((self.f)(&item), item));
+                }
+                Async::Ready(None) => {}
+                Async::NotReady => ready = false,
+            }
+        }
+        if !ready {
+            return Ok(Async::NotReady)
+        }
+
+        let mut next: Option<usize> = None;
+        for (i, peeked) in self.peeked.iter().enumerate() {
+            let key = match *peeked {
+                Some((ref key, _)) => key,
+                None => continue,
+            };
+            let smaller = match next {
+                Some(j) => *key < self.peeked[j].as_ref().unwrap().0,
+                None => true,
+            };
+            if smaller {
+                next = Some(i);
+            }
+        }
+        Ok(Async::Ready(next.and_then(|i| self.peeked[i].take()).map(|p| p.1)))
+    }
+}
+
+impl<S, K, F> fmt::Debug for MergeSorted<S, K, F>
+    where S: Stream + fmt::Debug,
+          S::Item: fmt::Debug,
+          K: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("MergeSorted")
+            .field("streams", &self.streams)
+            .field("peeked", &self.peeked)
+            .finish()
+    }
+}
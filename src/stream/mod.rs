@@ -21,61 +21,101 @@ mod iter;
 pub use self::iter::{iter, Iter};
 #[cfg(feature = "with-deprecated")]
 pub use self::Iter as IterStream;
+mod iter_ok;
+pub use self::iter_ok::{iter_ok, IterOk};
+mod iter_result;
+pub use self::iter_result::{iter_result, IterResult};
 
 mod repeat;
 pub use self::repeat::{repeat, Repeat};
 
+mod repeat_with;
+pub use self::repeat_with::{repeat_with, RepeatWith};
+
+mod poll_fn;
+pub use self::poll_fn::{poll_fn, PollFn};
+
+mod all;
 mod and_then;
+mod any;
 mod chain;
 mod concat;
+mod count;
+mod cycle;
+mod dedup;
 mod empty;
+mod enumerate;
 mod filter;
 mod filter_map;
+mod filter_map_async;
+mod flat_map;
 mod flatten;
 mod fold;
 mod for_each;
 mod from_err;
 mod fuse;
 mod future;
+mod inspect_drop;
 mod map;
 mod map_err;
 mod merge;
 mod once;
 mod or_else;
 mod peek;
+mod recover;
+mod scan;
 mod select;
+mod select_with_strategy;
 mod skip;
 mod skip_while;
 mod take;
+mod take_until;
 mod take_while;
 mod then;
+mod try_fold;
+mod try_unfold;
 mod unfold;
 mod zip;
 mod forward;
+pub use self::all::All;
 pub use self::and_then::AndThen;
+pub use self::any::Any;
 pub use self::chain::Chain;
-pub use self::concat::Concat;
+pub use self::concat::{Concat, Concat2};
+pub use self::count::Count;
+pub use self::cycle::Cycle;
+pub use self::dedup::{Dedup, DedupBy};
 pub use self::empty::{Empty, empty};
+pub use self::enumerate::Enumerate;
 pub use self::filter::Filter;
 pub use self::filter_map::FilterMap;
+pub use self::filter_map_async::FilterMapAsync;
+pub use self::flat_map::FlatMap;
 pub use self::flatten::Flatten;
 pub use self::fold::Fold;
 pub use self::for_each::ForEach;
 pub use self::from_err::FromErr;
 pub use self::fuse::Fuse;
 pub use self::future::StreamFuture;
+pub use self::inspect_drop::InspectDrop;
 pub use self::map::Map;
 pub use self::map_err::MapErr;
 pub use self::merge::{Merge, MergedItem};
 pub use self::once::{Once, once};
 pub use self::or_else::OrElse;
 pub use self::peek::Peekable;
+pub use self::recover::Recover;
+pub use self::scan::Scan;
 pub use self::select::Select;
+pub use self::select_with_strategy::{select_with_strategy, SelectWithStrategy, PollNext};
 pub use self::skip::Skip;
 pub use self::skip_while::SkipWhile;
 pub use self::take::Take;
+pub use self::take_until::TakeUntil;
 pub use self::take_while::TakeWhile;
 pub use self::then::Then;
+pub use self::try_fold::TryFold;
+pub use self::try_unfold::{TryUnfold, try_unfold};
 pub use self::unfold::{Unfold, unfold};
 pub use self::zip::Zip;
 pub use self::forward::Forward;
@@ -83,24 +123,64 @@ use sink::{Sink};
 
 if_std! {
     use std;
+    use std::time::Duration;
 
+    use Future;
+    use timer::{Timer, Elapsed};
+
+    mod batch_weighted;
     mod buffered;
     mod buffer_unordered;
     mod catch_unwind;
     mod chunks;
+    mod chunks_timeout;
     mod collect;
+    mod fan_out;
+    mod for_each_concurrent;
+    mod flatten_unordered;
+    mod group_by;
+    mod partition;
+    mod debounce;
+    mod sample;
+    mod join_by_key;
+    mod merge_sorted;
+    mod rate_limit;
+    mod share;
+    mod timeout;
+    mod valve;
     mod wait;
+    mod windows;
     mod channel;
     mod split;
     mod futures_unordered;
+    mod futures_ordered;
+    mod select_all;
+    pub use self::batch_weighted::BatchWeighted;
     pub use self::buffered::Buffered;
     pub use self::buffer_unordered::BufferUnordered;
     pub use self::catch_unwind::CatchUnwind;
     pub use self::chunks::Chunks;
+    pub use self::chunks_timeout::ChunksTimeout;
     pub use self::collect::Collect;
+    pub use self::fan_out::{FanOut, LagPolicy};
+    pub use self::for_each_concurrent::{ForEachConcurrent, TryForEachConcurrent};
+    pub use self::flatten_unordered::FlattenUnordered;
+    pub use self::group_by::{GroupBy, Group};
+    pub use self::partition::Partition;
+    pub use self::debounce::Debounce;
+    pub use self::sample::Sample;
+    pub use self::join_by_key::{join_by_key, JoinByKey};
+    pub use self::merge_sorted::{merge_sorted, MergeSorted};
+    pub use self::rate_limit::RateLimit;
+    pub use self::share::Share;
+    pub use self::timeout::Timeout;
+    pub use self::valve::{valve, Valve, ValveHandle};
     pub use self::wait::Wait;
+    pub use self::windows::Windows;
     pub use self::split::{SplitStream, SplitSink};
     pub use self::futures_unordered::{futures_unordered, FuturesUnordered};
+    pub use self::futures_ordered::{futures_ordered, FuturesOrdered};
+    pub use self::select_all::{select_all, SelectAll};
 
     #[doc(hidden)]
     #[cfg(feature = "with-deprecated")]
@@ -110,6 +190,9 @@ if_std! {
     /// A type alias for `Box<Stream + Send>`
     pub type BoxStream<T, E> = ::std::boxed::Box<Stream<Item = T, Error = E> + Send>;
 
+    /// A type alias for `Box<Stream + 'a>`, which isn't required to be `Send`
+    pub type LocalBoxStream<'a, T, E> = ::std::boxed::Box<Stream<Item = T, Error = E> + 'a>;
+
     impl<S: ?Sized + Stream> Stream for ::std::boxed::Box<S> {
         type Item = S::Item;
         type Error = S::Error;
@@ -117,6 +200,10 @@ if_std! {
         fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
             (**self).poll()
         }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (**self).size_hint()
+        }
     }
 }
 
@@ -193,6 +280,25 @@ pub trait Stream {
     // TODO: more here
     fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error>;
 
+    /// Returns the bounds on the remaining length of the stream.
+    ///
+    /// This works just like `Iterator::size_hint`: the first element of the
+    /// returned tuple is a lower bound on the number of items the stream has
+    /// yet to produce, and the second element is an upper bound, where
+    /// `None` means that there's no known upper bound. Any errors that the
+    /// stream produces along the way count towards its length as well.
+    ///
+    /// The hint is only meant to be used for optimizations such as reserving
+    /// space for the items of the stream, and a buggy implementation may
+    /// return incorrect bounds, so it must not be relied upon for anything
+    /// else.
+    ///
+    /// The default implementation returns `(0, None)`, which is correct for
+    /// any stream.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, None)
+    }
+
     // TODO: should there also be a method like `poll` but doesn't return an
     //       item? basically just says "please make more progress internally"
     //       seems crucial for buffering to actually make any sense.
@@ -232,7 +338,7 @@ pub trait Stream {
     /// type inference as well by always returning a trait object. Note that
     /// this method requires the `Send` bound and returns a `BoxStream`, which
     /// also encodes this. If you'd like to create a `Box<Stream>` without the
-    /// `Send` bound, then the `Box::new` function can be used instead.
+    /// `Send` bound, then the `boxed_local` method can be used instead.
     ///
     /// This method is only available when the `use_std` feature of this
     /// library is activated, and it is activated by default.
@@ -253,6 +359,32 @@ pub trait Stream {
         ::std::boxed::Box::new(self)
     }
 
+    /// Convenience function for turning this stream into a trait object which
+    /// isn't required to be `Send`.
+    ///
+    /// This is the same as `boxed`, except that it returns a `LocalBoxStream`,
+    /// so it can be used with streams which can't be sent across threads,
+    /// such as the ones from the `unsync` module, or which borrow data.
+    ///
+    /// This method is only available when the `use_std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::stream::*;
+    /// use futures::unsync::mpsc;
+    ///
+    /// let (_tx, rx) = mpsc::channel(1);
+    /// let a: LocalBoxStream<i32, ()> = rx.boxed_local();
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn boxed_local<'a>(self) -> LocalBoxStream<'a, Self::Item, Self::Error>
+        where Self: Sized + 'a,
+    {
+        ::std::boxed::Box::new(self)
+    }
+
     /// Converts this stream into a `Future`.
     ///
     /// A stream can be viewed as a future which will resolve to a pair containing
@@ -341,7 +473,7 @@ pub trait Stream {
     /// use futures::sync::mpsc;
     ///
     /// let (_tx, rx) = mpsc::channel::<i32>(1);
-    /// let evens = rx.filter(|x| x % 0 == 2);
+    /// let evens = rx.filter(|x| x % 2 == 0);
     /// ```
     fn filter<F>(self, f: F) -> Filter<Self, F>
         where F: FnMut(&Self::Item) -> bool,
@@ -350,6 +482,58 @@ pub trait Stream {
         filter::new(self, f)
     }
 
+    /// Suppresses consecutive duplicate items of this stream.
+    ///
+    /// Each item is compared with the last item which was yielded, and is
+    /// only yielded itself if the two are not equal. This is useful for
+    /// change notifications where only transitions matter. A clone of the
+    /// last yielded item is kept around for the comparison.
+    ///
+    /// All errors are passed through without affecting the comparison.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::{Future, Stream};
+    /// use futures::stream;
+    ///
+    /// let states = stream::iter_ok::<_, ()>(vec![1, 1, 2, 2, 2, 1]);
+    /// assert_eq!(states.dedup().collect().wait(), Ok(vec![1, 2, 1]));
+    /// ```
+    fn dedup(self) -> Dedup<Self>
+        where Self::Item: Clone + PartialEq,
+              Self: Sized
+    {
+        dedup::new(self)
+    }
+
+    /// Suppresses consecutive items of this stream which are considered the
+    /// same by the provided closure.
+    ///
+    /// This is like `dedup`, except that `same` is called with the last
+    /// yielded item and the new item, and the new item is discarded if it
+    /// returns `true`.
+    ///
+    /// All errors are passed through without affecting the comparison.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::{Future, Stream};
+    /// use futures::stream;
+    ///
+    /// let temperatures = stream::iter_ok::<_, ()>(vec![20, 21, 25, 24, 19]);
+    /// let changes = temperatures.dedup_by(|a: &i32, b: &i32| (a - b).abs() < 3);
+    /// assert_eq!(changes.collect().wait(), Ok(vec![20, 25, 19]));
+    /// ```
+    fn dedup_by<F>(self, same: F) -> DedupBy<Self, F>
+        where F: FnMut(&Self::Item, &Self::Item) -> bool,
+              Self::Item: Clone,
+              Self: Sized
+    {
+        dedup::new_by(self, same)
+    }
+
     /// Filters the values produced by this stream while simultaneously mapping
     /// them to a different type.
     ///
@@ -372,7 +556,7 @@ pub trait Stream {
     ///
     /// let (_tx, rx) = mpsc::channel::<i32>(1);
     /// let evens_plus_one = rx.filter_map(|x| {
-    ///     if x % 0 == 2 {
+    ///     if x % 2 == 0 {
     ///         Some(x + 1)
     ///     } else {
     ///         None
@@ -386,6 +570,42 @@ pub trait Stream {
         filter_map::new(self, f)
     }
 
+    /// Filters the values produced by this stream while simultaneously mapping
+    /// them to a different type, using an asynchronous computation.
+    ///
+    /// This combinator is like `filter_map`, except that the provided closure
+    /// returns a future instead of an `Option` directly. As values of this
+    /// stream are made available the closure is run on them, and the
+    /// returned future is then run to completion. If it resolves to `Some(e)`
+    /// then the stream will yield the value `e`, but if it resolves to `None`
+    /// then the next value will be produced.
+    ///
+    /// All errors of this stream are passed through without being given to
+    /// the closure, and errors from the returned futures are passed through
+    /// as well. Note that the `Result` type implements the `IntoFuture` trait
+    /// so it is possible to simply return a `Result` from the closure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::{Future, Stream};
+    /// use futures::future;
+    /// use futures::stream;
+    ///
+    /// let numbers = stream::iter_ok::<_, ()>(vec![1, 2, 3, 4]);
+    /// let evens_plus_one = numbers.filter_map_async(|x| {
+    ///     future::ok(if x % 2 == 0 { Some(x + 1) } else { None })
+    /// });
+    /// assert_eq!(evens_plus_one.collect().wait(), Ok(vec![3, 5]));
+    /// ```
+    fn filter_map_async<F, U, B>(self, f: F) -> FilterMapAsync<Self, F, U>
+        where F: FnMut(Self::Item) -> U,
+              U: IntoFuture<Item=Option<B>, Error=Self::Error>,
+              Self: Sized
+    {
+        filter_map_async::new(self, f)
+    }
+
     /// Chain on a computation for when a value is ready, passing the resulting
     /// item to the provided closure `f`.
     ///
@@ -497,12 +717,50 @@ pub trait Stream {
         or_else::new(self, f)
     }
 
+    /// Attempt to recover from errors produced by this stream, optionally
+    /// replacing them with an item.
+    ///
+    /// This is similar to `or_else`, except that the future returned by the
+    /// closure resolves to an `Option` of an item. If it resolves to
+    /// `Some(item)` then the item takes the place of the error in the
+    /// returned stream, and if it resolves to `None` the error is simply
+    /// skipped. Either way the stream carries on with the next value of this
+    /// stream afterwards, so a single error doesn't have to end the whole
+    /// computation. If the returned future fails, that error is passed on.
+    ///
+    /// Any successful values produced by this stream will not be passed to the
+    /// closure, and will be passed through.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::{Future, Stream};
+    /// use futures::stream;
+    ///
+    /// let s = stream::iter_result(vec![Ok(1), Err("bad"), Err("skip"), Ok(4)]);
+    /// let s = s.recover(|e| -> Result<_, ()> {
+    ///     Ok(if e == "bad" { Some(0) } else { None })
+    /// });
+    /// assert_eq!(s.collect().wait(), Ok(vec![1, 0, 4]));
+    /// ```
+    fn recover<F, U>(self, f: F) -> Recover<Self, F, U>
+        where F: FnMut(Self::Error) -> U,
+              U: IntoFuture<Item = Option<Self::Item>>,
+              Self: Sized
+    {
+        recover::new(self, f)
+    }
+
     /// Collect all of the values of this stream into a vector, returning a
     /// future representing the result of that computation.
     ///
     /// This combinator will collect all successful results of this stream and
     /// collect them into a `Vec<Self::Item>`. If an error happens then all
-    /// collected elements will be dropped and the error will be returned.
+    /// collected elements will be dropped and the error will be returned. To
+    /// collect into a different kind of collection, see `collect_as`.
+    ///
+    /// The vector is created with enough room for the lower bound of this
+    /// stream's `size_hint`.
     ///
     /// The returned future will be resolved whenever an error happens or when
     /// the stream returns `Ok(None)`.
@@ -532,6 +790,44 @@ pub trait Stream {
     #[cfg(feature = "use_std")]
     fn collect(self) -> Collect<Self>
         where Self: Sized
+    {
+        collect::new_vec(self)
+    }
+
+    /// Collect all of the values of this stream into any collection which
+    /// can be extended with them, returning a future representing the
+    /// result of that computation.
+    ///
+    /// This combinator behaves like `collect`, except that rather than always
+    /// producing a `Vec<Self::Item>` it starts out with `C::default()` and
+    /// extends it with each successful result of this stream. This allows
+    /// collecting into a `HashMap`, a `BTreeSet`, a `String` and so on. If an
+    /// error happens then all collected elements will be dropped and the
+    /// error will be returned.
+    ///
+    /// This method is only available when the `use_std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use futures::{Stream, Future};
+    /// use futures::stream;
+    ///
+    /// let pairs = stream::iter_ok::<_, ()>(vec![("a", 1), ("b", 2)]);
+    /// let map = pairs.collect_as::<HashMap<_, _>>().wait().unwrap();
+    /// assert_eq!(map["a"], 1);
+    /// assert_eq!(map["b"], 2);
+    ///
+    /// let chars = stream::iter_ok::<_, ()>(vec!['h', 'i']);
+    /// assert_eq!(chars.collect_as::<String>().wait(), Ok("hi".to_string()));
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn collect_as<C>(self) -> Collect<Self, C>
+        where C: Default + Extend<Self::Item>,
+              Self: Sized
     {
         collect::new(self)
     }
@@ -563,6 +859,12 @@ pub trait Stream {
     /// let result = rx.concat();
     /// assert_eq!(result.wait(), Ok(vec![7, 8, 9, 4, 5, 6, 1, 2, 3]));
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// The returned future will panic if this stream finishes without
+    /// yielding any items, as there's nothing to return. See `concat2` for a
+    /// version of this method which returns a default value instead.
     fn concat(self) -> Concat<Self>
         where Self: Sized,
               Self::Item: Extend<<<Self as Stream>::Item as IntoIterator>::Item> + IntoIterator,
@@ -570,6 +872,37 @@ pub trait Stream {
         concat::new(self)
     }
 
+    /// Concatenate all results of a stream into a single extendable
+    /// destination, returning a future representing the end result.
+    ///
+    /// This combinator will extend the first item with the contents of all
+    /// the successful results of the stream. If the stream is empty, the
+    /// default value will be returned. If an error occurs, all the results
+    /// will be dropped and the error will be returned.
+    ///
+    /// This works with any item type which can be extended with its own
+    /// contents and which implements `Default`, such as `Vec`, and is
+    /// typically used to collect a body which arrives in chunks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::{Future, Stream};
+    /// use futures::stream;
+    ///
+    /// let chunks = stream::iter_ok::<_, ()>(vec![vec![1, 2], vec![], vec![3]]);
+    /// assert_eq!(chunks.concat2().wait(), Ok(vec![1, 2, 3]));
+    ///
+    /// let empty = stream::empty::<Vec<i32>, ()>();
+    /// assert_eq!(empty.concat2().wait(), Ok(vec![]));
+    /// ```
+    fn concat2(self) -> Concat2<Self>
+        where Self: Sized,
+              Self::Item: Extend<<<Self as Stream>::Item as IntoIterator>::Item> + IntoIterator + Default,
+    {
+        concat::new2(self)
+    }
+
     /// Execute an accumulating computation over a stream, collecting all the
     /// values into one final result.
     ///
@@ -580,7 +913,10 @@ pub trait Stream {
     /// resolve to this value.
     ///
     /// If an error happens then collected state will be dropped and the error
-    /// will be returned.
+    /// will be returned. This applies both to errors from this stream and to
+    /// errors from the futures returned by the closure, which are converted
+    /// with `From`. In either case no further items are pulled from this
+    /// stream.
     ///
     /// # Examples
     ///
@@ -592,6 +928,23 @@ pub trait Stream {
     /// let sum = number_stream.fold(0, |a, b| ok(a + b));
     /// assert_eq!(sum.wait(), Ok(15));
     /// ```
+    ///
+    /// Stopping at the first error returned by the closure:
+    ///
+    /// ```
+    /// use futures::stream::{self, Stream};
+    /// use futures::future::Future;
+    ///
+    /// let number_stream = stream::iter_ok::<_, String>(0..6);
+    /// let sum = number_stream.fold(0, |a, b| {
+    ///     if b < 3 {
+    ///         Ok(a + b)
+    ///     } else {
+    ///         Err(format!("{} is too big", b))
+    ///     }
+    /// });
+    /// assert_eq!(sum.wait(), Err("3 is too big".to_string()));
+    /// ```
     fn fold<F, T, Fut>(self, init: T, f: F) -> Fold<Self, F, Fut, T>
         where F: FnMut(T, Self::Item) -> Fut,
               Fut: IntoFuture<Item = T>,
@@ -601,6 +954,40 @@ pub trait Stream {
         fold::new(self, f, init)
     }
 
+    /// Execute an accumulating computation over a stream, stopping at the
+    /// first error, and resolving to the final state.
+    ///
+    /// This is the asynchronous analogue of `Iterator::try_fold`, and is the
+    /// same as `fold` except that the futures returned by the closure must
+    /// have the same error type as this stream. No conversion takes place,
+    /// so a closure which returns a `Result` needs no type annotations for
+    /// its error. The fold stops at the first error, whether it comes from
+    /// this stream or from the closure, and resolves to that error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::stream::{self, Stream};
+    /// use futures::future::Future;
+    ///
+    /// let number_stream = stream::iter_ok::<_, u32>(0..6);
+    /// let sum = number_stream.try_fold(0, |a, b| {
+    ///     if b < 3 {
+    ///         Ok(a + b)
+    ///     } else {
+    ///         Err(b)
+    ///     }
+    /// });
+    /// assert_eq!(sum.wait(), Err(3));
+    /// ```
+    fn try_fold<F, T, Fut>(self, init: T, f: F) -> TryFold<Self, F, Fut, T>
+        where F: FnMut(T, Self::Item) -> Fut,
+              Fut: IntoFuture<Item = T, Error = Self::Error>,
+              Self: Sized
+    {
+        try_fold::new(self, f, init)
+    }
+
     /// Flattens a stream of streams into just one continuous stream.
     ///
     /// If this stream's elements are themselves streams then this combinator
@@ -642,6 +1029,31 @@ pub trait Stream {
         flatten::new(self)
     }
 
+    /// Maps this stream's items to a stream with the provided closure, and
+    /// flattens the resulting streams into one long stream of elements.
+    ///
+    /// This is equivalent to `self.map(f).flatten()`. Each stream returned by
+    /// `f` will get exhausted before the next item of this stream is mapped.
+    /// Errors from this stream are converted with `From` and passed through,
+    /// as are errors from the mapped streams.
+    ///
+    /// ```
+    /// use futures::{Future, Stream};
+    /// use futures::stream;
+    ///
+    /// let stream = stream::iter_ok::<_, ()>(vec![1, 2, 3]);
+    /// let repeated = stream.flat_map(|n| stream::iter_ok::<_, ()>(vec![n; n]));
+    /// assert_eq!(repeated.collect().wait(), Ok(vec![1, 2, 2, 3, 3, 3]));
+    /// ```
+    fn flat_map<F, U>(self, f: F) -> FlatMap<Self, F, U>
+        where F: FnMut(Self::Item) -> U,
+              U: Stream,
+              U::Error: From<Self::Error>,
+              Self: Sized
+    {
+        flat_map::new(self, f)
+    }
+
     /// Skip elements on this stream while the predicate provided resolves to
     /// `true`.
     ///
@@ -671,99 +1083,415 @@ pub trait Stream {
         take_while::new(self, pred)
     }
 
-    /// Runs this stream to completion, executing the provided closure for each
-    /// element on the stream.
+    /// Combinator similar to `Stream::fold` that holds internal state and
+    /// produces a new stream.
     ///
-    /// The closure provided will be called for each item this stream resolves
-    /// to successfully, producing a future. That future will then be executed
-    /// to completion before moving on to the next item.
+    /// Accepts an initial state and a closure `f`. The closure is called with
+    /// a mutable reference to the state and each item of this stream, and
+    /// returns a future resolving to an `Option`. If the future resolves to
+    /// `Some(value)` then `value` is yielded from the returned stream, and if
+    /// it resolves to `None` then the returned stream is finished.
     ///
-    /// The returned value is a `Future` where the `Item` type is `()` and
-    /// errors are otherwise threaded through. Any error on the stream or in the
-    /// closure will cause iteration to be halted immediately and the future
-    /// will resolve to that error.
-    fn for_each<F, U>(self, f: F) -> ForEach<Self, F, U>
-        where F: FnMut(Self::Item) -> U,
-              U: IntoFuture<Item=(), Error = Self::Error>,
+    /// Any errors produced by this stream will not be passed to the closure,
+    /// and will be passed through.
+    ///
+    /// Note that the `Result` type implements the `IntoFuture` trait so it is
+    /// possible to simply return a `Result` from the closure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::stream::{self, Stream};
+    /// use futures::future::Future;
+    ///
+    /// let stream = stream::iter_ok::<_, ()>(vec![1, 2, 3, 4, 5]);
+    /// let totals = stream.scan(0, |total, x| {
+    ///     *total += x;
+    ///     if *total < 10 {
+    ///         Ok(Some(*total))
+    ///     } else {
+    ///         Ok(None)
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(totals.collect().wait(), Ok(vec![1, 3, 6]));
+    /// ```
+    fn scan<St, F, U, B>(self, initial_state: St, f: F) -> Scan<Self, St, F, U>
+        where F: FnMut(&mut St, Self::Item) -> U,
+              U: IntoFuture<Item=Option<B>, Error=Self::Error>,
               Self: Sized
     {
-        for_each::new(self, f)
+        scan::new(self, initial_state, f)
     }
 
-    /// Map this stream's error to any error implementing `From` for
-    /// this stream's `Error`, returning a new stream.
+    /// Take elements from this stream until the provided future resolves.
     ///
-    /// This function does for streams what `try!` does for `Result`,
-    /// by letting the compiler infer the type of the resulting error.
-    /// Just as `map_err` above, this is useful for example to ensure
-    /// that streams have the same error type when used with
-    /// combinators.
+    /// This function will yield the items of this stream until `future`
+    /// resolves, successfully or with an error, after which the returned
+    /// stream is finished. This is typically used to wire a shutdown signal,
+    /// such as a `oneshot::Receiver`, into a consumer of a stream. The
+    /// result of the future is discarded, and `TakeUntil::is_stopped` can be
+    /// used to find out whether the stream ended because of the future or
+    /// because this stream finished.
     ///
-    /// Note that this function consumes the receiving stream and returns a
-    /// wrapped version of it.
-    fn from_err<E: From<Self::Error>>(self) -> FromErr<Self, E>
-        where Self: Sized,
-    {
-        from_err::new(self)
-    }
-
-    /// Creates a new stream of at most `amt` items of the underlying stream.
+    /// Errors from this stream are passed through until the future resolves.
     ///
-    /// Once `amt` items have been yielded from this stream then it will always
-    /// return that the stream is done.
+    /// # Examples
     ///
-    /// # Errors
+    /// ```
+    /// use futures::{Future, Stream};
+    /// use futures::stream;
+    /// use futures::sync::oneshot;
     ///
-    /// Any errors yielded from underlying stream, before the desired amount of
-    /// items is reached, are passed through and do not affect the total number
-    /// of items taken.
-    fn take(self, amt: u64) -> Take<Self>
-        where Self: Sized
+    /// let (stop_tx, stop_rx) = oneshot::channel::<()>();
+    /// let mut ticks = stream::repeat::<_, ()>("tick").take_until(stop_rx).wait();
+    ///
+    /// assert_eq!(ticks.next(), Some(Ok("tick")));
+    /// stop_tx.send(()).unwrap();
+    /// assert_eq!(ticks.next(), None);
+    /// ```
+    fn take_until<F>(self, future: F) -> TakeUntil<Self, F>
+        where F: IntoFuture,
+              Self: Sized
     {
-        take::new(self, amt)
+        take_until::new(self, future)
     }
 
-    /// Creates a new stream which skips `amt` items of the underlying stream.
+    /// Counts the items of this stream, returning a future of the total.
     ///
-    /// Once `amt` items have been skipped from this stream then it will always
-    /// return the remaining items on this stream.
+    /// The returned future resolves once this stream has ended. Any error on
+    /// the stream will cause counting to be halted immediately and the
+    /// future will resolve to that error.
     ///
-    /// # Errors
+    /// # Examples
     ///
-    /// All errors yielded from underlying stream are passed through and do not
-    /// affect the total number of items skipped.
-    fn skip(self, amt: u64) -> Skip<Self>
+    /// ```
+    /// use futures::{Future, Stream};
+    /// use futures::stream;
+    ///
+    /// let stream = stream::iter_ok::<_, ()>(vec!['a', 'b', 'c']);
+    /// assert_eq!(stream.count().wait(), Ok(3));
+    /// ```
+    fn count(self) -> Count<Self>
         where Self: Sized
     {
-        skip::new(self, amt)
+        count::new(self)
     }
 
-    /// Fuse a stream such that `poll` will never again be called once it has
-    /// finished.
+    /// Checks whether any item of this stream satisfies the predicate
+    /// provided, returning a future of the answer.
     ///
-    /// Currently once a stream has returned `None` from `poll` any further
-    /// calls could exhibit bad behavior such as block forever, panic, never
-    /// return, etc. If it is known that `poll` may be called after stream has
-    /// already finished, then this method can be used to ensure that it has
-    /// defined semantics.
+    /// The predicate returns a future resolving to a `bool`. As soon as one of
+    /// these resolves to `true` the returned future resolves to `true`
+    /// without pulling any more items from the stream. If the stream ends
+    /// without that happening, the returned future resolves to `false`. Any
+    /// error on the stream or from the predicate will cause the future to
+    /// resolve to that error.
     ///
-    /// Once a stream has been `fuse`d and it finishes, then it will forever
-    /// return `None` from `poll`. This, unlike for the traits `poll` method,
-    /// is guaranteed.
+    /// Note that the `Result` type implements the `IntoFuture` trait so it is
+    /// possible to simply return a `Result` from the predicate.
     ///
-    /// Also note that as soon as this stream returns `None` it will be dropped
-    /// to reclaim resources associated with it.
-    fn fuse(self) -> Fuse<Self>
-        where Self: Sized
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::{Future, Stream};
+    /// use futures::stream;
+    ///
+    /// let stream = stream::iter_ok::<_, ()>(vec![1, 2, 3]);
+    /// assert_eq!(stream.any(|i| Ok(i == 2)).wait(), Ok(true));
+    /// ```
+    fn any<P, R>(self, pred: P) -> Any<Self, P, R>
+        where P: FnMut(Self::Item) -> R,
+              R: IntoFuture<Item=bool, Error=Self::Error>,
+              Self: Sized
     {
-        fuse::new(self)
+        any::new(self, pred)
     }
 
-    /// Catches unwinding panics while polling the stream.
+    /// Checks whether all items of this stream satisfy the predicate
+    /// provided, returning a future of the answer.
     ///
-    /// Caught panic (if any) will be the last element of the resulting stream.
+    /// The predicate returns a future resolving to a `bool`. As soon as one of
+    /// these resolves to `false` the returned future resolves to `false`
+    /// without pulling any more items from the stream. If the stream ends
+    /// without that happening, the returned future resolves to `true`. Any
+    /// error on the stream or from the predicate will cause the future to
+    /// resolve to that error.
     ///
-    /// In general, panics within a stream can propagate all the way out to the
+    /// Note that the `Result` type implements the `IntoFuture` trait so it is
+    /// possible to simply return a `Result` from the predicate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::{Future, Stream};
+    /// use futures::stream;
+    ///
+    /// let stream = stream::iter_ok::<_, ()>(vec![1, 2, 3]);
+    /// assert_eq!(stream.all(|i| Ok(i < 3)).wait(), Ok(false));
+    /// ```
+    fn all<P, R>(self, pred: P) -> All<Self, P, R>
+        where P: FnMut(Self::Item) -> R,
+              R: IntoFuture<Item=bool, Error=Self::Error>,
+              Self: Sized
+    {
+        all::new(self, pred)
+    }
+
+    /// Runs this stream to completion, executing the provided closure for each
+    /// element on the stream.
+    ///
+    /// The closure provided will be called for each item this stream resolves
+    /// to successfully, producing a future. That future will then be executed
+    /// to completion before moving on to the next item.
+    ///
+    /// The returned value is a `Future` where the `Item` type is `()` and
+    /// errors are otherwise threaded through. Any error on the stream or in the
+    /// closure will cause iteration to be halted immediately and the future
+    /// will resolve to that error.
+    fn for_each<F, U>(self, f: F) -> ForEach<Self, F, U>
+        where F: FnMut(Self::Item) -> U,
+              U: IntoFuture<Item=(), Error = Self::Error>,
+              Self: Sized
+    {
+        for_each::new(self, f)
+    }
+
+    /// Runs this stream to completion, executing the provided closure for each
+    /// element on the stream, with up to `limit` of the resulting futures
+    /// running concurrently.
+    ///
+    /// The closure provided will be called for each item this stream resolves
+    /// to successfully, producing a future. Unlike `for_each`, the next item
+    /// is pulled from this stream without waiting for that future to
+    /// complete, as long as fewer than `limit` futures are running. The
+    /// returned future resolves once this stream has ended and all of the
+    /// futures have completed.
+    ///
+    /// The returned value is a `Future` where the `Item` type is `()` and
+    /// errors are otherwise threaded through. Any error on the stream or in
+    /// one of the futures will cause iteration to be halted immediately, the
+    /// other futures to be dropped, and the future to resolve to that error.
+    ///
+    /// This method is only available when the `use_std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `limit` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// use futures::{Future, Stream};
+    /// use futures::stream;
+    ///
+    /// let total = Arc::new(AtomicUsize::new(0));
+    /// let total2 = total.clone();
+    /// let work = stream::iter_ok::<_, ()>(1..5).for_each_concurrent(2, move |i| {
+    ///     total2.fetch_add(i, Ordering::SeqCst);
+    ///     Ok(())
+    /// });
+    /// work.wait().unwrap();
+    /// assert_eq!(total.load(Ordering::SeqCst), 10);
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn for_each_concurrent<F, U>(self, limit: usize, f: F) -> ForEachConcurrent<Self, F, U>
+        where F: FnMut(Self::Item) -> U,
+              U: IntoFuture<Item=(), Error = Self::Error>,
+              Self: Sized
+    {
+        for_each_concurrent::new(self, limit, f)
+    }
+
+    /// Runs this stream to completion, executing the provided closure for each
+    /// element on the stream, with up to `limit` of the resulting futures
+    /// running concurrently, and stopping early on the first error.
+    ///
+    /// This combinator behaves like `for_each_concurrent`, except for what
+    /// happens when an error occurs, either on the stream or in one of the
+    /// futures. Rather than dropping all of the other futures right away, no
+    /// further items are pulled from this stream and the futures which
+    /// are already running are driven to completion. Once they've all
+    /// finished, the returned future resolves to the first error which
+    /// occurred. Any errors after the first one are discarded.
+    ///
+    /// This method is only available when the `use_std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `limit` is zero.
+    #[cfg(feature = "use_std")]
+    fn try_for_each_concurrent<F, U>(self, limit: usize, f: F)
+                                     -> TryForEachConcurrent<Self, F, U>
+        where F: FnMut(Self::Item) -> U,
+              U: IntoFuture<Item=(), Error = Self::Error>,
+              Self: Sized
+    {
+        for_each_concurrent::try_new(self, limit, f)
+    }
+
+    /// Map this stream's error to any error implementing `From` for
+    /// this stream's `Error`, returning a new stream.
+    ///
+    /// This function does for streams what `try!` does for `Result`,
+    /// by letting the compiler infer the type of the resulting error.
+    /// Just as `map_err` above, this is useful for example to ensure
+    /// that streams have the same error type when used with
+    /// combinators.
+    ///
+    /// Note that this function consumes the receiving stream and returns a
+    /// wrapped version of it.
+    fn from_err<E: From<Self::Error>>(self) -> FromErr<Self, E>
+        where Self: Sized,
+    {
+        from_err::new(self)
+    }
+
+    /// Creates a new stream of at most `amt` items of the underlying stream.
+    ///
+    /// Once `amt` items have been yielded from this stream then it will always
+    /// return that the stream is done.
+    ///
+    /// # Errors
+    ///
+    /// Any errors yielded from underlying stream, before the desired amount of
+    /// items is reached, are passed through and do not affect the total number
+    /// of items taken.
+    fn take(self, amt: u64) -> Take<Self>
+        where Self: Sized
+    {
+        take::new(self, amt)
+    }
+
+    /// Creates a stream which yields each item of this stream along with its
+    /// index, starting from zero.
+    ///
+    /// # Errors
+    ///
+    /// All errors yielded from underlying stream are passed through and do not
+    /// affect the index of the following items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::{Future, Stream};
+    /// use futures::stream;
+    ///
+    /// let letters = stream::iter_ok::<_, ()>(vec!['a', 'b']);
+    /// let pairs = letters.enumerate().collect().wait();
+    /// assert_eq!(pairs, Ok(vec![(0, 'a'), (1, 'b')]));
+    /// ```
+    fn enumerate(self) -> Enumerate<Self>
+        where Self: Sized
+    {
+        enumerate::new(self)
+    }
+
+    /// Creates a new stream which skips `amt` items of the underlying stream.
+    ///
+    /// Once `amt` items have been skipped from this stream then it will always
+    /// return the remaining items on this stream.
+    ///
+    /// # Errors
+    ///
+    /// All errors yielded from underlying stream are passed through and do not
+    /// affect the total number of items skipped.
+    fn skip(self, amt: u64) -> Skip<Self>
+        where Self: Sized
+    {
+        skip::new(self, amt)
+    }
+
+    /// Fuse a stream such that `poll` will never again be called once it has
+    /// finished.
+    ///
+    /// Currently once a stream has returned `None` from `poll` any further
+    /// calls could exhibit bad behavior such as block forever, panic, never
+    /// return, etc. If it is known that `poll` may be called after stream has
+    /// already finished, then this method can be used to ensure that it has
+    /// defined semantics.
+    ///
+    /// Once a stream has been `fuse`d and it finishes, then it will forever
+    /// return `None` from `poll`. This, unlike for the traits `poll` method,
+    /// is guaranteed.
+    ///
+    /// Also note that as soon as this stream returns `None` it will be dropped
+    /// to reclaim resources associated with it.
+    fn fuse(self) -> Fuse<Self>
+        where Self: Sized
+    {
+        fuse::new(self)
+    }
+
+    /// Borrows a stream, rather than consuming it.
+    ///
+    /// This is useful to allow applying stream adaptors while still retaining
+    /// ownership of the original stream, for example to consume only part of
+    /// it with one combinator chain and then carry on using the rest.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::{Future, Stream};
+    /// use futures::stream;
+    ///
+    /// let mut s = stream::iter_ok::<_, ()>(vec![1, 2, 3, 4, 5]);
+    ///
+    /// let first = s.by_ref().take(2).collect().wait();
+    /// assert_eq!(first, Ok(vec![1, 2]));
+    ///
+    /// let rest = s.collect().wait();
+    /// assert_eq!(rest, Ok(vec![3, 4, 5]));
+    /// ```
+    fn by_ref(&mut self) -> &mut Self
+        where Self: Sized
+    {
+        self
+    }
+
+    /// Runs the closure `f` if this stream is dropped before it has finished.
+    ///
+    /// The closure is run when the returned stream is dropped, unless the
+    /// stream had already returned `None` from `poll`. This gives a hook to
+    /// detect that a consumer has lost interest in the stream, for example
+    /// to release resources held on its behalf elsewhere. Errors don't count
+    /// as the stream finishing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cell::Cell;
+    ///
+    /// use futures::{Future, Stream};
+    /// use futures::stream;
+    ///
+    /// let cancelled = Cell::new(false);
+    /// let s = stream::iter_ok::<_, ()>(vec![1, 2, 3])
+    ///     .inspect_drop(|| cancelled.set(true));
+    ///
+    /// // Only the first item is taken before the stream is dropped
+    /// let (first, rest) = s.into_future().wait().ok().unwrap();
+    /// assert_eq!(first, Some(1));
+    /// drop(rest);
+    /// assert!(cancelled.get());
+    /// ```
+    fn inspect_drop<F>(self, f: F) -> InspectDrop<Self, F>
+        where F: FnOnce(),
+              Self: Sized
+    {
+        inspect_drop::new(self, f)
+    }
+
+    /// Catches unwinding panics while polling the stream.
+    ///
+    /// Caught panic (if any) will be the last element of the resulting stream.
+    ///
+    /// In general, panics within a stream can propagate all the way out to the
     /// task level. This combinator makes it possible to halt unwinding within
     /// the stream itself. It's most commonly used within task executors. This
     /// method should not be used for error handling.
@@ -842,6 +1570,209 @@ pub trait Stream {
         buffer_unordered::new(self, amt)
     }
 
+    /// Flattens a stream of streams into just one continuous stream, polling
+    /// up to `limit` of the inner streams concurrently.
+    ///
+    /// Unlike `flatten`, which exhausts each inner stream before moving on to
+    /// the next one, this adaptor will pull up to `limit` inner streams from
+    /// this stream and yield their items in whatever order they become
+    /// available. A slow inner stream will therefore not hold up items from
+    /// the others. Once an inner stream finishes, another one is pulled from
+    /// this stream to take its place.
+    ///
+    /// Errors from this stream are converted with `From` and passed through,
+    /// as are errors from the inner streams. An inner stream that produces an
+    /// error continues to be polled.
+    ///
+    /// This method is only available when the `use_std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `limit` is zero.
+    #[cfg(feature = "use_std")]
+    fn flatten_unordered(self, limit: usize) -> FlattenUnordered<Self>
+        where Self::Item: Stream,
+              <Self::Item as Stream>::Error: From<Self::Error>,
+              Self: Sized
+    {
+        flatten_unordered::new(self, limit)
+    }
+
+    /// Fans this stream out to `n` subscribers, each of which receives every
+    /// item.
+    ///
+    /// Every item (and error) produced by this stream is cloned into a buffer
+    /// for each of the returned handles, which holds at most `capacity`
+    /// items. When a new item arrives while a subscriber's buffer is full,
+    /// `policy` decides what happens: with `LagPolicy::Block` no further
+    /// items are pulled from this stream until the slowest subscriber has
+    /// caught up, and with `LagPolicy::DropOldest` the lagging subscriber
+    /// misses out on its oldest buffered item instead.
+    ///
+    /// Dropping a handle unsubscribes it, so it no longer holds up the other
+    /// subscribers.
+    ///
+    /// This method is only available when the `use_std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `capacity` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::{Future, Stream};
+    /// use futures::stream::{self, LagPolicy};
+    ///
+    /// let numbers = stream::iter_ok::<_, ()>(vec![1, 2, 3]);
+    /// let mut handles = numbers.fan_out(2, 4, LagPolicy::Block);
+    /// let b = handles.pop().unwrap();
+    /// let a = handles.pop().unwrap();
+    /// let both = a.collect().join(b.collect());
+    /// assert_eq!(both.wait(), Ok((vec![1, 2, 3], vec![1, 2, 3])));
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn fan_out(self, n: usize, capacity: usize, policy: LagPolicy)
+               -> ::std::vec::Vec<FanOut<Self>>
+        where Self::Item: Clone,
+              Self::Error: Clone,
+              Self: Sized
+    {
+        fan_out::new(self, n, capacity, policy)
+    }
+
+    /// Turns this stream into one which can be shared between several
+    /// subscribers, replaying recent items to late subscribers.
+    ///
+    /// Additional subscribers are created by cloning the returned handle. The
+    /// most recent `buffer_size` items (and errors) produced by this stream
+    /// are kept in a replay buffer, and a freshly cloned handle starts with
+    /// the oldest item in that buffer before continuing with live items.
+    ///
+    /// This stream is polled whenever a subscriber has caught up and wants
+    /// another item, so subscribers never hold each other up. A subscriber
+    /// which falls more than `buffer_size` items behind misses the items
+    /// which were pushed out of the buffer in the meantime.
+    ///
+    /// This method is only available when the `use_std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `buffer_size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::{Future, Stream};
+    /// use futures::stream;
+    ///
+    /// let mut a = stream::iter_ok::<_, ()>(vec![1, 2, 3, 4]).share(2);
+    /// let b = a.clone();
+    /// assert_eq!(a.by_ref().take(3).collect().wait(), Ok(vec![1, 2, 3]));
+    ///
+    /// // A late subscriber catches up on the last two items
+    /// let c = a.clone();
+    /// assert_eq!(c.collect().wait(), Ok(vec![2, 3, 4]));
+    /// assert_eq!(a.collect().wait(), Ok(vec![4]));
+    ///
+    /// // A subscriber which fell behind skips items no longer buffered
+    /// assert_eq!(b.collect().wait(), Ok(vec![3, 4]));
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn share(self, buffer_size: usize) -> Share<Self>
+        where Self::Item: Clone,
+              Self::Error: Clone,
+              Self: Sized
+    {
+        share::new(self, buffer_size)
+    }
+
+    /// Splits this stream into sub-streams of items which share the same key.
+    ///
+    /// The closure `f` is called with a reference to each item to compute its
+    /// key. The returned stream yields a `(key, group)` pair for every key
+    /// that's seen for the first time, where `group` is a stream of all items
+    /// with that key. If a group is dropped then items with its key are
+    /// routed to a brand new group instead.
+    ///
+    /// Only one item is buffered at a time, so if that item belongs to a
+    /// group (or a new group) which isn't being polled then no further items
+    /// will be pulled from this stream until it is. If the returned stream of
+    /// groups is dropped, items for keys without a live group are discarded.
+    ///
+    /// Errors from this stream are passed through to whichever of the
+    /// returned streams is being polled when they happen.
+    ///
+    /// This method is only available when the `use_std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::{Future, Stream};
+    /// use futures::stream;
+    ///
+    /// let words = stream::iter_ok::<_, ()>(vec!["apple", "bee", "avocado"]);
+    /// let groups = words.group_by(|word| word.chars().next().unwrap());
+    ///
+    /// // Each group is collected concurrently, so that no group is left
+    /// // holding up the others.
+    /// let groups = groups.map(|(letter, group)| {
+    ///     group.collect().map(move |words| (letter, words))
+    /// });
+    /// let mut groups = groups.buffer_unordered(10).collect().wait().unwrap();
+    /// groups.sort();
+    /// assert_eq!(groups, vec![('a', vec!["apple", "avocado"]), ('b', vec!["bee"])]);
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn group_by<K, F>(self, f: F) -> GroupBy<Self, K, F>
+        where F: FnMut(&Self::Item) -> K,
+              K: Clone + Eq + ::std::hash::Hash,
+              Self: Sized
+    {
+        group_by::new(self, f)
+    }
+
+    /// Splits this stream in two, according to a predicate.
+    ///
+    /// The closure `pred` is called with a reference to each item. The first
+    /// returned stream yields the items for which `pred` returned `true`, and
+    /// the second one the items for which it returned `false`.
+    ///
+    /// Only one item is buffered at a time, so if that item belongs to the
+    /// half which isn't being polled then no further items will be pulled
+    /// from this stream until it is. This means that the two halves should
+    /// be consumed concurrently. If one of the halves is dropped, the items
+    /// which would have been yielded by it are discarded.
+    ///
+    /// Errors from this stream are passed through to whichever half is being
+    /// polled when they happen.
+    ///
+    /// This method is only available when the `use_std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::{Future, Stream};
+    /// use futures::stream;
+    ///
+    /// let numbers = stream::iter_ok::<_, ()>(vec![1, 2, 3, 4, 5]);
+    /// let (evens, odds) = numbers.partition(|i| i % 2 == 0);
+    /// let both = evens.collect().join(odds.collect());
+    /// assert_eq!(both.wait(), Ok((vec![2, 4], vec![1, 3, 5])));
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn partition<P>(self, pred: P) -> (Partition<Self, P>, Partition<Self, P>)
+        where P: FnMut(&Self::Item) -> bool,
+              Self: Sized
+    {
+        partition::new(self, pred)
+    }
+
     /// An adapter for merging the output of two streams.
     ///
     /// The merged stream produces items from one or both of the underlying
@@ -892,6 +1823,33 @@ pub trait Stream {
         chain::new(self, other)
     }
 
+    /// Creates a stream which repeats this stream endlessly.
+    ///
+    /// A copy of this stream is made up front, and every time the current
+    /// copy finishes it is replaced with a fresh clone of that original. This
+    /// means that this stream's `Clone` implementation must produce a stream
+    /// which starts from the beginning, as is the case for `stream::iter_ok`.
+    /// If a full pass of the stream doesn't yield anything, including
+    /// errors, the returned stream finishes instead of spinning forever.
+    ///
+    /// Errors are passed through without restarting the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::{Future, Stream};
+    /// use futures::stream;
+    ///
+    /// let stream = stream::iter_ok::<_, ()>(vec![1, 2]);
+    /// let cycled = stream.cycle().take(5).collect().wait();
+    /// assert_eq!(cycled, Ok(vec![1, 2, 1, 2, 1]));
+    /// ```
+    fn cycle(self) -> Cycle<Self>
+        where Self: Sized + Clone
+    {
+        cycle::new(self)
+    }
+
     /// Creates a new stream which exposes a `peek` method.
     ///
     /// Calling `peek` returns a reference to the next item in the stream.
@@ -928,6 +1886,219 @@ pub trait Stream {
         chunks::new(self, capacity)
     }
 
+    /// An adaptor for batching up items of the stream by weight instead of
+    /// by count.
+    ///
+    /// The closure `f` is called with each item to determine its weight, for
+    /// example its size in bytes. Items are buffered into a vector until
+    /// their total weight reaches `max_weight`, at which point the vector is
+    /// yielded. An item which would take a batch over the limit is held back
+    /// for the next batch instead, so batches only exceed `max_weight` when
+    /// a single item on its own does.
+    ///
+    /// Like with `chunks`, a partial batch is yielded if the underlying
+    /// stream ends or produces an error, and errors are passed through the
+    /// stream unbuffered.
+    ///
+    /// This method is only available when the `use_std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `max_weight` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::{Future, Stream};
+    /// use futures::stream;
+    ///
+    /// let words = stream::iter_ok::<_, ()>(vec!["a", "bb", "ccc", "dddd"]);
+    /// let batches = words.batch_weighted(5, |w| w.len());
+    /// assert_eq!(batches.collect().wait(),
+    ///            Ok(vec![vec!["a", "bb"], vec!["ccc"], vec!["dddd"]]));
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn batch_weighted<F>(self, max_weight: usize, f: F) -> BatchWeighted<Self, F>
+        where F: FnMut(&Self::Item) -> usize,
+              Self: Sized
+    {
+        batch_weighted::new(self, max_weight, f)
+    }
+
+    /// An adaptor for yielding overlapping windows of the most recent items
+    /// of this stream.
+    ///
+    /// Once `size` items have been produced by this stream, every new item
+    /// results in a vector of the last `size` items, oldest first, being
+    /// yielded. This is similar to `slice::windows`, and if this stream ends
+    /// before producing `size` items then no windows are yielded at all. This
+    /// is useful for computing moving averages or detecting patterns.
+    ///
+    /// Errors are passed through the stream without affecting the window.
+    ///
+    /// This method is only available when the `use_std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::{Future, Stream};
+    /// use futures::stream;
+    ///
+    /// let numbers = stream::iter_ok::<_, ()>(vec![1, 2, 3, 4]);
+    /// let windows = numbers.windows(3).collect().wait();
+    /// assert_eq!(windows, Ok(vec![vec![1, 2, 3], vec![2, 3, 4]]));
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn windows(self, size: usize) -> Windows<Self>
+        where Self::Item: Clone,
+              Self: Sized
+    {
+        windows::new(self, size)
+    }
+
+    /// An adaptor for chunking up items of the stream inside a vector, with a
+    /// deadline for each chunk.
+    ///
+    /// This combinator behaves like `chunks`, buffering at most `capacity`
+    /// items before yielding them, except that a chunk is also yielded once
+    /// `duration` has elapsed since its first item was buffered, even if it
+    /// isn't full yet. The deadline is requested from `timer`. This is
+    /// typically used to batch up writes without delaying any one item for
+    /// too long.
+    ///
+    /// If the underlying stream ended then the buffered items, if any, are
+    /// yielded. If an error happens from the underlying stream then the
+    /// currently buffered items will be yielded before the error. Errors from
+    /// the timer are converted with `From` and passed through, after which
+    /// the buffered items are yielded on the next poll.
+    ///
+    /// This method is only available when the `use_std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `capacity` is zero.
+    #[cfg(feature = "use_std")]
+    fn chunks_timeout<T>(self, capacity: usize, duration: Duration, timer: T)
+                         -> ChunksTimeout<Self, T>
+        where T: Timer,
+              Self::Error: From<<T::Sleep as Future>::Error>,
+              Self: Sized
+    {
+        chunks_timeout::new(self, capacity, duration, timer)
+    }
+
+    /// Creates a stream which only yields an item once this stream has been
+    /// quiet for `duration`.
+    ///
+    /// Every time this stream produces an item the item is held on to and a
+    /// new sleep of `duration` is requested from `timer`, discarding any
+    /// previously held item. Once the sleep completes without another item
+    /// arriving, the most recent item is yielded. This is typically used to
+    /// coalesce bursts of change notifications into a single event.
+    ///
+    /// If the underlying stream finishes while an item is held, that item is
+    /// yielded immediately before the returned stream finishes. Errors from
+    /// the underlying stream are passed through immediately, and errors from
+    /// the timer are converted with `From` and passed through as well.
+    ///
+    /// This method is only available when the `use_std` feature of this
+    /// library is activated, and it is activated by default.
+    #[cfg(feature = "use_std")]
+    fn debounce<T>(self, duration: Duration, timer: T) -> Debounce<Self, T>
+        where T: Timer,
+              Self::Error: From<<T::Sleep as Future>::Error>,
+              Self: Sized
+    {
+        debounce::new(self, duration, timer)
+    }
+
+    /// Creates a stream which yields the most recent item of this stream once
+    /// every `interval`.
+    ///
+    /// The returned stream starts a periodic tick using `timer` the first time
+    /// it's polled. Items produced by this stream in between ticks are held on
+    /// to, with each new item replacing the previous one. On every tick the
+    /// held item, if any, is yielded and all others are dropped. This is
+    /// useful for downsampling high-frequency streams such as telemetry.
+    ///
+    /// If the underlying stream finishes while an item is held, that item is
+    /// yielded immediately before the returned stream finishes. Errors from
+    /// the underlying stream are passed through immediately, and errors from
+    /// the timer are converted with `From` and passed through as well.
+    ///
+    /// This method is only available when the `use_std` feature of this
+    /// library is activated, and it is activated by default.
+    #[cfg(feature = "use_std")]
+    fn sample<T>(self, interval: Duration, timer: T) -> Sample<Self, T>
+        where T: Timer,
+              Self::Error: From<<T::Sleep as Future>::Error>,
+              Self: Sized
+    {
+        sample::new(self, interval, timer)
+    }
+
+    /// Creates a stream which limits the rate at which items are yielded from
+    /// this stream, using a token bucket.
+    ///
+    /// The bucket holds up to `burst` tokens and starts out full. Yielding an
+    /// item takes a token out of the bucket, and while the bucket isn't full
+    /// a token is added back every `period`, as measured by `timer`. This
+    /// means that bursts of up to `burst` items are passed through
+    /// immediately, while the sustained rate is capped at one item per
+    /// `period`. When the bucket is empty, this stream isn't polled until a
+    /// token is available.
+    ///
+    /// Errors from this stream are passed through without taking a token,
+    /// and errors from the timer are converted with `From` and passed through
+    /// as well.
+    ///
+    /// This method is only available when the `use_std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `burst` is zero.
+    #[cfg(feature = "use_std")]
+    fn rate_limit<T>(self, period: Duration, burst: usize, timer: T) -> RateLimit<Self, T>
+        where T: Timer,
+              Self::Error: From<<T::Sleep as Future>::Error>,
+              Self: Sized
+    {
+        rate_limit::new(self, period, burst, timer)
+    }
+
+    /// Creates a stream which yields an error whenever this stream takes
+    /// longer than `duration` to produce its next item.
+    ///
+    /// The clock is started the first time the returned stream is polled, and
+    /// restarted every time this stream produces an item, an error, or the
+    /// end of the stream. If `duration` passes in between, the returned
+    /// stream yields an error created from `timer::Elapsed` with `From`, and
+    /// the clock is restarted. This stream keeps being polled after a
+    /// timeout, so it's up to the consumer to decide whether to carry on,
+    /// which makes this useful to detect dead connections.
+    ///
+    /// Errors from the timer are converted with `From` and passed through as
+    /// well.
+    ///
+    /// This method is only available when the `use_std` feature of this
+    /// library is activated, and it is activated by default.
+    #[cfg(feature = "use_std")]
+    fn timeout<T>(self, duration: Duration, timer: T) -> Timeout<Self, T>
+        where T: Timer,
+              Self::Error: From<Elapsed> + From<<T::Sleep as Future>::Error>,
+              Self: Sized
+    {
+        timeout::new(self, duration, timer)
+    }
+
     /// Creates a stream that selects the next element from either this stream
     /// or the provided one, whichever is ready first.
     ///
@@ -990,4 +2161,8 @@ impl<'a, S: ?Sized + Stream> Stream for &'a mut S {
     fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
         (**self).poll()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (**self).size_hint()
+    }
 }
@@ -31,4 +31,8 @@ impl<T, E> Stream for Once<T, E> {
     fn poll(&mut self) -> Poll<Option<T>, E> {
         self.0.poll()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
 }
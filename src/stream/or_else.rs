@@ -77,4 +77,10 @@ impl<S, F, U> Stream for OrElse<S, F, U>
             Ok(Async::NotReady) => Ok(Async::NotReady)
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending = if self.future.is_some() { 1 } else { 0 };
+        let (lower, upper) = self.stream.size_hint();
+        (lower.saturating_add(pending), upper.and_then(|n| n.checked_add(pending)))
+    }
 }
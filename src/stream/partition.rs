@@ -0,0 +1,173 @@
+use std::fmt;
+use std::prelude::v1::*;
+use std::sync::{Arc, Mutex};
+
+use {Async, Poll};
+use stream::{Stream, Fuse};
+use task::{self, Task};
+
+/// One half of a stream which has been partitioned in two by a predicate.
+///
+/// The two halves of the partition are produced by the `Stream::partition`
+/// method. The first half yields the items for which the predicate returned
+/// `true` and the second half those for which it returned `false`.
+#[must_use = "streams do nothing unless polled"]
+pub struct Partition<S, P>
+    where S: Stream,
+{
+    side: bool,
+    inner: Arc<Mutex<Inner<S, P>>>,
+}
+
+struct Inner<S, P>
+    where S: Stream,
+{
+    stream: Fuse<S>,
+    pred: P,
+
+    // The single item which has been pulled from the underlying stream but
+    // not yet taken by the half it belongs to. While this is occupied no
+    // further items are pulled, so neither half can buffer unboundedly.
+    pending: Option<(bool, S::Item)>,
+
+    // The task of each half which is blocked, either on `pending` or on the
+    // underlying stream, indexed by side. Both are woken whenever anything
+    // changes.
+    blocked: [Option<Task>; 2],
+
+    // Which halves of the partition are still alive, indexed by side.
+    alive: [bool; 2],
+}
+
+pub fn new<S, P>(s: S, pred: P) -> (Partition<S, P>, Partition<S, P>)
+    where S: Stream,
+          P: FnMut(&S::Item) -> bool,
+{
+    let inner = Arc::new(Mutex::new(Inner {
+        stream: super::fuse::new(s),
+        pred: pred,
+        pending: None,
+        blocked: [None, None],
+        alive: [true, true],
+    }));
+    let yes = Partition {
+        side: true,
+        inner: inner.clone(),
+    };
+    let no = Partition {
+        side: false,
+        inner: inner,
+    };
+    (yes, no)
+}
+
+impl<S, P> Inner<S, P>
+    where S: Stream,
+{
+    fn unpark_blocked(&mut self) {
+        for task in self.blocked.iter_mut() {
+            if let Some(task) = task.take() {
+                task.unpark();
+            }
+        }
+    }
+}
+
+impl<S, P> Inner<S, P>
+    where S: Stream,
+          P: FnMut(&S::Item) -> bool,
+{
+    fn poll(&mut self, side: bool) -> Poll<Option<S::Item>, S::Error> {
+        loop {
+            match self.pending.take() {
+                Some((s, item)) => {
+                    if s == side {
+                        self.unpark_blocked();
+                        return Ok(Async::Ready(Some(item)))
+                    }
+                    self.pending = Some((s, item));
+                    self.blocked[side as usize] = Some(task::park());
+                    return Ok(Async::NotReady)
+                }
+                None => {}
+            }
+
+            let item = match self.stream.poll() {
+                Ok(Async::Ready(Some(item))) => item,
+                // The underlying stream only notifies the last task to poll
+                // it, so make sure the other half finds out too.
+                Ok(Async::Ready(None)) => {
+                    self.unpark_blocked();
+                    return Ok(Async::Ready(None))
+                }
+                Ok(Async::NotReady) => {
+                    self.blocked[side as usize] = Some(task::park());
+                    return Ok(Async::NotReady)
+                }
+                Err(e) => {
+                    self.unpark_blocked();
+                    return Err(e)
+                }
+            };
+            let s = (self.pred)(&item);
+            if s == side {
+                return Ok(Async::Ready(Some(item)))
+            }
+            // Items for a half which has been dropped are discarded.
+            if self.alive[s as usize] {
+                self.pending = Some((s, item));
+                self.unpark_blocked();
+            }
+        }
+    }
+}
+
+impl<S, P> Partition<S, P>
+    where S: Stream,
+{
+    /// Returns `true` if this is the half of the partition which yields the
+    /// items matching the predicate.
+    pub fn is_matching(&self) -> bool {
+        self.side
+    }
+}
+
+impl<S, P> Stream for Partition<S, P>
+    where S: Stream,
+          P: FnMut(&S::Item) -> bool,
+{
+    type Item = S::Item;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<S::Item>, S::Error> {
+        self.inner.lock().unwrap().poll(self.side)
+    }
+}
+
+impl<S, P> Drop for Partition<S, P>
+    where S: Stream,
+{
+    fn drop(&mut self) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.alive[self.side as usize] = false;
+            let ours = match inner.pending {
+                Some((s, _)) => s == self.side,
+                None => false,
+            };
+            if ours {
+                inner.pending = None;
+            }
+            inner.unpark_blocked();
+        }
+    }
+}
+
+impl<S, P> fmt::Debug for Partition<S, P>
+    where S: Stream,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Partition")
+            .field("side", &self.side)
+            .finish()
+    }
+}
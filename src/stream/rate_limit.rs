@@ -0,0 +1,128 @@
+use std::time::Duration;
+
+use {Async, Future, Poll};
+use stream::Stream;
+use timer::Timer;
+
+/// A stream combinator which limits the rate at which items are yielded from
+/// the underlying stream using a token bucket.
+///
+/// This structure is produced by the `Stream::rate_limit` method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct RateLimit<S, T>
+    where T: Timer,
+{
+    stream: S,
+    timer: T,
+    period: Duration,
+    burst: usize,
+    tokens: usize,
+    refill: Option<T::Sleep>,
+}
+
+pub fn new<S, T>(s: S, period: Duration, burst: usize, timer: T) -> RateLimit<S, T>
+    where S: Stream,
+          T: Timer,
+          S::Error: From<<T::Sleep as Future>::Error>,
+{
+    assert!(burst > 0);
+
+    RateLimit {
+        stream: s,
+        timer: timer,
+        period: period,
+        burst: burst,
+        tokens: burst,
+        refill: None,
+    }
+}
+
+impl<S, T> RateLimit<S, T>
+    where T: Timer,
+{
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+impl<S, T> ::sink::Sink for RateLimit<S, T>
+    where S: ::sink::Sink,
+          T: Timer,
+{
+    type SinkItem = S::SinkItem;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: S::SinkItem) -> ::StartSend<S::SinkItem, S::SinkError> {
+        self.stream.start_send(item)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.poll_complete()
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.close()
+    }
+}
+
+impl<S, T> Stream for RateLimit<S, T>
+    where S: Stream,
+          T: Timer,
+          S::Error: From<<T::Sleep as Future>::Error>,
+{
+    type Item = S::Item;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<S::Item>, S::Error> {
+        // Add a token back into the bucket every `period`, for as long as
+        // the bucket isn't full.
+        loop {
+            if let Some(ref mut refill) = self.refill {
+                match refill.poll() {
+                    Ok(Async::Ready(())) => self.tokens += 1,
+                    Ok(Async::NotReady) => break,
+                    Err(e) => {
+                        self.refill = None;
+                        return Err(From::from(e))
+                    }
+                }
+            }
+            self.refill = None;
+            if self.tokens >= self.burst {
+                break
+            }
+            self.refill = Some(self.timer.sleep(self.period));
+        }
+
+        if self.tokens == 0 {
+            return Ok(Async::NotReady)
+        }
+
+        let item = try_ready!(self.stream.poll());
+        if item.is_some() {
+            self.tokens -= 1;
+        }
+        Ok(Async::Ready(item))
+    }
+}
@@ -0,0 +1,111 @@
+use {IntoFuture, Future, Poll, Async};
+use stream::Stream;
+
+/// A stream combinator which attempts to recover from errors produced by a
+/// stream, optionally replacing them with an item.
+///
+/// This structure is produced by the `Stream::recover` method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Recover<S, F, U>
+    where U: IntoFuture,
+{
+    stream: S,
+    future: Option<U::Future>,
+    f: F,
+}
+
+pub fn new<S, F, U>(s: S, f: F) -> Recover<S, F, U>
+    where S: Stream,
+          F: FnMut(S::Error) -> U,
+          U: IntoFuture<Item=Option<S::Item>>,
+{
+    Recover {
+        stream: s,
+        future: None,
+        f: f,
+    }
+}
+
+impl<S, F, U> Recover<S, F, U>
+    where U: IntoFuture,
+{
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+impl<S, F, U> ::sink::Sink for Recover<S, F, U>
+    where S: ::sink::Sink, U: IntoFuture
+{
+    type SinkItem = S::SinkItem;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: S::SinkItem) -> ::StartSend<S::SinkItem, S::SinkError> {
+        self.stream.start_send(item)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.poll_complete()
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.close()
+    }
+}
+
+impl<S, F, U> Stream for Recover<S, F, U>
+    where S: Stream,
+          F: FnMut(S::Error) -> U,
+          U: IntoFuture<Item=Option<S::Item>>,
+{
+    type Item = S::Item;
+    type Error = U::Error;
+
+    fn poll(&mut self) -> Poll<Option<S::Item>, U::Error> {
+        loop {
+            if self.future.is_none() {
+                let err = match self.stream.poll() {
+                    Ok(Async::Ready(e)) => return Ok(Async::Ready(e)),
+                    Ok(Async::NotReady) => return Ok(Async::NotReady),
+                    Err(e) => e,
+                };
+                self.future = Some((self.f)(err).into_future());
+            }
+            match self.future.as_mut().unwrap().poll() {
+                Ok(Async::Ready(Some(item))) => {
+                    self.future = None;
+                    return Ok(Async::Ready(Some(item)))
+                }
+                // The error was dealt with without a replacement item, so
+                // move on to the next one.
+                Ok(Async::Ready(None)) => self.future = None,
+                Err(e) => {
+                    self.future = None;
+                    return Err(e)
+                }
+                Ok(Async::NotReady) => return Ok(Async::NotReady),
+            }
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use core::marker;
+
+use stream::Stream;
+
+use {Async, Poll};
+
+/// Stream that produces elements by repeatedly calling a closure.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct RepeatWith<F, E> {
+    f: F,
+    error: marker::PhantomData<fn() -> E>,
+}
+
+/// Create a stream which produces items by repeatedly calling `f`.
+///
+/// This is similar to `repeat`, except that the item doesn't need to be
+/// `Clone` and a fresh item is computed on each call to `poll`.
+///
+/// Stream never produces an error or EOF.
+///
+/// ```rust
+/// use futures::*;
+///
+/// let mut n = 0;
+/// let mut stream = stream::repeat_with::<_, bool, _>(move || { n += 1; n });
+/// assert_eq!(Ok(Async::Ready(Some(1))), stream.poll());
+/// assert_eq!(Ok(Async::Ready(Some(2))), stream.poll());
+/// assert_eq!(Ok(Async::Ready(Some(3))), stream.poll());
+/// ```
+pub fn repeat_with<F, E, T>(f: F) -> RepeatWith<F, E>
+    where F: FnMut() -> T
+{
+    RepeatWith {
+        f: f,
+        error: marker::PhantomData,
+    }
+}
+
+impl<F, E, T> Stream for RepeatWith<F, E>
+    where F: FnMut() -> T
+{
+    type Item = T;
+    type Error = E;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        Ok(Async::Ready(Some((self.f)())))
+    }
+}
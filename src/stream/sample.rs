@@ -0,0 +1,130 @@
+use std::time::Duration;
+
+use {Async, Future, Poll};
+use stream::{Stream, Fuse};
+use timer::Timer;
+
+/// A stream combinator which yields the most recent item of the underlying
+/// stream once per interval.
+///
+/// This structure is produced by the `Stream::sample` method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Sample<S, T>
+    where S: Stream,
+          T: Timer,
+{
+    stream: Fuse<S>,
+    timer: T,
+    interval: Duration,
+    item: Option<S::Item>,
+    sleep: Option<T::Sleep>,
+}
+
+pub fn new<S, T>(s: S, interval: Duration, timer: T) -> Sample<S, T>
+    where S: Stream,
+          T: Timer,
+          S::Error: From<<T::Sleep as Future>::Error>,
+{
+    Sample {
+        stream: s.fuse(),
+        timer: timer,
+        interval: interval,
+        item: None,
+        sleep: None,
+    }
+}
+
+impl<S, T> Sample<S, T>
+    where S: Stream,
+          T: Timer,
+{
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &S {
+        self.stream.get_ref()
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> &mut S {
+        self.stream.get_mut()
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> S {
+        self.stream.into_inner()
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+impl<S, T> ::sink::Sink for Sample<S, T>
+    where S: ::sink::Sink + Stream,
+          T: Timer,
+{
+    type SinkItem = S::SinkItem;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: S::SinkItem) -> ::StartSend<S::SinkItem, S::SinkError> {
+        self.stream.start_send(item)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.poll_complete()
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.close()
+    }
+}
+
+impl<S, T> Stream for Sample<S, T>
+    where S: Stream,
+          T: Timer,
+          S::Error: From<<T::Sleep as Future>::Error>,
+{
+    type Item = S::Item;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<S::Item>, S::Error> {
+        // Pull everything the underlying stream has ready, only remembering the
+        // latest item. Errors are passed through immediately.
+        loop {
+            match try!(self.stream.poll()) {
+                Async::Ready(Some(item)) => self.item = Some(item),
+                Async::Ready(None) => {
+                    self.sleep = None;
+                    return Ok(Async::Ready(self.item.take()))
+                }
+                Async::NotReady => break,
+            }
+        }
+
+        // Ticks start the first time we're polled, and each tick immediately
+        // schedules the next one. Ticks in which no item arrived are skipped.
+        loop {
+            if self.sleep.is_none() {
+                self.sleep = Some(self.timer.sleep(self.interval));
+            }
+            match self.sleep.as_mut().unwrap().poll() {
+                Ok(Async::Ready(())) => {
+                    self.sleep = None;
+                    if let Some(item) = self.item.take() {
+                        return Ok(Async::Ready(Some(item)))
+                    }
+                }
+                Ok(Async::NotReady) => return Ok(Async::NotReady),
+                Err(e) => {
+                    self.sleep = None;
+                    return Err(From::from(e))
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use {Async, IntoFuture, Future, Poll};
+use stream::Stream;
+
+/// A stream combinator which threads a piece of state through each item of
+/// the underlying stream, potentially ending the stream early.
+///
+/// This structure is produced by the `Stream::scan` method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Scan<S, St, F, U>
+    where U: IntoFuture,
+{
+    stream: S,
+    state: St,
+    f: F,
+    future: Option<U::Future>,
+    done: bool,
+}
+
+pub fn new<S, St, F, U, B>(s: S, initial_state: St, f: F) -> Scan<S, St, F, U>
+    where S: Stream,
+          F: FnMut(&mut St, S::Item) -> U,
+          U: IntoFuture<Item=Option<B>, Error=S::Error>,
+{
+    Scan {
+        stream: s,
+        state: initial_state,
+        f: f,
+        future: None,
+        done: false,
+    }
+}
+
+impl<S, St, F, U> Scan<S, St, F, U>
+    where U: IntoFuture,
+{
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+impl<S, St, F, U: IntoFuture> ::sink::Sink for Scan<S, St, F, U>
+    where S: ::sink::Sink
+{
+    type SinkItem = S::SinkItem;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: S::SinkItem) -> ::StartSend<S::SinkItem, S::SinkError> {
+        self.stream.start_send(item)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.poll_complete()
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.close()
+    }
+}
+
+impl<S, St, F, U, B> Stream for Scan<S, St, F, U>
+    where S: Stream,
+          F: FnMut(&mut St, S::Item) -> U,
+          U: IntoFuture<Item=Option<B>, Error=S::Error>,
+{
+    type Item = B;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<B>, S::Error> {
+        if self.done {
+            return Ok(Async::Ready(None));
+        }
+
+        if self.future.is_none() {
+            let item = match try_ready!(self.stream.poll()) {
+                None => return Ok(Async::Ready(None)),
+                Some(e) => e,
+            };
+            self.future = Some((self.f)(&mut self.state, item).into_future());
+        }
+        assert!(self.future.is_some());
+        match self.future.as_mut().unwrap().poll() {
+            Ok(Async::Ready(Some(e))) => {
+                self.future = None;
+                Ok(Async::Ready(Some(e)))
+            }
+            Ok(Async::Ready(None)) => {
+                self.future = None;
+                self.done = true;
+                Ok(Async::Ready(None))
+            }
+            Err(e) => {
+                self.future = None;
+                Err(e)
+            }
+            Ok(Async::NotReady) => Ok(Async::NotReady)
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use {Poll, Async};
+use stream::{Stream, Fuse};
+
+/// Which of the two streams of a `SelectWithStrategy` should be polled
+/// first.
+///
+/// This is returned by the strategy closure passed to
+/// `stream::select_with_strategy`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PollNext {
+    /// Poll the first stream first.
+    Left,
+    /// Poll the second stream first.
+    Right,
+}
+
+/// An adapter for merging the output of two streams, where a closure picks
+/// which stream gets polled first.
+///
+/// This stream is produced by the `stream::select_with_strategy` function.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct SelectWithStrategy<S1, S2, F> {
+    stream1: Fuse<S1>,
+    stream2: Fuse<S2>,
+    strategy: F,
+}
+
+/// Merges the output of two streams, polling them in the order chosen by
+/// `strategy`.
+///
+/// This is similar to `Stream::select`, but rather than polling the two
+/// streams in a round-robin fashion, the `strategy` closure is called every
+/// time the returned stream is polled to decide which of the two streams
+/// goes first. If that stream doesn't have an item ready, the other one is
+/// polled as well. Always returning `PollNext::Left`, for example, gives the
+/// first stream priority over the second, which is handy when control
+/// messages must not be stuck behind a backlog of data.
+///
+/// The returned stream finishes once both streams have finished. Errors
+/// are passed through as soon as they happen.
+///
+/// # Examples
+///
+/// ```
+/// use futures::{Future, Stream};
+/// use futures::stream::{self, PollNext};
+///
+/// let high = stream::iter_ok::<_, ()>(vec![1, 2]);
+/// let low = stream::iter_ok(vec![10, 20]);
+/// let s = stream::select_with_strategy(high, low, || PollNext::Left);
+/// assert_eq!(s.collect().wait(), Ok(vec![1, 2, 10, 20]));
+/// ```
+pub fn select_with_strategy<S1, S2, F>(stream1: S1, stream2: S2, strategy: F)
+                                       -> SelectWithStrategy<S1, S2, F>
+    where S1: Stream,
+          S2: Stream<Item = S1::Item, Error = S1::Error>,
+          F: FnMut() -> PollNext,
+{
+    SelectWithStrategy {
+        stream1: stream1.fuse(),
+        stream2: stream2.fuse(),
+        strategy: strategy,
+    }
+}
+
+impl<S1, S2, F> Stream for SelectWithStrategy<S1, S2, F>
+    where S1: Stream,
+          S2: Stream<Item = S1::Item, Error = S1::Error>,
+          F: FnMut() -> PollNext,
+{
+    type Item = S1::Item;
+    type Error = S1::Error;
+
+    fn poll(&mut self) -> Poll<Option<S1::Item>, S1::Error> {
+        let (a, b) = match (self.strategy)() {
+            PollNext::Left => {
+                (&mut self.stream1 as &mut Stream<Item=_, Error=_>,
+                 &mut self.stream2 as &mut Stream<Item=_, Error=_>)
+            }
+            PollNext::Right => {
+                (&mut self.stream2 as &mut Stream<Item=_, Error=_>,
+                 &mut self.stream1 as &mut Stream<Item=_, Error=_>)
+            }
+        };
+
+        let a_done = match try!(a.poll()) {
+            Async::Ready(Some(item)) => return Ok(Some(item).into()),
+            Async::Ready(None) => true,
+            Async::NotReady => false,
+        };
+
+        match try!(b.poll()) {
+            Async::Ready(Some(item)) => Ok(Some(item).into()),
+            Async::Ready(None) if a_done => Ok(None.into()),
+            Async::Ready(None) => Ok(Async::NotReady),
+            Async::NotReady => Ok(Async::NotReady),
+        }
+    }
+}
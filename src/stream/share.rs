@@ -0,0 +1,170 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::mem;
+use std::prelude::v1::*;
+use std::sync::{Arc, Mutex};
+
+use {Async, Poll};
+use stream::{Stream, Fuse};
+use task::{self, Task};
+
+/// A handle to a stream which is shared between several subscribers, each of
+/// which observes the items produced by the underlying stream.
+///
+/// This stream is produced by the `Stream::share` method, and additional
+/// subscribers are created by cloning a handle.
+#[must_use = "streams do nothing unless polled"]
+pub struct Share<S>
+    where S: Stream,
+{
+    // Sequence number of the next item this handle will yield.
+    pos: u64,
+    id: usize,
+    inner: Arc<Mutex<Inner<S>>>,
+}
+
+struct Inner<S>
+    where S: Stream,
+{
+    stream: Fuse<S>,
+    // The most recent items produced by the stream, oldest first.
+    buffer: VecDeque<Result<S::Item, S::Error>>,
+    capacity: usize,
+    // Sequence number of the item at the front of `buffer`.
+    start: u64,
+    // Tasks waiting on the underlying stream, keyed by the id of the handle
+    // which was polled. All of these are woken whenever a new item arrives.
+    blocked: HashMap<usize, Task>,
+    next_id: usize,
+}
+
+pub fn new<S>(s: S, buffer_size: usize) -> Share<S>
+    where S: Stream,
+          S::Item: Clone,
+          S::Error: Clone,
+{
+    assert!(buffer_size > 0);
+
+    Share {
+        pos: 0,
+        id: 0,
+        inner: Arc::new(Mutex::new(Inner {
+            stream: super::fuse::new(s),
+            buffer: VecDeque::with_capacity(buffer_size),
+            capacity: buffer_size,
+            start: 0,
+            blocked: HashMap::new(),
+            next_id: 1,
+        })),
+    }
+}
+
+impl<S> Inner<S>
+    where S: Stream,
+{
+    fn unpark_blocked(&mut self) {
+        for (_, task) in mem::replace(&mut self.blocked, HashMap::new()) {
+            task.unpark();
+        }
+    }
+}
+
+impl<S> Inner<S>
+    where S: Stream,
+          S::Item: Clone,
+          S::Error: Clone,
+{
+    fn poll(&mut self, id: usize, pos: &mut u64) -> Poll<Option<S::Item>, S::Error> {
+        loop {
+            // A handle which fell too far behind skips the items which have
+            // already been pushed out of the buffer.
+            if *pos < self.start {
+                *pos = self.start;
+            }
+            let index = (*pos - self.start) as usize;
+            if let Some(next) = self.buffer.get(index) {
+                *pos += 1;
+                return next.clone().map(|item| Async::Ready(Some(item)))
+            }
+
+            if self.stream.is_done() {
+                return Ok(Async::Ready(None))
+            }
+
+            let next = match self.stream.poll() {
+                Ok(Async::Ready(Some(item))) => Ok(item),
+                Ok(Async::Ready(None)) => {
+                    self.unpark_blocked();
+                    return Ok(Async::Ready(None))
+                }
+                Ok(Async::NotReady) => {
+                    // Replacing this handle's previous task, if any, keeps
+                    // repeated polls from piling up.
+                    self.blocked.insert(id, task::park());
+                    return Ok(Async::NotReady)
+                }
+                Err(e) => Err(e),
+            };
+            if self.buffer.len() >= self.capacity {
+                self.buffer.pop_front();
+                self.start += 1;
+            }
+            self.buffer.push_back(next);
+            self.unpark_blocked();
+        }
+    }
+}
+
+impl<S> Stream for Share<S>
+    where S: Stream,
+          S::Item: Clone,
+          S::Error: Clone,
+{
+    type Item = S::Item;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<S::Item>, S::Error> {
+        self.inner.lock().unwrap().poll(self.id, &mut self.pos)
+    }
+}
+
+impl<S> Clone for Share<S>
+    where S: Stream,
+{
+    /// Creates a new subscriber to the shared stream.
+    ///
+    /// The new handle starts with the oldest item which is still held in the
+    /// replay buffer, and then continues with live items.
+    fn clone(&self) -> Share<S> {
+        let mut inner = self.inner.lock().unwrap();
+        let id = inner.next_id;
+        inner.next_id += 1;
+        Share {
+            pos: inner.start,
+            id: id,
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<S> Drop for Share<S>
+    where S: Stream,
+{
+    fn drop(&mut self) {
+        // This handle may have been the one waiting on the underlying
+        // stream, so let another subscriber take over.
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.unpark_blocked();
+        }
+    }
+}
+
+impl<S> fmt::Debug for Share<S>
+    where S: Stream,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Share")
+            .field("pos", &self.pos)
+            .finish()
+    }
+}
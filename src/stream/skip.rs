@@ -56,4 +56,14 @@ impl<S> Stream for Skip<S>
 
         self.stream.poll()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.stream.size_hint();
+        let lower = if (lower as u64) > self.remaining {
+            lower - self.remaining as usize
+        } else {
+            0
+        };
+        (lower, upper)
+    }
 }
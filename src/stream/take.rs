@@ -58,4 +58,20 @@ impl<S> Stream for Take<S>
             Ok(Async::Ready(next))
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.remaining == 0 {
+            return (0, Some(0))
+        }
+
+        // Errors don't count towards the limit, so only the lower bound can
+        // be capped.
+        let (lower, upper) = self.stream.size_hint();
+        let lower = if (lower as u64) < self.remaining {
+            lower
+        } else {
+            self.remaining as usize
+        };
+        (lower, upper)
+    }
 }
@@ -0,0 +1,115 @@
+use {Async, Future, IntoFuture, Poll};
+use stream::Stream;
+
+/// A stream combinator which yields items of the underlying stream until a
+/// future resolves.
+///
+/// This structure is produced by the `Stream::take_until` method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct TakeUntil<S, F> where F: IntoFuture {
+    stream: S,
+    future: Option<F::Future>,
+    stopped: bool,
+}
+
+pub fn new<S, F>(s: S, f: F) -> TakeUntil<S, F>
+    where S: Stream,
+          F: IntoFuture,
+{
+    TakeUntil {
+        stream: s,
+        future: Some(f.into_future()),
+        stopped: false,
+    }
+}
+
+impl<S, F> TakeUntil<S, F> where F: IntoFuture {
+    /// Returns `true` once this stream has ended, either because the future
+    /// resolved or because the underlying stream finished.
+    pub fn is_stopped(&self) -> bool {
+        self.stopped
+    }
+
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+impl<S, F> ::sink::Sink for TakeUntil<S, F>
+    where S: ::sink::Sink,
+          F: IntoFuture,
+{
+    type SinkItem = S::SinkItem;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: S::SinkItem) -> ::StartSend<S::SinkItem, S::SinkError> {
+        self.stream.start_send(item)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.poll_complete()
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.close()
+    }
+}
+
+impl<S, F> Stream for TakeUntil<S, F>
+    where S: Stream,
+          F: IntoFuture,
+{
+    type Item = S::Item;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<S::Item>, S::Error> {
+        if self.stopped {
+            return Ok(Async::Ready(None))
+        }
+
+        // The future is checked first so that once it has resolved no more
+        // items are let through, even if the stream has some ready.
+        let resolved = match self.future {
+            Some(ref mut future) => {
+                match future.poll() {
+                    Ok(Async::NotReady) => false,
+                    Ok(Async::Ready(_)) | Err(_) => true,
+                }
+            }
+            None => false,
+        };
+        if resolved {
+            self.future = None;
+            self.stopped = true;
+            return Ok(Async::Ready(None))
+        }
+
+        let item = try_ready!(self.stream.poll());
+        if item.is_none() {
+            self.future = None;
+            self.stopped = true;
+        }
+        Ok(Async::Ready(item))
+    }
+}
@@ -0,0 +1,117 @@
+use std::time::Duration;
+
+use {Async, Future, Poll};
+use stream::Stream;
+use timer::{Timer, Elapsed};
+
+/// A stream combinator which yields an error whenever the underlying stream
+/// takes too long to produce its next item.
+///
+/// This structure is produced by the `Stream::timeout` method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Timeout<S, T>
+    where T: Timer,
+{
+    stream: S,
+    timer: T,
+    duration: Duration,
+    sleep: Option<T::Sleep>,
+}
+
+pub fn new<S, T>(s: S, duration: Duration, timer: T) -> Timeout<S, T>
+    where S: Stream,
+          T: Timer,
+          S::Error: From<Elapsed> + From<<T::Sleep as Future>::Error>,
+{
+    Timeout {
+        stream: s,
+        timer: timer,
+        duration: duration,
+        sleep: None,
+    }
+}
+
+impl<S, T> Timeout<S, T>
+    where T: Timer,
+{
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+impl<S, T> ::sink::Sink for Timeout<S, T>
+    where S: ::sink::Sink,
+          T: Timer,
+{
+    type SinkItem = S::SinkItem;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: S::SinkItem) -> ::StartSend<S::SinkItem, S::SinkError> {
+        self.stream.start_send(item)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.poll_complete()
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.close()
+    }
+}
+
+impl<S, T> Stream for Timeout<S, T>
+    where S: Stream,
+          T: Timer,
+          S::Error: From<Elapsed> + From<<T::Sleep as Future>::Error>,
+{
+    type Item = S::Item;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<S::Item>, S::Error> {
+        match self.stream.poll() {
+            Ok(Async::NotReady) => {}
+            other => {
+                // Anything from the stream, including errors, restarts the
+                // clock for the next item.
+                self.sleep = None;
+                return other
+            }
+        }
+
+        if self.sleep.is_none() {
+            self.sleep = Some(self.timer.sleep(self.duration));
+        }
+        match self.sleep.as_mut().unwrap().poll() {
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            Ok(Async::Ready(())) => {
+                self.sleep = None;
+                Err(From::from(Elapsed))
+            }
+            Err(e) => {
+                self.sleep = None;
+                Err(From::from(e))
+            }
+        }
+    }
+}
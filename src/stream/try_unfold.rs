@@ -0,0 +1,107 @@
+use core::mem;
+
+use {Future, IntoFuture, Async, Poll};
+use stream::Stream;
+
+/// Creates a `Stream` from a seed and a closure returning a `Future` which
+/// decides whether the stream continues.
+///
+/// This function is similar to `unfold()`, except that the decision of
+/// whether to produce another item is made asynchronously. `try_unfold()`
+/// will call the provided closure with the provided seed and wait for the
+/// returned `Future` to complete. If it completes with `Some((a, b))` then the
+/// value `a` is yielded and `b` is used as the next internal state. If it
+/// completes with `None` then the stream is finished.
+///
+/// In case of error generated by the returned `Future`, the error will be
+/// returned by the `Stream`. The `Stream` will then yield
+/// `Ok(Async::Ready(None))` in future calls to `poll()`.
+///
+/// This is typically useful for producers such as paginated requests, where
+/// it's only known whether there is more data once a request has completed.
+///
+/// # Example
+///
+/// ```rust
+/// use futures::stream::{self, Stream};
+/// use futures::future::{self, Future};
+///
+/// let mut stream = stream::try_unfold(0, |state| {
+///     if state <= 2 {
+///         future::ok::<_, u32>(Some((state * 2, state + 1)))
+///     } else {
+///         future::ok(None)
+///     }
+/// });
+///
+/// let result = stream.collect().wait();
+/// assert_eq!(result, Ok(vec![0, 2, 4]));
+/// ```
+pub fn try_unfold<T, F, Fut, It>(init: T, f: F) -> TryUnfold<T, F, Fut>
+    where F: FnMut(T) -> Fut,
+          Fut: IntoFuture<Item = Option<(It, T)>>,
+{
+    TryUnfold {
+        f: f,
+        state: State::Ready(init),
+    }
+}
+
+/// A stream which creates futures, polls them and return their result, until
+/// one of them resolves to `None`.
+///
+/// This stream is returned by the `futures::stream::try_unfold` method
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct TryUnfold<T, F, Fut> where Fut: IntoFuture {
+    f: F,
+    state: State<T, Fut::Future>,
+}
+
+impl <T, F, Fut, It> Stream for TryUnfold<T, F, Fut>
+    where F: FnMut(T) -> Fut,
+          Fut: IntoFuture<Item = Option<(It, T)>>,
+{
+    type Item = It;
+    type Error = Fut::Error;
+
+    fn poll(&mut self) -> Poll<Option<It>, Fut::Error> {
+        loop {
+            match mem::replace(&mut self.state, State::Empty) {
+                // State::Empty may happen if the future returned an error or
+                // `None`
+                State::Empty => { return Ok(Async::Ready(None)); }
+                State::Ready(state) => {
+                    self.state = State::Processing((self.f)(state).into_future());
+                }
+                State::Processing(mut fut) => {
+                    match try!(fut.poll()) {
+                        Async::Ready(Some((item, next_state))) => {
+                            self.state = State::Ready(next_state);
+                            return Ok(Async::Ready(Some(item)));
+                        }
+                        Async::Ready(None) => {
+                            return Ok(Async::Ready(None));
+                        }
+                        Async::NotReady => {
+                            self.state = State::Processing(fut);
+                            return Ok(Async::NotReady);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+enum State<T, F> where F: Future {
+    /// Placeholder state when doing work, or when the stream has finished
+    Empty,
+
+    /// Ready to generate new future; current internal state is the `T`
+    Ready(T),
+
+    /// Working on a future generated previously
+    Processing(F),
+}
@@ -0,0 +1,163 @@
+use std::sync::{Arc, Mutex};
+
+use {Async, Poll};
+use stream::Stream;
+use task::{self, Task};
+
+/// A stream which can be paused and resumed with a `ValveHandle`.
+///
+/// This structure is produced by the `stream::valve` function.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Valve<S> {
+    stream: S,
+    inner: Arc<Mutex<Inner>>,
+}
+
+/// A handle used to pause and resume a `Valve` stream.
+///
+/// This structure is produced by the `stream::valve` function, and can be
+/// cloned to control the same valve from several places.
+#[derive(Clone, Debug)]
+pub struct ValveHandle {
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    open: bool,
+    task: Option<Task>,
+}
+
+/// Wraps a stream so that it can be paused and resumed.
+///
+/// This function returns the wrapped stream along with a handle. The valve
+/// starts out open, in which case the returned stream behaves just like
+/// `stream`. Once it's closed with `ValveHandle::close` the returned stream
+/// reports that it's not ready, without polling `stream` at all, until the
+/// valve is opened again with `ValveHandle::open`. This gives consumers a
+/// simple flow control switch.
+///
+/// # Examples
+///
+/// ```
+/// use futures::{Future, Stream};
+/// use futures::stream;
+///
+/// let (s, handle) = stream::valve(stream::iter_ok::<_, ()>(vec![1, 2, 3]));
+/// assert!(handle.is_open());
+///
+/// // Closing the valve pauses the stream until it's opened again.
+/// handle.close();
+/// assert!(!handle.is_open());
+/// handle.open();
+///
+/// assert_eq!(s.collect().wait(), Ok(vec![1, 2, 3]));
+/// ```
+pub fn valve<S>(stream: S) -> (Valve<S>, ValveHandle)
+    where S: Stream,
+{
+    let inner = Arc::new(Mutex::new(Inner {
+        open: true,
+        task: None,
+    }));
+    let valve = Valve {
+        stream: stream,
+        inner: inner.clone(),
+    };
+    (valve, ValveHandle { inner: inner })
+}
+
+impl<S> Valve<S> {
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+impl<S> ::sink::Sink for Valve<S>
+    where S: ::sink::Sink
+{
+    type SinkItem = S::SinkItem;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: S::SinkItem) -> ::StartSend<S::SinkItem, S::SinkError> {
+        self.stream.start_send(item)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.poll_complete()
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.close()
+    }
+}
+
+impl<S> Stream for Valve<S>
+    where S: Stream,
+{
+    type Item = S::Item;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<S::Item>, S::Error> {
+        {
+            let mut inner = self.inner.lock().unwrap();
+            if !inner.open {
+                inner.task = Some(task::park());
+                return Ok(Async::NotReady)
+            }
+        }
+        self.stream.poll()
+    }
+}
+
+impl ValveHandle {
+    /// Opens the valve, allowing items to flow through the stream again.
+    ///
+    /// If the stream's task was blocked on the valve being closed, it is
+    /// notified.
+    pub fn open(&self) {
+        let task = {
+            let mut inner = self.inner.lock().unwrap();
+            inner.open = true;
+            inner.task.take()
+        };
+        if let Some(task) = task {
+            task.unpark();
+        }
+    }
+
+    /// Closes the valve, pausing the stream.
+    ///
+    /// Items which are already being processed by the consumer are not
+    /// affected, but the underlying stream won't be polled again until the
+    /// valve is reopened.
+    pub fn close(&self) {
+        self.inner.lock().unwrap().open = false;
+    }
+
+    /// Returns `true` if the valve is currently open.
+    pub fn is_open(&self) -> bool {
+        self.inner.lock().unwrap().open
+    }
+}
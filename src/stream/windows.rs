@@ -0,0 +1,101 @@
+use std::collections::VecDeque;
+use std::prelude::v1::*;
+
+use {Async, Poll};
+use stream::Stream;
+
+/// A stream combinator which yields overlapping windows of the most recent
+/// items of the underlying stream.
+///
+/// This structure is produced by the `Stream::windows` method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Windows<S>
+    where S: Stream
+{
+    stream: S,
+    size: usize,
+    window: VecDeque<S::Item>,
+}
+
+pub fn new<S>(s: S, size: usize) -> Windows<S>
+    where S: Stream,
+          S::Item: Clone,
+{
+    assert!(size > 0);
+
+    Windows {
+        stream: s,
+        size: size,
+        window: VecDeque::with_capacity(size),
+    }
+}
+
+impl<S> Windows<S> where S: Stream {
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+impl<S> ::sink::Sink for Windows<S>
+    where S: ::sink::Sink + Stream
+{
+    type SinkItem = S::SinkItem;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: S::SinkItem) -> ::StartSend<S::SinkItem, S::SinkError> {
+        self.stream.start_send(item)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.poll_complete()
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.close()
+    }
+}
+
+impl<S> Stream for Windows<S>
+    where S: Stream,
+          S::Item: Clone,
+{
+    type Item = Vec<S::Item>;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        loop {
+            let item = match try_ready!(self.stream.poll()) {
+                Some(item) => item,
+                None => return Ok(Async::Ready(None)),
+            };
+            if self.window.len() == self.size {
+                self.window.pop_front();
+            }
+            self.window.push_back(item);
+            if self.window.len() == self.size {
+                return Ok(Async::Ready(Some(self.window.iter().cloned().collect())))
+            }
+        }
+    }
+}
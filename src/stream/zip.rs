@@ -58,4 +58,23 @@ impl<S1, S2> Stream for Zip<S1, S2>
             Ok(Async::NotReady)
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let queued1 = if self.queued1.is_some() { 1 } else { 0 };
+        let queued2 = if self.queued2.is_some() { 1 } else { 0 };
+        let (lower1, upper1) = self.stream1.size_hint();
+        let (lower2, upper2) = self.stream2.size_hint();
+        let lower1 = lower1.saturating_add(queued1);
+        let lower2 = lower2.saturating_add(queued2);
+        let upper1 = upper1.and_then(|n| n.checked_add(queued1));
+        let upper2 = upper2.and_then(|n| n.checked_add(queued2));
+
+        // Errors from either stream are passed through without being paired
+        // up, so the upper bound is only known if both are.
+        let upper = match (upper1, upper2) {
+            (Some(a), Some(b)) => a.checked_add(b),
+            _ => None,
+        };
+        (::core::cmp::min(lower1, lower2), upper)
+    }
 }
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+use std::mem;
+use std::prelude::v1::*;
+use std::sync::{Arc, Mutex as StdMutex};
+
+use {Async, Future, Poll};
+use task::{self, Task};
+
+/// A futures-aware barrier, which lets a number of tasks wait until all of
+/// them have reached a certain point.
+///
+/// Each task calls `wait`, and the returned futures all resolve once `n`
+/// tasks have done so. One of them, the last to arrive, is chosen as the
+/// "leader", which is useful when exactly one task needs to do some work
+/// once everyone is ready. Waiting never blocks the current thread, and the
+/// barrier can be reused once all tasks have been released.
+///
+/// A `Barrier` is a handle, and cloning it creates a new handle to the same
+/// barrier.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+/// use futures::Future;
+/// use futures::sync::Barrier;
+///
+/// let barrier = Barrier::new(3);
+/// let threads = (0..3).map(|_| {
+///     let barrier = barrier.clone();
+///     thread::spawn(move || barrier.wait().wait().unwrap().is_leader())
+/// }).collect::<Vec<_>>();
+///
+/// let leaders = threads.into_iter()
+///                      .map(|t| t.join().unwrap())
+///                      .filter(|&leader| leader)
+///                      .count();
+/// assert_eq!(leaders, 1);
+/// ```
+#[derive(Debug)]
+pub struct Barrier {
+    inner: Arc<StdMutex<State>>,
+}
+
+#[derive(Debug)]
+struct State {
+    n: usize,
+    // Number of tasks which have arrived in the current generation.
+    count: usize,
+    // Incremented every time the barrier releases its tasks.
+    generation: u64,
+    // Tasks which have arrived in the current generation, keyed by the id
+    // they were given when they did.
+    blocked: HashMap<usize, Task>,
+    next_id: usize,
+}
+
+impl Barrier {
+    /// Creates a new barrier which releases tasks once `n` of them are
+    /// waiting.
+    ///
+    /// A barrier created with an `n` of zero behaves like one created with
+    /// an `n` of one, so every call to `wait` completes right away.
+    pub fn new(n: usize) -> Barrier {
+        Barrier {
+            inner: Arc::new(StdMutex::new(State {
+                n: n,
+                count: 0,
+                generation: 0,
+                blocked: HashMap::new(),
+                next_id: 0,
+            })),
+        }
+    }
+
+    /// Returns a future which resolves once `n` tasks are waiting on this
+    /// barrier.
+    ///
+    /// The task counts as having arrived at the barrier once the returned
+    /// future is first polled. If the future is dropped before the barrier
+    /// releases it, the task no longer counts as waiting. Note that the
+    /// returned future will never resolve to an error.
+    pub fn wait(&self) -> BarrierWait {
+        BarrierWait {
+            barrier: self.clone(),
+            arrived: None,
+        }
+    }
+}
+
+impl Clone for Barrier {
+    fn clone(&self) -> Barrier {
+        Barrier { inner: self.inner.clone() }
+    }
+}
+
+/// Future returned by `Barrier::wait` which will resolve once all tasks have
+/// arrived at the barrier.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct BarrierWait {
+    barrier: Barrier,
+    // The generation we arrived in and our id, once we have.
+    arrived: Option<(u64, usize)>,
+}
+
+impl Future for BarrierWait {
+    type Item = BarrierWaitResult;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<BarrierWaitResult, ()> {
+        let mut state = self.barrier.inner.lock().unwrap();
+        let id = match self.arrived {
+            None => {
+                state.count += 1;
+                if state.count >= state.n {
+                    state.count = 0;
+                    state.generation += 1;
+                    for (_, task) in mem::replace(&mut state.blocked, HashMap::new()) {
+                        task.unpark();
+                    }
+                    return Ok(Async::Ready(BarrierWaitResult { is_leader: true }))
+                }
+                let id = state.next_id;
+                state.next_id = state.next_id.wrapping_add(1);
+                self.arrived = Some((state.generation, id));
+                id
+            }
+            Some((generation, id)) => {
+                if state.generation != generation {
+                    self.arrived = None;
+                    return Ok(Async::Ready(BarrierWaitResult { is_leader: false }))
+                }
+                id
+            }
+        };
+        // Update the task in case we've been moved to another task since we
+        // arrived.
+        state.blocked.insert(id, task::park());
+        Ok(Async::NotReady)
+    }
+}
+
+impl Drop for BarrierWait {
+    fn drop(&mut self) {
+        if let Some((generation, id)) = self.arrived {
+            let mut state = self.barrier.inner.lock().unwrap();
+            if state.generation == generation {
+                state.count -= 1;
+                state.blocked.remove(&id);
+            }
+        }
+    }
+}
+
+/// Resolved value of the `BarrierWait` future.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BarrierWaitResult {
+    is_leader: bool,
+}
+
+impl BarrierWaitResult {
+    /// Returns whether this task was chosen as the leader.
+    ///
+    /// Exactly one of the tasks released by a barrier is the leader.
+    pub fn is_leader(&self) -> bool {
+        self.is_leader
+    }
+}
@@ -1,6 +1,10 @@
+use std::any::Any;
 use std::boxed::Box;
 use std::cell::UnsafeCell;
+use std::error::Error;
+use std::fmt;
 use std::mem;
+use std::ptr;
 use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
 use std::sync::atomic::AtomicUsize;
@@ -131,6 +135,33 @@ impl<T> BiLock<T> {
         }
     }
 
+    /// Attempts to put the two "halves" of a `BiLock<T>` back together and
+    /// recover the original value.
+    ///
+    /// Succeeds only if the two `BiLock<T>`s were created by the same call to
+    /// `BiLock::new`, otherwise both are handed back in the error.
+    pub fn reunite(self, other: Self) -> Result<T, ReuniteError<T>> {
+        if &*self.inner as *const _ != &*other.inner as *const _ {
+            return Err(ReuniteError(self, other))
+        }
+        drop(other);
+
+        let inner = match Arc::try_unwrap(self.inner) {
+            Ok(inner) => inner,
+            Err(_) => panic!("both halves of the lock exist elsewhere"),
+        };
+        // Neither half can be holding the lock as we own both of them.
+        assert_eq!(inner.state.load(SeqCst), 0);
+
+        // `Inner` has a destructor, so the value is moved out by hand, and
+        // the rest of `Inner` is forgotten as it doesn't own anything else.
+        unsafe {
+            let value = ptr::read(inner.inner.get());
+            mem::forget(inner);
+            Ok(value)
+        }
+    }
+
     fn unlock(&self) {
         match self.inner.state.swap(0, SeqCst) {
             // we've locked the lock, shouldn't be possible for us to see an
@@ -155,6 +186,30 @@ impl<T> Drop for Inner<T> {
     }
 }
 
+/// Error indicating two `BiLock<T>`s were not two halves of a whole, and
+/// thus could not be `reunite`d.
+pub struct ReuniteError<T>(pub BiLock<T>, pub BiLock<T>);
+
+impl<T> fmt::Debug for ReuniteError<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_tuple("ReuniteError")
+            .field(&"...")
+            .finish()
+    }
+}
+
+impl<T> fmt::Display for ReuniteError<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "tried to reunite two BiLocks that don't form a pair")
+    }
+}
+
+impl<T: Any> Error for ReuniteError<T> {
+    fn description(&self) -> &str {
+        "tried to reunite two BiLocks that don't form a pair"
+    }
+}
+
 /// Returned RAII guard from the `poll_lock` method.
 ///
 /// This structure acts as a sentinel to the data in the `BiLock<T>` itself,
@@ -12,6 +12,22 @@
 
 pub mod oneshot;
 pub mod mpsc;
+pub mod mpmc;
+pub mod broadcast;
+pub mod watch;
+pub mod mux;
+mod barrier;
 mod bilock;
+mod mutex;
+mod notify;
+mod once_cell;
+mod rwlock;
+mod semaphore;
 
-pub use self::bilock::{BiLock, BiLockGuard, BiLockAcquire, BiLockAcquired};
+pub use self::barrier::{Barrier, BarrierWait, BarrierWaitResult};
+pub use self::bilock::{BiLock, BiLockGuard, BiLockAcquire, BiLockAcquired, ReuniteError};
+pub use self::mutex::{Mutex, MutexAcquire, MutexGuard};
+pub use self::notify::{Notify, Notified};
+pub use self::once_cell::{OnceCell, GetOrInit};
+pub use self::rwlock::{RwLock, RwLockRead, RwLockWrite, RwLockReadGuard, RwLockWriteGuard};
+pub use self::semaphore::{Semaphore, SemaphoreAcquire, Permit};
@@ -0,0 +1,229 @@
+//! A multi-producer, multi-consumer, futures-aware, FIFO queue with back
+//! pressure.
+//!
+//! Unlike `broadcast`, every message sent on an mpmc channel is delivered to
+//! exactly one `Receiver`: whichever one polls for it first. Receivers can be
+//! cloned freely, which makes this channel a good fit for distributing work
+//! among a pool of consumers, such as the workers of a job queue. Messages
+//! are delivered in the order in which they were sent.
+//!
+//! As with `mpsc`, the channel capacity is equal to `buffer + num-senders`,
+//! and a sender is notified when additional capacity is available.
+//!
+//! # Disconnection
+//!
+//! When all `Sender` handles have been dropped, the streams of every
+//! `Receiver` end once the messages still buffered have been received. When
+//! all `Receiver` handles have been dropped, sending results in an error.
+
+use std::collections::{HashMap, VecDeque};
+use std::mem;
+use std::prelude::v1::*;
+use std::sync::{Arc, Mutex};
+
+use {Async, AsyncSink, Poll, StartSend, Sink, Stream};
+use sync_impl::send_error::send_error;
+use task::{self, Task};
+
+pub use sync::mpsc::SendError;
+
+/// The transmission end of an mpmc channel.
+///
+/// This is created by the `channel` function, and can be cloned to send
+/// from several places.
+#[derive(Debug)]
+pub struct Sender<T> {
+    id: usize,
+    inner: Arc<Mutex<Inner<T>>>,
+}
+
+/// The receiving end of an mpmc channel, which implements `Stream`.
+///
+/// This is created by the `channel` function. Cloning a receiver creates a
+/// new one which competes with the others for messages.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Receiver<T> {
+    id: usize,
+    inner: Arc<Mutex<Inner<T>>>,
+}
+
+#[derive(Debug)]
+struct Inner<T> {
+    queue: VecDeque<T>,
+    buffer: usize,
+    num_senders: usize,
+    num_receivers: usize,
+    next_id: usize,
+    // Receivers waiting for a message, in the order they started waiting.
+    // Only one of them is woken for every message sent.
+    blocked_receivers: VecDeque<(usize, Task)>,
+    // Senders waiting for capacity, keyed by their id. All of these are
+    // woken whenever a message is received.
+    blocked_senders: HashMap<usize, Task>,
+}
+
+/// Creates a new mpmc channel with bounded capacity.
+///
+/// # Examples
+///
+/// ```
+/// use futures::{Future, Stream, Sink};
+/// use futures::stream;
+/// use futures::sync::mpmc;
+///
+/// let (tx, rx) = mpmc::channel(8);
+/// let rx2 = rx.clone();
+/// tx.send_all(stream::iter_ok(0..4)).wait().unwrap();
+///
+/// // Each message is only received once
+/// let mut rx = rx.wait();
+/// let mut rx2 = rx2.wait();
+/// assert_eq!(rx.next(), Some(Ok(0)));
+/// assert_eq!(rx2.next(), Some(Ok(1)));
+/// assert_eq!(rx.next(), Some(Ok(2)));
+/// assert_eq!(rx2.next(), Some(Ok(3)));
+/// ```
+pub fn channel<T>(buffer: usize) -> (Sender<T>, Receiver<T>) {
+    let inner = Arc::new(Mutex::new(Inner {
+        queue: VecDeque::new(),
+        buffer: buffer,
+        num_senders: 1,
+        num_receivers: 1,
+        next_id: 1,
+        blocked_receivers: VecDeque::new(),
+        blocked_senders: HashMap::new(),
+    }));
+    let tx = Sender { id: 0, inner: inner.clone() };
+    let rx = Receiver { id: 0, inner: inner };
+    (tx, rx)
+}
+
+impl<T> Inner<T> {
+    // Hands out ids to senders and receivers alike.
+    fn next_id(&mut self) -> usize {
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        id
+    }
+
+    fn unpark_receiver(&mut self) {
+        if let Some((_, task)) = self.blocked_receivers.pop_front() {
+            task.unpark();
+        }
+    }
+
+    fn unpark_senders(&mut self) {
+        for (_, task) in mem::replace(&mut self.blocked_senders, HashMap::new()) {
+            task.unpark();
+        }
+    }
+}
+
+impl<T> Sink for Sender<T> {
+    type SinkItem = T;
+    type SinkError = SendError<T>;
+
+    fn start_send(&mut self, msg: T) -> StartSend<T, SendError<T>> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.num_receivers == 0 {
+            return Err(send_error(msg))
+        }
+        if inner.queue.len() >= inner.buffer + inner.num_senders {
+            // Replacing this sender's previous task, if any, keeps retries
+            // from piling up.
+            inner.blocked_senders.insert(self.id, task::park());
+            return Ok(AsyncSink::NotReady(msg))
+        }
+        inner.queue.push_back(msg);
+        inner.unpark_receiver();
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), SendError<T>> {
+        Ok(Async::Ready(()))
+    }
+
+    fn close(&mut self) -> Poll<(), SendError<T>> {
+        Ok(Async::Ready(()))
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Sender<T> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.num_senders += 1;
+        Sender {
+            id: inner.next_id(),
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.num_senders -= 1;
+        inner.blocked_senders.remove(&self.id);
+        if inner.num_senders == 0 {
+            for (_, task) in mem::replace(&mut inner.blocked_receivers, VecDeque::new()) {
+                task.unpark();
+            }
+        }
+    }
+}
+
+impl<T> Stream for Receiver<T> {
+    type Item = T;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<Option<T>, ()> {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(msg) = inner.queue.pop_front() {
+            inner.blocked_receivers.retain(|r| r.0 != self.id);
+            inner.unpark_senders();
+            // Make sure a message which is still buffered isn't left behind
+            // while other receivers are waiting.
+            if !inner.queue.is_empty() {
+                inner.unpark_receiver();
+            }
+            return Ok(Async::Ready(Some(msg)))
+        }
+        if inner.num_senders == 0 {
+            return Ok(Async::Ready(None))
+        }
+        let task = task::park();
+        match inner.blocked_receivers.iter().position(|r| r.0 == self.id) {
+            Some(i) => inner.blocked_receivers[i].1 = task,
+            None => inner.blocked_receivers.push_back((self.id, task)),
+        }
+        Ok(Async::NotReady)
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Receiver<T> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.num_receivers += 1;
+        Receiver {
+            id: inner.next_id(),
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.num_receivers -= 1;
+        inner.blocked_receivers.retain(|r| r.0 != self.id);
+        // We may have been woken up for a message we'll now never receive,
+        // so pass it on to somebody else.
+        if !inner.queue.is_empty() {
+            inner.unpark_receiver();
+        }
+        if inner.num_receivers == 0 {
+            inner.unpark_senders();
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::prelude::v1::*;
+use std::sync::{Arc, Mutex};
+
+use sync::mpsc::SendError;
+use sync_impl::send_error::send_error;
+use task::{self, Task};
+use {Async, AsyncSink, Poll, StartSend, Sink, Stream};
+
+/// The transmission end of a lossy channel, which implements `Sink`.
+///
+/// This is created by the `lossy_channel` function.
+#[derive(Debug)]
+pub struct LossySender<T> {
+    inner: Arc<Mutex<Inner<T>>>,
+}
+
+/// The receiving end of a lossy channel, which implements `Stream`.
+///
+/// This is created by the `lossy_channel` function.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct LossyReceiver<T> {
+    inner: Arc<Mutex<Inner<T>>>,
+}
+
+#[derive(Debug)]
+struct Inner<T> {
+    buffer: VecDeque<T>,
+    capacity: usize,
+    // Number of messages overwritten since the receiver was last told.
+    dropped: u64,
+    num_senders: usize,
+    closed: bool,
+    recv_task: Option<Task>,
+}
+
+/// Error yielded by a `LossyReceiver` when messages were overwritten before
+/// it could receive them.
+///
+/// The receiver carries on with the oldest message still buffered after
+/// yielding this error.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Lagged(u64);
+
+impl Lagged {
+    /// Returns the number of messages which were dropped.
+    pub fn dropped(&self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Display for Lagged {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "receiver lagged behind by {} messages", self.0)
+    }
+}
+
+impl Error for Lagged {
+    fn description(&self) -> &str {
+        "receiver lagged behind"
+    }
+}
+
+/// Creates a bounded channel which overwrites the oldest message when full,
+/// instead of exerting back pressure.
+///
+/// Sending never waits: if `capacity` messages are already buffered, the
+/// oldest of them is dropped to make room. The next time the receiver is
+/// polled it yields a `Lagged` error with the number of messages dropped,
+/// and then carries on with the messages still buffered. This is the right
+/// behavior for data where only recent messages matter, such as telemetry
+/// or feeds of the latest state, and where a slow receiver mustn't hold up
+/// the senders.
+///
+/// # Panics
+///
+/// This function will panic if `capacity` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use futures::Stream;
+/// use futures::sync::mpsc;
+///
+/// let (tx, rx) = mpsc::lossy_channel(2);
+/// for i in 0..5 {
+///     tx.lossy_send(i).unwrap();
+/// }
+/// drop(tx);
+///
+/// let mut rx = rx.wait();
+/// assert_eq!(rx.next().unwrap().unwrap_err().dropped(), 3);
+/// assert_eq!(rx.next(), Some(Ok(3)));
+/// assert_eq!(rx.next(), Some(Ok(4)));
+/// assert_eq!(rx.next(), None);
+/// ```
+pub fn lossy_channel<T>(capacity: usize) -> (LossySender<T>, LossyReceiver<T>) {
+    assert!(capacity > 0);
+
+    let inner = Arc::new(Mutex::new(Inner {
+        buffer: VecDeque::with_capacity(capacity),
+        capacity: capacity,
+        dropped: 0,
+        num_senders: 1,
+        closed: false,
+        recv_task: None,
+    }));
+    let tx = LossySender { inner: inner.clone() };
+    let rx = LossyReceiver { inner: inner };
+    (tx, rx)
+}
+
+impl<T> LossySender<T> {
+    /// Sends a message, overwriting the oldest one buffered if the channel
+    /// is full.
+    ///
+    /// Unlike `Sink::send`, this sends the message right away rather than
+    /// through a future, as sending never needs to wait. An error is
+    /// returned with the message if the receiver has been closed or dropped.
+    pub fn lossy_send(&self, msg: T) -> Result<(), SendError<T>> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.closed {
+            return Err(send_error(msg))
+        }
+        if inner.buffer.len() == inner.capacity {
+            inner.buffer.pop_front();
+            inner.dropped += 1;
+        }
+        inner.buffer.push_back(msg);
+        if let Some(task) = inner.recv_task.take() {
+            task.unpark();
+        }
+        Ok(())
+    }
+}
+
+impl<T> Sink for LossySender<T> {
+    type SinkItem = T;
+    type SinkError = SendError<T>;
+
+    fn start_send(&mut self, msg: T) -> StartSend<T, SendError<T>> {
+        try!(self.lossy_send(msg));
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), SendError<T>> {
+        Ok(Async::Ready(()))
+    }
+
+    fn close(&mut self) -> Poll<(), SendError<T>> {
+        Ok(Async::Ready(()))
+    }
+}
+
+impl<T> Clone for LossySender<T> {
+    fn clone(&self) -> LossySender<T> {
+        self.inner.lock().unwrap().num_senders += 1;
+        LossySender { inner: self.inner.clone() }
+    }
+}
+
+impl<T> Drop for LossySender<T> {
+    fn drop(&mut self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.num_senders -= 1;
+        if inner.num_senders == 0 {
+            if let Some(task) = inner.recv_task.take() {
+                task.unpark();
+            }
+        }
+    }
+}
+
+impl<T> LossyReceiver<T> {
+    /// Closes the receiving half
+    ///
+    /// This prevents any further messages from being sent on the channel
+    /// while still enabling the receiver to drain messages that are buffered.
+    pub fn close(&mut self) {
+        self.inner.lock().unwrap().closed = true;
+    }
+}
+
+impl<T> Stream for LossyReceiver<T> {
+    type Item = T;
+    type Error = Lagged;
+
+    fn poll(&mut self) -> Poll<Option<T>, Lagged> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.dropped > 0 {
+            let dropped = inner.dropped;
+            inner.dropped = 0;
+            return Err(Lagged(dropped))
+        }
+        if let Some(msg) = inner.buffer.pop_front() {
+            return Ok(Async::Ready(Some(msg)))
+        }
+        if inner.closed || inner.num_senders == 0 {
+            return Ok(Async::Ready(None))
+        }
+        inner.recv_task = Some(task::park());
+        Ok(Async::NotReady)
+    }
+}
+
+impl<T> Drop for LossyReceiver<T> {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
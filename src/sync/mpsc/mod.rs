@@ -68,20 +68,28 @@
 // by the queue structure.
 
 use std::fmt;
+use std::prelude::v1::*;
 use std::error::Error;
 use std::any::Any;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering::SeqCst;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, Weak};
 use std::thread;
 use std::usize;
 
 use sync::mpsc::queue::{Queue, PopResult};
+use sync_impl::send_error::send_error;
 use task::{self, Task};
-use {Async, AsyncSink, Poll, StartSend, Sink, Stream};
+use {Async, AsyncSink, Future, Poll, StartSend, Sink, Stream};
 
+mod lossy;
+mod priority;
 mod queue;
 
+pub use self::lossy::{lossy_channel, LossySender, LossyReceiver, Lagged};
+pub use self::priority::{priority_channel, PrioritySender, PriorityReceiver};
+pub use sync_impl::send_error::SendError;
+
 /// The transmission end of a channel which is used to send values.
 ///
 /// This is created by the `channel` method.
@@ -93,7 +101,7 @@ pub struct Sender<T> {
     // Handle to the task that is blocked on this sender. This handle is sent
     // to the receiver half in order to be notified when the sender becomes
     // unblocked.
-    sender_task: SenderTask,
+    sender_task: Arc<Mutex<SenderTask>>,
 
     // True if the sender might be blocked. This is an optimization to avoid
     // having to lock the mutex most of the time.
@@ -134,36 +142,93 @@ pub struct Receiver<T> {
 #[derive(Debug)]
 pub struct UnboundedReceiver<T>(Receiver<T>);
 
-/// Error type for sending, used when the receiving end of a channel is
-/// dropped
+/// A sending handle to a channel which doesn't keep the channel open.
+///
+/// Once every `Sender` has been dropped the receiver sees the channel as
+/// closed, even if weak handles are still around. A `WeakSender` can be
+/// upgraded back into a `Sender` for as long as that hasn't happened.
+///
+/// This is created by the `Sender::downgrade` method.
+#[derive(Debug)]
+pub struct WeakSender<T> {
+    inner: Weak<Inner<T>>,
+}
+
+/// A sending handle to an unbounded channel which doesn't keep the channel
+/// open.
+///
+/// This is created by the `UnboundedSender::downgrade` method, and is
+/// otherwise the same as `WeakSender`.
+#[derive(Debug)]
+pub struct WeakUnboundedSender<T>(WeakSender<T>);
+
+/// Error type returned from `try_send`
+#[derive(Clone, PartialEq, Eq)]
+pub struct TrySendError<T> {
+    kind: TrySendErrorKind<T>,
+}
+
 #[derive(Clone, PartialEq, Eq)]
-pub struct SendError<T>(T);
+enum TrySendErrorKind<T> {
+    Full(T),
+    Disconnected(T),
+}
 
-impl<T> fmt::Debug for SendError<T> {
+impl<T> fmt::Debug for TrySendError<T> {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        fmt.debug_tuple("SendError")
-            .field(&"...")
+        let kind = match self.kind {
+            TrySendErrorKind::Full(_) => "Full",
+            TrySendErrorKind::Disconnected(_) => "Disconnected",
+        };
+        fmt.debug_struct("TrySendError")
+            .field("kind", &kind)
             .finish()
     }
 }
 
-impl<T> fmt::Display for SendError<T> {
+impl<T> fmt::Display for TrySendError<T> {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        write!(fmt, "send failed because receiver is gone")
+        write!(fmt, "{}", self.description_str())
     }
 }
 
-impl<T: Any> Error for SendError<T>
-{
+impl<T: Any> Error for TrySendError<T> {
     fn description(&self) -> &str {
-        "send failed because receiver is gone"
+        self.description_str()
     }
 }
 
-impl<T> SendError<T> {
+impl<T> TrySendError<T> {
+    /// Returns `true` if this error is a result of the channel being full.
+    pub fn is_full(&self) -> bool {
+        match self.kind {
+            TrySendErrorKind::Full(_) => true,
+            TrySendErrorKind::Disconnected(_) => false,
+        }
+    }
+
+    /// Returns `true` if this error is a result of the receiver being
+    /// dropped.
+    pub fn is_disconnected(&self) -> bool {
+        match self.kind {
+            TrySendErrorKind::Full(_) => false,
+            TrySendErrorKind::Disconnected(_) => true,
+        }
+    }
+
     /// Returns the message that was attempted to be sent but failed.
     pub fn into_inner(self) -> T {
-        self.0
+        match self.kind {
+            TrySendErrorKind::Full(msg) => msg,
+            TrySendErrorKind::Disconnected(msg) => msg,
+        }
+    }
+
+    fn description_str(&self) -> &'static str {
+        match self.kind {
+            TrySendErrorKind::Full(_) => "send failed because channel is full",
+            TrySendErrorKind::Disconnected(_) => "send failed because receiver is gone",
+        }
     }
 }
 
@@ -180,7 +245,7 @@ struct Inner<T> {
     message_queue: Queue<Option<T>>,
 
     // Atomic, FIFO queue used to send parked task handles to the receiver.
-    parked_queue: Queue<SenderTask>,
+    parked_queue: Queue<Arc<Mutex<SenderTask>>>,
 
     // Number of senders in existence
     num_senders: AtomicUsize,
@@ -227,7 +292,27 @@ const MAX_CAPACITY: usize = !(OPEN_MASK);
 const MAX_BUFFER: usize = MAX_CAPACITY >> 1;
 
 // Sent to the consumer to wake up blocked producers
-type SenderTask = Arc<Mutex<Option<Task>>>;
+#[derive(Debug)]
+struct SenderTask {
+    task: Option<Task>,
+    is_parked: bool,
+}
+
+impl SenderTask {
+    fn new() -> SenderTask {
+        SenderTask {
+            task: None,
+            is_parked: false,
+        }
+    }
+
+    // Marks the sender as no longer parked, returning the task to notify if
+    // there is one.
+    fn unpark(&mut self) -> Option<Task> {
+        self.is_parked = false;
+        self.task.take()
+    }
+}
 
 /// Creates an in-memory channel implementation of the `Stream` trait with
 /// bounded capacity.
@@ -281,7 +366,7 @@ fn channel2<T>(buffer: Option<usize>) -> (Sender<T>, Receiver<T>) {
 
     let tx = Sender {
         inner: inner.clone(),
-        sender_task: Arc::new(Mutex::new(None)),
+        sender_task: Arc::new(Mutex::new(SenderTask::new())),
         maybe_parked: false,
     };
 
@@ -299,6 +384,103 @@ fn channel2<T>(buffer: Option<usize>) -> (Sender<T>, Receiver<T>) {
  */
 
 impl<T> Sender<T> {
+    /// Polls the channel to determine if there is guaranteed to be capacity
+    /// to send at least one item without waiting.
+    ///
+    /// Returns `Ok(Async::Ready(()))` if there is sufficient capacity, or
+    /// `Ok(Async::NotReady)` if the channel is not guaranteed to have
+    /// capacity, in which case the current task is scheduled to be notified
+    /// once the receiver has drained enough messages. Returns
+    /// `Err(SendError(()))` if the receiver has gone away.
+    ///
+    /// This allows a producer to wait for capacity before doing the work of
+    /// producing a value, rather than having `start_send` hand the value back.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if called from outside the context of a task or
+    /// future.
+    pub fn poll_ready(&mut self) -> Poll<(), SendError<()>> {
+        let state = decode_state(self.inner.state.load(SeqCst));
+        if !state.is_open {
+            return Err(send_error(()));
+        }
+
+        Ok(self.poll_unparked(true))
+    }
+
+    /// Returns the number of messages currently buffered in the channel.
+    ///
+    /// Note that other handles to the channel may be sending or receiving
+    /// concurrently, so this is only a snapshot which may be out of date as
+    /// soon as it's returned.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if there are no messages buffered in the channel.
+    ///
+    /// Like `len`, this is only a snapshot of the channel's state.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of messages the channel can hold.
+    ///
+    /// This is the `buffer` the channel was created with, plus one
+    /// guaranteed slot for every `Sender` in existence, so it changes as
+    /// senders are cloned and dropped.
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    /// Returns a future which resolves to a `SendPermit` once there's
+    /// capacity in the channel for a message from this sender.
+    ///
+    /// Sending a message through the permit never waits, which allows a
+    /// producer to secure its slot in the channel before doing the work of
+    /// producing a potentially expensive value. The future resolves to an
+    /// error if the receiving half has gone away.
+    pub fn reserve(&mut self) -> Reserve<T> {
+        Reserve { sender: Some(self) }
+    }
+
+    /// Creates a `WeakSender` for this channel, which doesn't keep the
+    /// channel open.
+    pub fn downgrade(&self) -> WeakSender<T> {
+        WeakSender {
+            inner: Arc::downgrade(&self.inner),
+        }
+    }
+
+    /// Attempts to send a message on this `Sender` without blocking.
+    ///
+    /// Unlike `start_send`, this function doesn't need to be called from
+    /// within the context of a task, as it never schedules the current task
+    /// to be notified. This makes it suitable for producers running outside
+    /// of an executor.
+    ///
+    /// If the channel is at capacity, an error is returned for which
+    /// `is_full` returns `true`. If the receiving half has gone away, an
+    /// error is returned for which `is_disconnected` returns `true`. In both
+    /// cases the message can be recovered with `into_inner`.
+    pub fn try_send(&mut self, msg: T) -> Result<(), TrySendError<T>> {
+        // If the sender is currently blocked, reject the message before doing
+        // any work.
+        if !self.poll_unparked(false).is_ready() {
+            return Err(TrySendError {
+                kind: TrySendErrorKind::Full(msg),
+            });
+        }
+
+        // The channel has capacity to accept the message, so send it.
+        self.do_send(Some(msg), false).map_err(|e| {
+            TrySendError {
+                kind: TrySendErrorKind::Disconnected(e.into_inner()),
+            }
+        })
+    }
+
     // Do the send without failing
     fn do_send(&mut self, msg: Option<T>, can_park: bool) -> Result<(), SendError<T>> {
         // First, increment the number of messages contained by the channel.
@@ -320,7 +502,7 @@ impl<T> Sender<T> {
                 //     num-senders + buffer + 1
                 //
                 if let Some(msg) = msg {
-                    return Err(SendError(msg));
+                    return Err(send_error(msg));
                 } else {
                     return Ok(());
                 }
@@ -349,7 +531,7 @@ impl<T> Sender<T> {
     fn do_send_nb(&self, msg: T) -> Result<(), SendError<T>> {
         match self.inc_num_messages(false) {
             Some(park_self) => assert!(!park_self),
-            None => return Err(SendError(msg)),
+            None => return Err(send_error(msg)),
         };
 
         self.queue_push_and_signal(Some(msg));
@@ -386,11 +568,13 @@ impl<T> Sender<T> {
             assert!(state.num_messages < MAX_CAPACITY, "buffer space exhausted; \
                     sending this messages would overflow the state");
 
-            state.num_messages += 1;
-
-            // The channel is closed by all sender handles being dropped.
+            // The channel is closed by all sender handles being dropped. The
+            // termination message isn't counted as a message, so that the
+            // count only reflects the values buffered in the channel.
             if close {
                 state.is_open = false;
+            } else {
+                state.num_messages += 1;
             }
 
             let next = encode_state(&state);
@@ -399,7 +583,7 @@ impl<T> Sender<T> {
                     // Block if the current number of pending messages has exceeded
                     // the configured buffer size
                     let park_self = match self.inner.buffer {
-                        Some(buffer) => state.num_messages > buffer,
+                        Some(buffer) => !close && state.num_messages > buffer,
                         None => false,
                     };
 
@@ -448,7 +632,11 @@ impl<T> Sender<T> {
             None
         };
 
-        *self.sender_task.lock().unwrap() = task;
+        {
+            let mut sender_task = self.sender_task.lock().unwrap();
+            sender_task.task = task;
+            sender_task.is_parked = true;
+        }
 
         // Send handle over queue
         let t = self.sender_task.clone();
@@ -460,14 +648,14 @@ impl<T> Sender<T> {
         self.maybe_parked = state.is_open;
     }
 
-    fn poll_unparked(&mut self) -> Async<()> {
+    fn poll_unparked(&mut self, do_park: bool) -> Async<()> {
         // First check the `maybe_parked` variable. This avoids acquiring the
         // lock in most cases
         if self.maybe_parked {
             // Get a lock on the task handle
             let mut task = self.sender_task.lock().unwrap();
 
-            if task.is_none() {
+            if !task.is_parked {
                 self.maybe_parked = false;
                 return Async::Ready(())
             }
@@ -477,8 +665,10 @@ impl<T> Sender<T> {
             // the correct task will be notified.
             //
             // Update the task in case the `Sender` has been moved to another
-            // task
-            *task = Some(task::park());
+            // task, unless we're being called from outside of a task.
+            if do_park {
+                task.task = Some(task::park());
+            }
 
             Async::NotReady
         } else {
@@ -494,7 +684,7 @@ impl<T> Sink for Sender<T> {
     fn start_send(&mut self, msg: T) -> StartSend<T, SendError<T>> {
         // If the sender is currently blocked, reject the message before doing
         // any work.
-        if !self.poll_unparked().is_ready() {
+        if !self.poll_unparked(true).is_ready() {
             return Ok(AsyncSink::NotReady(msg));
         }
 
@@ -518,10 +708,41 @@ impl<T> UnboundedSender<T> {
     ///
     /// This is an unbounded sender, so this function differs from `Sink::send`
     /// by ensuring the return type reflects that the channel is always ready to
-    /// receive messages.
-    pub fn send(&self, msg: T) -> Result<(), SendError<T>> {
+    /// receive messages. The only way for this to fail is if the receiving
+    /// half has gone away, in which case the message is handed back in the
+    /// error.
+    pub fn unbounded_send(&self, msg: T) -> Result<(), SendError<T>> {
         self.0.do_send_nb(msg)
     }
+
+    /// Returns the number of messages currently buffered in the channel.
+    ///
+    /// Note that other handles to the channel may be sending or receiving
+    /// concurrently, so this is only a snapshot which may be out of date as
+    /// soon as it's returned.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no messages buffered in the channel.
+    ///
+    /// Like `len`, this is only a snapshot of the channel's state.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Creates a `WeakUnboundedSender` for this channel, which doesn't keep
+    /// the channel open.
+    pub fn downgrade(&self) -> WeakUnboundedSender<T> {
+        WeakUnboundedSender(self.0.downgrade())
+    }
+
+    #[deprecated(note = "renamed to `unbounded_send`", since = "0.1.14")]
+    #[doc(hidden)]
+    #[cfg(feature = "with-deprecated")]
+    pub fn send(&self, msg: T) -> Result<(), SendError<T>> {
+        self.unbounded_send(msg)
+    }
 }
 
 impl<T> Sink for UnboundedSender<T> {
@@ -589,7 +810,7 @@ impl<T> Clone for Sender<T> {
             if actual == curr {
                 return Sender {
                     inner: self.inner.clone(),
-                    sender_task: Arc::new(Mutex::new(None)),
+                    sender_task: Arc::new(Mutex::new(SenderTask::new())),
                     maybe_parked: false,
                 };
             }
@@ -599,6 +820,71 @@ impl<T> Clone for Sender<T> {
     }
 }
 
+impl<T> WeakSender<T> {
+    /// Attempts to turn this handle back into a `Sender`.
+    ///
+    /// Returns `None` if every `Sender` has already been dropped. A handle
+    /// can still be upgraded after the receiver has gone away, as long as some
+    /// `Sender` is alive.
+    pub fn upgrade(&self) -> Option<Sender<T>> {
+        let inner = match self.inner.upgrade() {
+            Some(inner) => inner,
+            None => return None,
+        };
+        let mut curr = inner.num_senders.load(SeqCst);
+
+        loop {
+            // The channel has been closed by the last `Sender` going away, so
+            // it can't be reopened.
+            if curr == 0 {
+                return None;
+            }
+
+            if curr == inner.max_senders() {
+                panic!("cannot upgrade `WeakSender` -- too many outstanding senders");
+            }
+
+            let next = curr + 1;
+            let actual = inner.num_senders.compare_and_swap(curr, next, SeqCst);
+
+            if actual == curr {
+                return Some(Sender {
+                    inner: inner,
+                    sender_task: Arc::new(Mutex::new(SenderTask::new())),
+                    maybe_parked: false,
+                });
+            }
+
+            curr = actual;
+        }
+    }
+}
+
+impl<T> Clone for WeakSender<T> {
+    fn clone(&self) -> WeakSender<T> {
+        WeakSender {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> WeakUnboundedSender<T> {
+    /// Attempts to turn this handle back into an `UnboundedSender`.
+    ///
+    /// Returns `None` if every `UnboundedSender` has already been dropped.
+    /// A handle can still be upgraded after the receiver has gone away, as
+    /// long as some `UnboundedSender` is alive.
+    pub fn upgrade(&self) -> Option<UnboundedSender<T>> {
+        self.0.upgrade().map(UnboundedSender)
+    }
+}
+
+impl<T> Clone for WeakUnboundedSender<T> {
+    fn clone(&self) -> WeakUnboundedSender<T> {
+        WeakUnboundedSender(self.0.clone())
+    }
+}
+
 impl<T> Drop for Sender<T> {
     fn drop(&mut self) {
         // Ordering between variables don't matter here
@@ -617,6 +903,56 @@ impl<T> Drop for Sender<T> {
  */
 
 impl<T> Receiver<T> {
+    /// Returns a future which receives a batch of messages at once.
+    ///
+    /// The returned future waits for at least one message to be available,
+    /// and then moves up to `limit` messages which are already queued into
+    /// `buf` in one go. It resolves to the number of messages received,
+    /// which is only zero once the channel is closed and drained.
+    ///
+    /// This can be used by consumers with a high throughput to avoid waking
+    /// up once per message.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `limit` is zero.
+    pub fn recv_many<'a>(&'a mut self, buf: &'a mut Vec<T>, limit: usize)
+                         -> RecvMany<'a, T>
+    {
+        assert!(limit > 0);
+
+        RecvMany {
+            receiver: self,
+            buf: buf,
+            limit: limit,
+        }
+    }
+
+    /// Returns the number of messages currently buffered in the channel.
+    ///
+    /// Note that other handles to the channel may be sending or receiving
+    /// concurrently, so this is only a snapshot which may be out of date as
+    /// soon as it's returned.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if there are no messages buffered in the channel.
+    ///
+    /// Like `len`, this is only a snapshot of the channel's state.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of messages the channel can hold.
+    ///
+    /// This is the `buffer` the channel was created with, plus one
+    /// guaranteed slot for every `Sender` in existence, so it changes as
+    /// senders are cloned and dropped.
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
     /// Closes the receiving half
     ///
     /// This prevents any further messages from being sent on the channel while
@@ -645,7 +981,7 @@ impl<T> Receiver<T> {
         loop {
             match unsafe { self.inner.parked_queue.pop() } {
                 PopResult::Data(task) => {
-                    let task = task.lock().unwrap().take();
+                    let task = task.lock().unwrap().unpark();
                     if let Some(task) = task {
                         task.unpark();
                     }
@@ -692,7 +1028,7 @@ impl<T> Receiver<T> {
                 PopResult::Data(task) => {
                     // Do this step first so that the lock is dropped when
                     // `unpark` is called
-                    let task = task.lock().unwrap().take();
+                    let task = task.lock().unwrap().unpark();
 
                     if let Some(task) = task {
                         task.unpark();
@@ -790,8 +1126,11 @@ impl<T> Stream for Receiver<T> {
             // one and unpark it.
             self.unpark_one();
 
-            // Decrement number of messages
-            self.dec_num_messages();
+            // Decrement number of messages, which doesn't include the
+            // termination message
+            if msg.is_some() {
+                self.dec_num_messages();
+            }
 
             // Return the message
             return Ok(Async::Ready(msg));
@@ -810,6 +1149,35 @@ impl<T> Drop for Receiver<T> {
 }
 
 impl<T> UnboundedReceiver<T> {
+    /// Returns a future which receives a batch of messages at once.
+    ///
+    /// See `Receiver::recv_many` for more details.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `limit` is zero.
+    pub fn recv_many<'a>(&'a mut self, buf: &'a mut Vec<T>, limit: usize)
+                         -> RecvMany<'a, T>
+    {
+        self.0.recv_many(buf, limit)
+    }
+
+    /// Returns the number of messages currently buffered in the channel.
+    ///
+    /// Note that other handles to the channel may be sending or receiving
+    /// concurrently, so this is only a snapshot which may be out of date as
+    /// soon as it's returned.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no messages buffered in the channel.
+    ///
+    /// Like `len`, this is only a snapshot of the channel's state.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     /// Closes the receiving half
     ///
     /// This prevents any further messages from being sent on the channel while
@@ -828,6 +1196,84 @@ impl<T> Stream for UnboundedReceiver<T> {
     }
 }
 
+/// A future which receives a batch of messages from a channel.
+///
+/// This is created by the `Receiver::recv_many` method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct RecvMany<'a, T: 'a> {
+    receiver: &'a mut Receiver<T>,
+    buf: &'a mut Vec<T>,
+    limit: usize,
+}
+
+impl<'a, T> Future for RecvMany<'a, T> {
+    type Item = usize;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<usize, ()> {
+        let mut received = 0;
+        while received < self.limit {
+            match try!(self.receiver.poll()) {
+                Async::Ready(Some(msg)) => {
+                    self.buf.push(msg);
+                    received += 1;
+                }
+                Async::Ready(None) => break,
+                Async::NotReady if received == 0 => return Ok(Async::NotReady),
+                Async::NotReady => break,
+            }
+        }
+        Ok(Async::Ready(received))
+    }
+}
+
+/// A future which waits for capacity to send a message on a channel.
+///
+/// This is created by the `Sender::reserve` method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct Reserve<'a, T: 'a> {
+    sender: Option<&'a mut Sender<T>>,
+}
+
+impl<'a, T> Future for Reserve<'a, T> {
+    type Item = SendPermit<'a, T>;
+    type Error = SendError<()>;
+
+    fn poll(&mut self) -> Poll<SendPermit<'a, T>, SendError<()>> {
+        try_ready!(self.sender.as_mut()
+                       .expect("cannot poll Reserve twice")
+                       .poll_ready());
+        Ok(Async::Ready(SendPermit {
+            sender: self.sender.take().unwrap(),
+        }))
+    }
+}
+
+/// Permission to send a single message on a channel without waiting.
+///
+/// This is the value the `Reserve` future resolves to. No capacity is set
+/// aside for the permit; it relies on the slot every `Sender` is guaranteed
+/// in the channel, so dropping it without sending anything just leaves that
+/// slot unused.
+#[derive(Debug)]
+pub struct SendPermit<'a, T: 'a> {
+    sender: &'a mut Sender<T>,
+}
+
+impl<'a, T> SendPermit<'a, T> {
+    /// Sends a message using the sender's guaranteed slot.
+    ///
+    /// This never waits, and doesn't need to be called from within the
+    /// context of a task. If the receiving half has gone away since the
+    /// permit was obtained, the message is dropped, just as it would have
+    /// been had it been sent before that.
+    pub fn send(self, msg: T) {
+        drop(self.sender.do_send(Some(msg), false));
+    }
+}
+
 /*
  *
  * ===== impl Inner =====
@@ -835,6 +1281,15 @@ impl<T> Stream for UnboundedReceiver<T> {
  */
 
 impl<T> Inner<T> {
+    fn len(&self) -> usize {
+        decode_state(self.state.load(SeqCst)).num_messages
+    }
+
+    fn capacity(&self) -> usize {
+        let buffer = self.buffer.expect("unbounded channels have no capacity");
+        buffer + self.num_senders.load(SeqCst)
+    }
+
     // The return value is such that the total number of messages that can be
     // enqueued into the channel will never exceed MAX_CAPACITY
     fn max_senders(&self) -> usize {
@@ -0,0 +1,263 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::mem;
+use std::prelude::v1::*;
+use std::sync::{Arc, Mutex};
+
+use sync::mpsc::{SendError, TrySendError, TrySendErrorKind};
+use sync_impl::send_error::send_error;
+use task::{self, Task};
+use {Async, AsyncSink, Poll, StartSend, Sink, Stream};
+
+/// The transmission end of a priority channel, which implements `Sink` for
+/// `(priority, message)` pairs.
+///
+/// This is created by the `priority_channel` function.
+#[derive(Debug)]
+pub struct PrioritySender<T> {
+    id: usize,
+    inner: Arc<Mutex<Inner<T>>>,
+}
+
+/// The receiving end of a priority channel, which implements `Stream`.
+///
+/// This is created by the `priority_channel` function.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct PriorityReceiver<T> {
+    inner: Arc<Mutex<Inner<T>>>,
+}
+
+#[derive(Debug)]
+struct Inner<T> {
+    queue: BinaryHeap<Entry<T>>,
+    buffer: usize,
+    // Incremented for every message, so equal priorities are received in
+    // the order they were sent.
+    next_seq: u64,
+    num_senders: usize,
+    closed: bool,
+    recv_task: Option<Task>,
+    // Senders waiting for capacity, keyed by their id. All of these are
+    // woken whenever a message is received.
+    blocked_senders: HashMap<usize, Task>,
+    next_id: usize,
+}
+
+#[derive(Debug)]
+struct Entry<T> {
+    priority: u32,
+    seq: u64,
+    msg: T,
+}
+
+impl<T> PartialEq for Entry<T> {
+    fn eq(&self, other: &Entry<T>) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T> Eq for Entry<T> {}
+
+impl<T> PartialOrd for Entry<T> {
+    fn partial_cmp(&self, other: &Entry<T>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Entry<T> {
+    fn cmp(&self, other: &Entry<T>) -> Ordering {
+        // The heap pops the greatest entry first, so earlier messages must
+        // compare as greater within a priority.
+        match self.priority.cmp(&other.priority) {
+            Ordering::Equal => other.seq.cmp(&self.seq),
+            o => o,
+        }
+    }
+}
+
+/// Creates a bounded channel whose receiver always yields the message with
+/// the highest priority first.
+///
+/// Every message is sent along with a priority, and of the messages
+/// currently buffered the receiver yields the one with the greatest
+/// priority. Messages with the same priority are received in the order in
+/// which they were sent. This lets urgent messages, such as control
+/// messages, overtake bulk data which is already queued up.
+///
+/// As with `channel`, the channel capacity is equal to `buffer +
+/// num-senders`, and senders are notified when additional capacity is
+/// available.
+///
+/// # Examples
+///
+/// ```
+/// use futures::{Future, Stream};
+/// use futures::sync::mpsc;
+///
+/// let (mut tx, rx) = mpsc::priority_channel(4);
+/// tx.try_send(0, "bulk").unwrap();
+/// tx.try_send(0, "more bulk").unwrap();
+/// tx.try_send(1, "control").unwrap();
+/// drop(tx);
+///
+/// let msgs = rx.collect().wait().unwrap();
+/// assert_eq!(msgs, vec!["control", "bulk", "more bulk"]);
+/// ```
+pub fn priority_channel<T>(buffer: usize) -> (PrioritySender<T>, PriorityReceiver<T>) {
+    let inner = Arc::new(Mutex::new(Inner {
+        queue: BinaryHeap::new(),
+        buffer: buffer,
+        next_seq: 0,
+        num_senders: 1,
+        closed: false,
+        recv_task: None,
+        blocked_senders: HashMap::new(),
+        next_id: 1,
+    }));
+    let tx = PrioritySender { id: 0, inner: inner.clone() };
+    let rx = PriorityReceiver { inner: inner };
+    (tx, rx)
+}
+
+impl<T> Inner<T> {
+    fn is_full(&self) -> bool {
+        self.queue.len() >= self.buffer + self.num_senders
+    }
+
+    fn push(&mut self, priority: u32, msg: T) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.queue.push(Entry {
+            priority: priority,
+            seq: seq,
+            msg: msg,
+        });
+        if let Some(task) = self.recv_task.take() {
+            task.unpark();
+        }
+    }
+
+    fn unpark_senders(&mut self) {
+        for (_, task) in mem::replace(&mut self.blocked_senders, HashMap::new()) {
+            task.unpark();
+        }
+    }
+}
+
+impl<T> PrioritySender<T> {
+    /// Attempts to send a message with the given priority without waiting.
+    ///
+    /// This function, unlike `start_send`, is safe to call whether it's
+    /// being called on a task or not. Note that this function, however,
+    /// will *not* attempt to block the current task if the message cannot
+    /// be sent.
+    ///
+    /// An error is returned with the message if the channel is full or the
+    /// receiver has been dropped.
+    pub fn try_send(&mut self, priority: u32, msg: T) -> Result<(), TrySendError<T>> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.closed {
+            return Err(TrySendError {
+                kind: TrySendErrorKind::Disconnected(msg),
+            })
+        }
+        if inner.is_full() {
+            return Err(TrySendError {
+                kind: TrySendErrorKind::Full(msg),
+            })
+        }
+        inner.push(priority, msg);
+        Ok(())
+    }
+}
+
+impl<T> Sink for PrioritySender<T> {
+    type SinkItem = (u32, T);
+    type SinkError = SendError<(u32, T)>;
+
+    fn start_send(&mut self, item: (u32, T)) -> StartSend<(u32, T), SendError<(u32, T)>> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.closed {
+            return Err(send_error(item))
+        }
+        if inner.is_full() {
+            // Replacing this sender's previous task, if any, keeps retries
+            // from piling up.
+            inner.blocked_senders.insert(self.id, task::park());
+            return Ok(AsyncSink::NotReady(item))
+        }
+        inner.push(item.0, item.1);
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), SendError<(u32, T)>> {
+        Ok(Async::Ready(()))
+    }
+
+    fn close(&mut self) -> Poll<(), SendError<(u32, T)>> {
+        Ok(Async::Ready(()))
+    }
+}
+
+impl<T> Clone for PrioritySender<T> {
+    fn clone(&self) -> PrioritySender<T> {
+        let mut inner = self.inner.lock().unwrap();
+        let id = inner.next_id;
+        inner.next_id = inner.next_id.wrapping_add(1);
+        inner.num_senders += 1;
+        PrioritySender {
+            id: id,
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Drop for PrioritySender<T> {
+    fn drop(&mut self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.num_senders -= 1;
+        inner.blocked_senders.remove(&self.id);
+        if inner.num_senders == 0 {
+            if let Some(task) = inner.recv_task.take() {
+                task.unpark();
+            }
+        }
+    }
+}
+
+impl<T> PriorityReceiver<T> {
+    /// Closes the receiving half
+    ///
+    /// This prevents any further messages from being sent on the channel
+    /// while still enabling the receiver to drain messages that are buffered.
+    pub fn close(&mut self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.closed = true;
+        inner.unpark_senders();
+    }
+}
+
+impl<T> Stream for PriorityReceiver<T> {
+    type Item = T;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<Option<T>, ()> {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(entry) = inner.queue.pop() {
+            inner.unpark_senders();
+            return Ok(Async::Ready(Some(entry.msg)))
+        }
+        if inner.closed || inner.num_senders == 0 {
+            return Ok(Async::Ready(None))
+        }
+        inner.recv_task = Some(task::park());
+        Ok(Async::NotReady)
+    }
+}
+
+impl<T> Drop for PriorityReceiver<T> {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
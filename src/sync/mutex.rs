@@ -0,0 +1,171 @@
+use std::cell::UnsafeCell;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex as StdMutex};
+
+use {Async, Future, Poll};
+use sync_impl::mutex::State;
+
+/// A futures-aware mutex.
+///
+/// Unlike `std::sync::Mutex`, acquiring this lock never blocks the current
+/// thread. Instead `lock` returns a future which resolves to a guard once the
+/// lock is available, parking the current task in the meantime. Tasks are
+/// handed the lock in the order in which they started waiting for it, so no
+/// task can be starved by others repeatedly taking the lock.
+///
+/// A `Mutex` is a handle to the protected data, and cloning it creates a new
+/// handle to the same data. Guards own a handle as well, so they can be moved
+/// into other futures freely.
+///
+/// # Examples
+///
+/// ```
+/// use futures::Future;
+/// use futures::sync::Mutex;
+///
+/// let mutex = Mutex::new(0);
+/// let mut guard = mutex.lock().wait().unwrap();
+/// *guard += 1;
+/// assert!(mutex.try_lock().is_none());
+/// drop(guard);
+/// assert_eq!(*mutex.try_lock().unwrap(), 1);
+/// ```
+#[derive(Debug)]
+pub struct Mutex<T> {
+    inner: Arc<Inner<T>>,
+}
+
+#[derive(Debug)]
+struct Inner<T> {
+    state: StdMutex<State>,
+    data: UnsafeCell<T>,
+}
+
+unsafe impl<T: Send> Send for Inner<T> {}
+unsafe impl<T: Send> Sync for Inner<T> {}
+
+impl<T> Mutex<T> {
+    /// Creates a new mutex protecting the provided data.
+    pub fn new(t: T) -> Mutex<T> {
+        Mutex {
+            inner: Arc::new(Inner {
+                state: StdMutex::new(State::new()),
+                data: UnsafeCell::new(t),
+            }),
+        }
+    }
+
+    /// Returns a future which resolves to a guard for the protected data
+    /// once the lock has been acquired.
+    ///
+    /// The lock is unlocked once the guard is dropped. Note that the
+    /// returned future will never resolve to an error.
+    pub fn lock(&self) -> MutexAcquire<T> {
+        MutexAcquire {
+            mutex: Some(self.clone()),
+            id: None,
+        }
+    }
+
+    /// Attempts to acquire this lock without waiting.
+    ///
+    /// Returns `None` if the lock is already held, or if there are other
+    /// tasks waiting for it. This function doesn't need to be called from
+    /// within the context of a task.
+    pub fn try_lock(&self) -> Option<MutexGuard<T>> {
+        if self.inner.state.lock().unwrap().try_lock() {
+            Some(MutexGuard { mutex: self.clone() })
+        } else {
+            None
+        }
+    }
+
+    fn unlock(&self) {
+        let task = self.inner.state.lock().unwrap().unlock();
+        if let Some(task) = task {
+            task.unpark();
+        }
+    }
+}
+
+impl<T> Clone for Mutex<T> {
+    fn clone(&self) -> Mutex<T> {
+        Mutex { inner: self.inner.clone() }
+    }
+}
+
+/// Future returned by `Mutex::lock` which will resolve when the lock is
+/// acquired.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct MutexAcquire<T> {
+    mutex: Option<Mutex<T>>,
+    // Set once we're in the queue of waiters.
+    id: Option<usize>,
+}
+
+impl<T> Future for MutexAcquire<T> {
+    type Item = MutexGuard<T>;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<MutexGuard<T>, ()> {
+        {
+            let mutex = self.mutex.as_ref().expect("cannot poll MutexAcquire twice");
+            let mut state = mutex.inner.state.lock().unwrap();
+            if !state.poll_lock(&mut self.id) {
+                return Ok(Async::NotReady)
+            }
+        }
+        Ok(Async::Ready(MutexGuard { mutex: self.mutex.take().unwrap() }))
+    }
+}
+
+impl<T> Drop for MutexAcquire<T> {
+    fn drop(&mut self) {
+        let (mutex, id) = match (self.mutex.take(), self.id) {
+            (Some(mutex), Some(id)) => (mutex, id),
+            _ => return,
+        };
+
+        let task = mutex.inner.state.lock().unwrap().cancel(id);
+        if let Some(task) = task {
+            task.unpark();
+        }
+    }
+}
+
+/// Resolved value of the `MutexAcquire<T>` future.
+///
+/// This structure acts as a sentinel to the data in the `Mutex<T>` itself,
+/// implementing `Deref` and `DerefMut` to `T`. When dropped, the lock will be
+/// unlocked.
+#[derive(Debug)]
+pub struct MutexGuard<T> {
+    mutex: Mutex<T>,
+}
+
+impl<T> MutexGuard<T> {
+    /// Returns a handle to the mutex this guard has locked.
+    pub fn mutex(&self) -> &Mutex<T> {
+        &self.mutex
+    }
+}
+
+impl<T> Deref for MutexGuard<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.mutex.inner.data.get() }
+    }
+}
+
+impl<T> DerefMut for MutexGuard<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.mutex.inner.data.get() }
+    }
+}
+
+impl<T> Drop for MutexGuard<T> {
+    fn drop(&mut self) {
+        self.mutex.unlock();
+    }
+}
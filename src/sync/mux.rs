@@ -0,0 +1,228 @@
+//! A multi-producer channel which demultiplexes messages by key.
+//!
+//! Every message sent on a mux channel is tagged with a key, and is delivered
+//! to the `Receiver` subscribed to that key, if any. This makes it a good fit
+//! for routing messages to per-connection or per-request tasks, without
+//! having to maintain a map of channels guarded by a lock.
+//!
+//! Each key has at most one `Receiver` at a time, created through the
+//! `Subscriber` handle. Keys are cleaned up automatically: once the
+//! `Receiver` for a key is dropped, its buffered messages are dropped as
+//! well, and messages sent to that key are rejected until somebody
+//! subscribes to it again.
+//!
+//! The stream of every `Receiver` ends once all `Sender` handles have been
+//! dropped and the messages still buffered for it have been received.
+
+use std::collections::{HashMap, VecDeque};
+use std::collections::hash_map::Entry;
+use std::hash::Hash;
+use std::prelude::v1::*;
+use std::sync::{Arc, Mutex};
+
+use {Async, AsyncSink, Poll, StartSend, Sink, Stream};
+use sync_impl::send_error::send_error;
+use task::{self, Task};
+
+pub use sync::mpsc::SendError;
+
+/// The transmission end of a mux channel, which implements `Sink` for
+/// `(key, message)` pairs.
+///
+/// This is created by the `channel` function, and can be cloned to send
+/// from several places.
+#[derive(Debug)]
+pub struct Sender<K: Eq + Hash, T> {
+    inner: Arc<Mutex<Inner<K, T>>>,
+}
+
+/// A handle used to subscribe to the messages sent to a key.
+///
+/// This is created by the `channel` function, and can be cloned to subscribe
+/// from several places.
+#[derive(Debug)]
+pub struct Subscriber<K: Eq + Hash, T> {
+    inner: Arc<Mutex<Inner<K, T>>>,
+}
+
+/// The receiving end of a mux channel for a single key, which implements
+/// `Stream`.
+///
+/// This is created by the `Subscriber::subscribe` method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Receiver<K: Eq + Hash + Clone, T> {
+    key: K,
+    inner: Arc<Mutex<Inner<K, T>>>,
+}
+
+#[derive(Debug)]
+struct Inner<K: Eq + Hash, T> {
+    // Only keys with a receiver are present.
+    slots: HashMap<K, Slot<T>>,
+    num_senders: usize,
+}
+
+#[derive(Debug)]
+struct Slot<T> {
+    queue: VecDeque<T>,
+    recv_task: Option<Task>,
+}
+
+/// Creates a new mux channel, returning its sending half and a handle for
+/// subscribing to keys.
+///
+/// # Examples
+///
+/// ```
+/// use futures::Stream;
+/// use futures::sync::mux;
+///
+/// let (tx, subscriber) = mux::channel();
+/// let a = subscriber.subscribe("a").unwrap();
+/// let b = subscriber.subscribe("b").unwrap();
+///
+/// tx.send_to("a", 1).unwrap();
+/// tx.send_to("b", 2).unwrap();
+/// tx.send_to("a", 3).unwrap();
+/// assert!(tx.send_to("c", 4).is_err());
+/// drop(tx);
+///
+/// assert_eq!(a.wait().collect::<Result<Vec<_>, _>>(), Ok(vec![1, 3]));
+/// assert_eq!(b.wait().collect::<Result<Vec<_>, _>>(), Ok(vec![2]));
+/// ```
+pub fn channel<K, T>() -> (Sender<K, T>, Subscriber<K, T>)
+    where K: Eq + Hash + Clone,
+{
+    let inner = Arc::new(Mutex::new(Inner {
+        slots: HashMap::new(),
+        num_senders: 1,
+    }));
+    let tx = Sender { inner: inner.clone() };
+    let subscriber = Subscriber { inner: inner };
+    (tx, subscriber)
+}
+
+impl<K: Eq + Hash, T> Sender<K, T> {
+    /// Sends a message to the receiver subscribed to `key`.
+    ///
+    /// Messages are buffered without bound until the receiver takes them, so
+    /// this never needs to wait. An error is returned with the key and
+    /// message if nobody is subscribed to `key`.
+    pub fn send_to(&self, key: K, msg: T) -> Result<(), SendError<(K, T)>> {
+        let mut inner = self.inner.lock().unwrap();
+        let slot = match inner.slots.get_mut(&key) {
+            Some(slot) => slot,
+            None => return Err(send_error((key, msg))),
+        };
+        slot.queue.push_back(msg);
+        if let Some(task) = slot.recv_task.take() {
+            task.unpark();
+        }
+        Ok(())
+    }
+
+    /// Returns whether somebody is currently subscribed to `key`.
+    pub fn is_subscribed(&self, key: &K) -> bool {
+        self.inner.lock().unwrap().slots.contains_key(key)
+    }
+}
+
+impl<K: Eq + Hash, T> Sink for Sender<K, T> {
+    type SinkItem = (K, T);
+    type SinkError = SendError<(K, T)>;
+
+    fn start_send(&mut self, item: (K, T)) -> StartSend<(K, T), SendError<(K, T)>> {
+        try!(self.send_to(item.0, item.1));
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), SendError<(K, T)>> {
+        Ok(Async::Ready(()))
+    }
+
+    fn close(&mut self) -> Poll<(), SendError<(K, T)>> {
+        Ok(Async::Ready(()))
+    }
+}
+
+impl<K: Eq + Hash, T> Clone for Sender<K, T> {
+    fn clone(&self) -> Sender<K, T> {
+        self.inner.lock().unwrap().num_senders += 1;
+        Sender { inner: self.inner.clone() }
+    }
+}
+
+impl<K: Eq + Hash, T> Drop for Sender<K, T> {
+    fn drop(&mut self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.num_senders -= 1;
+        if inner.num_senders == 0 {
+            for slot in inner.slots.values_mut() {
+                if let Some(task) = slot.recv_task.take() {
+                    task.unpark();
+                }
+            }
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone, T> Subscriber<K, T> {
+    /// Subscribes to the messages sent to `key` from now on.
+    ///
+    /// Returns `None` if there already is a `Receiver` for `key`.
+    pub fn subscribe(&self, key: K) -> Option<Receiver<K, T>> {
+        let mut inner = self.inner.lock().unwrap();
+        match inner.slots.entry(key.clone()) {
+            Entry::Occupied(_) => None,
+            Entry::Vacant(entry) => {
+                entry.insert(Slot {
+                    queue: VecDeque::new(),
+                    recv_task: None,
+                });
+                Some(Receiver {
+                    key: key,
+                    inner: self.inner.clone(),
+                })
+            }
+        }
+    }
+}
+
+impl<K: Eq + Hash, T> Clone for Subscriber<K, T> {
+    fn clone(&self) -> Subscriber<K, T> {
+        Subscriber { inner: self.inner.clone() }
+    }
+}
+
+impl<K: Eq + Hash + Clone, T> Receiver<K, T> {
+    /// Returns the key this receiver is subscribed to.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+}
+
+impl<K: Eq + Hash + Clone, T> Stream for Receiver<K, T> {
+    type Item = T;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<Option<T>, ()> {
+        let mut inner = self.inner.lock().unwrap();
+        let num_senders = inner.num_senders;
+        let slot = inner.slots.get_mut(&self.key).expect("receiver's slot is missing");
+        if let Some(msg) = slot.queue.pop_front() {
+            return Ok(Async::Ready(Some(msg)))
+        }
+        if num_senders == 0 {
+            return Ok(Async::Ready(None))
+        }
+        slot.recv_task = Some(task::park());
+        Ok(Async::NotReady)
+    }
+}
+
+impl<K: Eq + Hash + Clone, T> Drop for Receiver<K, T> {
+    fn drop(&mut self) {
+        self.inner.lock().unwrap().slots.remove(&self.key);
+    }
+}
@@ -0,0 +1,169 @@
+use std::prelude::v1::*;
+use std::sync::{Arc, Mutex as StdMutex};
+
+use {Async, Future, Poll};
+use sync_impl::wait_queue::WaitQueue;
+use task::Task;
+
+/// A lightweight primitive for notifying tasks of an event.
+///
+/// Tasks wait for a notification with the `notified` future, and are woken
+/// up by `notify_one`, which wakes a single waiting task, or `notify_all`,
+/// which wakes every task waiting at the time.
+///
+/// If `notify_one` is called while no task is waiting, the notification
+/// isn't lost: it's stored as a permit, and the next call to `notified`
+/// completes right away by consuming it. At most one permit is stored, so
+/// several such calls only release a single future. This avoids a race
+/// between a task deciding to wait and another task sending the notification
+/// it is waiting for.
+///
+/// A `Notify` is a handle, and cloning it creates a new handle to the same
+/// primitive.
+///
+/// # Examples
+///
+/// ```
+/// use futures::Future;
+/// use futures::sync::Notify;
+///
+/// let notify = Notify::new();
+///
+/// // The notification is stored until someone waits for it
+/// notify.notify_one();
+/// notify.notified().wait().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct Notify {
+    inner: Arc<StdMutex<State>>,
+}
+
+#[derive(Debug)]
+struct State {
+    permit: bool,
+    // Tasks waiting for a notification, along with whether they were
+    // singled out by `notify_one` once they've been notified.
+    waiters: WaitQueue<bool>,
+}
+
+impl State {
+    fn notify_one(&mut self) -> Option<Task> {
+        match self.waiters.front_mut() {
+            Some(singled_out) => *singled_out = true,
+            None => {
+                self.permit = true;
+                return None
+            }
+        }
+        Some(self.waiters.grant(0))
+    }
+}
+
+impl Notify {
+    /// Creates a new `Notify` with no stored permit.
+    pub fn new() -> Notify {
+        Notify {
+            inner: Arc::new(StdMutex::new(State {
+                permit: false,
+                waiters: WaitQueue::new(),
+            })),
+        }
+    }
+
+    /// Returns a future which resolves once this task has been notified.
+    ///
+    /// If a permit has been stored by `notify_one`, it's consumed the first
+    /// time the future is polled and the future completes right away. Note
+    /// that the returned future will never resolve to an error.
+    pub fn notified(&self) -> Notified {
+        Notified {
+            notify: self.clone(),
+            id: None,
+        }
+    }
+
+    /// Notifies the task which has been waiting the longest.
+    ///
+    /// If no task is waiting, a permit is stored instead, so that the next
+    /// call to `notified` completes right away.
+    pub fn notify_one(&self) {
+        let task = self.inner.lock().unwrap().notify_one();
+        if let Some(task) = task {
+            task.unpark();
+        }
+    }
+
+    /// Notifies every task which is currently waiting.
+    ///
+    /// Unlike `notify_one`, this doesn't store a permit if no task is
+    /// waiting.
+    pub fn notify_all(&self) {
+        let tasks = self.inner.lock().unwrap().waiters.grant_all();
+        for task in tasks {
+            task.unpark();
+        }
+    }
+}
+
+impl Clone for Notify {
+    fn clone(&self) -> Notify {
+        Notify { inner: self.inner.clone() }
+    }
+}
+
+/// Future returned by `Notify::notified` which will resolve once the task
+/// has been notified.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct Notified {
+    notify: Notify,
+    // Set once we're in the queue of waiters.
+    id: Option<usize>,
+}
+
+impl Future for Notified {
+    type Item = ();
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<(), ()> {
+        let mut state = self.notify.inner.lock().unwrap();
+        match self.id {
+            None => {
+                if state.permit {
+                    state.permit = false;
+                    return Ok(Async::Ready(()))
+                }
+                self.id = Some(state.waiters.push(false));
+            }
+            Some(id) => {
+                if state.waiters.poll(id).is_some() {
+                    self.id = None;
+                    return Ok(Async::Ready(()))
+                }
+            }
+        }
+        Ok(Async::NotReady)
+    }
+}
+
+impl Drop for Notified {
+    fn drop(&mut self) {
+        let id = match self.id {
+            Some(id) => id,
+            None => return,
+        };
+
+        let task = {
+            let mut state = self.notify.inner.lock().unwrap();
+            match state.waiters.remove(id) {
+                // We were singled out by `notify_one` but are no longer
+                // interested, so pass the notification on.
+                Some(true) => state.notify_one(),
+                _ => None,
+            }
+        };
+        if let Some(task) = task {
+            task.unpark();
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use std::cell::UnsafeCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::mem;
+use std::prelude::v1::*;
+use std::sync::Mutex as StdMutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use {Async, Future, IntoFuture, Poll};
+use task::{self, Task};
+
+/// A cell which is lazily initialized by a future, at most once.
+///
+/// The first task to call `get_or_init` runs the initializer it was given,
+/// while others calling it concurrently are parked until the value is
+/// ready, and their initializers are never run. If the initializer fails,
+/// the error is returned to the task which ran it, and one of the waiting
+/// tasks gets to run its own initializer instead.
+///
+/// This is useful for shared resources which are expensive to establish,
+/// such as connection pools. As with `std::sync::Mutex`, a `OnceCell` is
+/// typically shared by wrapping it in an `Arc`.
+///
+/// # Examples
+///
+/// ```
+/// use futures::Future;
+/// use futures::future;
+/// use futures::sync::OnceCell;
+///
+/// let cell = OnceCell::new();
+/// assert_eq!(cell.get(), None);
+///
+/// let value = cell.get_or_init(|| future::ok::<_, ()>(1)).wait().unwrap();
+/// assert_eq!(*value, 1);
+///
+/// // The value is only initialized once
+/// let value = cell.get_or_init(|| future::ok::<_, ()>(2)).wait().unwrap();
+/// assert_eq!(*value, 1);
+/// ```
+pub struct OnceCell<T> {
+    // Set once `value` has been written, after which it's never modified
+    // again.
+    ready: AtomicBool,
+    value: UnsafeCell<Option<T>>,
+    state: StdMutex<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    initializing: bool,
+    // Tasks waiting for another task's initializer to finish, keyed by the
+    // id of the `GetOrInit` future which is waiting.
+    blocked: HashMap<usize, Task>,
+    next_id: usize,
+}
+
+unsafe impl<T: Send> Send for OnceCell<T> {}
+unsafe impl<T: Send + Sync> Sync for OnceCell<T> {}
+
+impl<T> OnceCell<T> {
+    /// Creates a new, uninitialized cell.
+    pub fn new() -> OnceCell<T> {
+        OnceCell {
+            ready: AtomicBool::new(false),
+            value: UnsafeCell::new(None),
+            state: StdMutex::new(State {
+                initializing: false,
+                blocked: HashMap::new(),
+                next_id: 0,
+            }),
+        }
+    }
+
+    /// Returns a reference to the value, or `None` if the cell hasn't been
+    /// initialized yet.
+    pub fn get(&self) -> Option<&T> {
+        if self.ready.load(Ordering::Acquire) {
+            unsafe { (*self.value.get()).as_ref() }
+        } else {
+            None
+        }
+    }
+
+    /// Returns a future which resolves to a reference to the value,
+    /// initializing it with the future returned by `f` if needed.
+    ///
+    /// `f` is only called if no other task is already initializing the
+    /// cell, at the time the returned future is polled. Otherwise the current
+    /// task is parked until the other initializer completes.
+    pub fn get_or_init<F, R>(&self, f: F) -> GetOrInit<T, F, R>
+        where F: FnOnce() -> R,
+              R: IntoFuture<Item = T>,
+    {
+        GetOrInit {
+            cell: self,
+            init: Some(f),
+            future: None,
+            id: None,
+        }
+    }
+
+    /// Consumes the cell, returning the value if it has been initialized.
+    pub fn into_inner(self) -> Option<T> {
+        unsafe { (*self.value.get()).take() }
+    }
+
+    // Called by the initializing task once it's done, successfully or not.
+    fn finish(&self) {
+        let blocked = {
+            let mut state = self.state.lock().unwrap();
+            state.initializing = false;
+            mem::replace(&mut state.blocked, HashMap::new())
+        };
+        for (_, task) in blocked {
+            task.unpark();
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for OnceCell<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("OnceCell")
+            .field("value", &self.get())
+            .finish()
+    }
+}
+
+/// Future returned by `OnceCell::get_or_init` which will resolve to a
+/// reference to the value once the cell has been initialized.
+#[must_use = "futures do nothing unless polled"]
+pub struct GetOrInit<'a, T: 'a, F, R: IntoFuture> {
+    cell: &'a OnceCell<T>,
+    init: Option<F>,
+    // Set while we're the ones initializing the cell.
+    future: Option<R::Future>,
+    // Set once we've had to wait for somebody else's initializer.
+    id: Option<usize>,
+}
+
+impl<'a, T, F, R> fmt::Debug for GetOrInit<'a, T, F, R>
+    where T: fmt::Debug,
+          R: IntoFuture,
+          R::Future: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("GetOrInit")
+            .field("cell", &self.cell)
+            .field("future", &self.future)
+            .finish()
+    }
+}
+
+impl<'a, T, F, R> Future for GetOrInit<'a, T, F, R>
+    where F: FnOnce() -> R,
+          R: IntoFuture<Item = T>,
+{
+    type Item = &'a T;
+    type Error = R::Error;
+
+    fn poll(&mut self) -> Poll<&'a T, R::Error> {
+        let cell = self.cell;
+        if self.future.is_none() {
+            if let Some(value) = cell.get() {
+                return Ok(Async::Ready(value))
+            }
+            {
+                let mut state = cell.state.lock().unwrap();
+                // Check again now that we hold the lock, as the initializer
+                // may have finished in the meantime.
+                if let Some(value) = cell.get() {
+                    return Ok(Async::Ready(value))
+                }
+                if state.initializing {
+                    let id = match self.id {
+                        Some(id) => id,
+                        None => {
+                            let id = state.next_id;
+                            state.next_id = state.next_id.wrapping_add(1);
+                            self.id = Some(id);
+                            id
+                        }
+                    };
+                    // Update the task in case we've been moved to another
+                    // task since we started waiting.
+                    state.blocked.insert(id, task::park());
+                    return Ok(Async::NotReady)
+                }
+                state.initializing = true;
+            }
+            let init = self.init.take().expect("cannot poll GetOrInit twice");
+            // Let somebody else initialize the cell if `init` panics.
+            let guard = Finish(cell);
+            self.future = Some(init().into_future());
+            mem::forget(guard);
+        }
+
+        let value = match self.future.as_mut().unwrap().poll() {
+            Ok(Async::Ready(value)) => value,
+            Ok(Async::NotReady) => return Ok(Async::NotReady),
+            Err(e) => {
+                self.future = None;
+                cell.finish();
+                return Err(e)
+            }
+        };
+        self.future = None;
+        unsafe {
+            *cell.value.get() = Some(value);
+        }
+        cell.ready.store(true, Ordering::Release);
+        cell.finish();
+        Ok(Async::Ready(cell.get().unwrap()))
+    }
+}
+
+// Calls `OnceCell::finish` when dropped, unless forgotten.
+struct Finish<'a, T: 'a>(&'a OnceCell<T>);
+
+impl<'a, T> Drop for Finish<'a, T> {
+    fn drop(&mut self) {
+        self.0.finish();
+    }
+}
+
+impl<'a, T, F, R: IntoFuture> Drop for GetOrInit<'a, T, F, R> {
+    fn drop(&mut self) {
+        // Let somebody else initialize the cell if we gave up halfway.
+        if self.future.take().is_some() {
+            self.cell.finish();
+        } else if let Some(id) = self.id {
+            self.cell.state.lock().unwrap().blocked.remove(&id);
+        }
+    }
+}
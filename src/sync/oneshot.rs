@@ -7,6 +7,7 @@ use std::error::Error;
 use std::fmt;
 
 use {Future, Poll, Async};
+use executor::{self, Executor};
 use lock::Lock;
 use task::{self, Task};
 
@@ -194,6 +195,20 @@ impl<T> Sender<T> {
             Ok(Async::NotReady)
         }
     }
+
+    /// Tests to see whether this `Sender`'s corresponding `Receiver`
+    /// has gone away.
+    ///
+    /// This function can be used to learn about when the `Receiver` (consumer)
+    /// half has gone away and nothing will be able to receive a message sent
+    /// from `send`, so that any work to produce the message can be abandoned.
+    ///
+    /// Unlike `poll_cancel`, this function does not register interest in the
+    /// `Receiver` going away, so it may be called outside the context of a
+    /// task.
+    pub fn is_canceled(&self) -> bool {
+        self.inner.complete.load(SeqCst)
+    }
 }
 
 impl<T> Drop for Sender<T> {
@@ -339,3 +354,52 @@ impl<T> Drop for Receiver<T> {
         }
     }
 }
+
+/// Executes a future on `executor`, returning a `Receiver` which will
+/// resolve to the future's result.
+///
+/// This is a building block for running a computation in one place and
+/// waiting for its result somewhere else. If the returned `Receiver` is
+/// dropped before the future completes, the future is dropped as well the
+/// next time the executor polls it, canceling the computation.
+///
+/// The `Receiver` resolves to `Canceled` if the future is dropped by the
+/// executor without completing.
+pub fn spawn_future<F>(future: F, executor: Arc<Executor>)
+                       -> Receiver<Result<F::Item, F::Error>>
+    where F: Future + Send + 'static,
+          F::Item: Send + 'static,
+          F::Error: Send + 'static,
+{
+    let (tx, rx) = channel();
+    executor::spawn(Execute {
+        future: future,
+        tx: Some(tx),
+    }).execute(executor);
+    rx
+}
+
+struct Execute<F: Future> {
+    future: F,
+    tx: Option<Sender<Result<F::Item, F::Error>>>,
+}
+
+impl<F: Future> Future for Execute<F> {
+    type Item = ();
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<(), ()> {
+        if let Ok(Async::Ready(())) = self.tx.as_mut().unwrap().poll_cancel() {
+            // Nobody is interested in the result anymore, so bail out
+            return Ok(Async::Ready(()))
+        }
+
+        let res = match self.future.poll() {
+            Ok(Async::Ready(item)) => Ok(item),
+            Ok(Async::NotReady) => return Ok(Async::NotReady),
+            Err(e) => Err(e),
+        };
+        drop(self.tx.take().unwrap().send(res));
+        Ok(Async::Ready(()))
+    }
+}
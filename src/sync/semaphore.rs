@@ -0,0 +1,230 @@
+use std::prelude::v1::*;
+use std::sync::{Arc, Mutex as StdMutex};
+
+use {Async, Future, Poll};
+use sync_impl::wait_queue::WaitQueue;
+use task::Task;
+
+/// A futures-aware counting semaphore.
+///
+/// A semaphore holds a number of permits, which tasks acquire with the
+/// `acquire` future and which are returned to the semaphore when the
+/// resulting `Permit` is dropped. Acquiring never blocks the current thread;
+/// instead the current task is parked until enough permits are available.
+/// Permits are handed out in the order in which tasks asked for them, so a
+/// large request isn't starved by a stream of smaller ones.
+///
+/// This is typically used to bound the number of concurrent operations, such
+/// as outbound requests. A `Semaphore` is a handle, and cloning it creates a
+/// new handle to the same semaphore. Permits own a handle as well, so they
+/// can be moved into spawned futures and live for as long as needed.
+///
+/// # Examples
+///
+/// ```
+/// use futures::Future;
+/// use futures::sync::Semaphore;
+///
+/// let semaphore = Semaphore::new(3);
+/// let permit = semaphore.acquire(2).wait().unwrap();
+/// assert_eq!(semaphore.available_permits(), 1);
+/// assert!(semaphore.try_acquire(2).is_none());
+/// drop(permit);
+/// assert_eq!(semaphore.available_permits(), 3);
+/// ```
+#[derive(Debug)]
+pub struct Semaphore {
+    inner: Arc<StdMutex<State>>,
+}
+
+#[derive(Debug)]
+struct State {
+    permits: usize,
+    // Tasks waiting for permits, along with how many they asked for.
+    waiters: WaitQueue<usize>,
+}
+
+impl State {
+    // Hands out permits to as many waiters as possible, returning the tasks
+    // which need to be woken up.
+    fn grant(&mut self) -> Vec<Task> {
+        let mut tasks = Vec::new();
+        while let Some(permits) = self.waiters.front().cloned() {
+            if permits > self.permits {
+                break
+            }
+            self.permits -= permits;
+            tasks.push(self.waiters.grant(0));
+        }
+        tasks
+    }
+}
+
+impl Semaphore {
+    /// Creates a new semaphore with the given number of permits.
+    pub fn new(permits: usize) -> Semaphore {
+        Semaphore {
+            inner: Arc::new(StdMutex::new(State {
+                permits: permits,
+                waiters: WaitQueue::new(),
+            })),
+        }
+    }
+
+    /// Returns the number of permits which are currently available.
+    ///
+    /// Note that other tasks may be acquiring or releasing permits
+    /// concurrently, so this may be out of date as soon as it's returned.
+    pub fn available_permits(&self) -> usize {
+        self.inner.lock().unwrap().permits
+    }
+
+    /// Adds `n` new permits to the semaphore, waking up any tasks which can
+    /// now acquire theirs.
+    pub fn add_permits(&self, n: usize) {
+        let tasks = {
+            let mut state = self.inner.lock().unwrap();
+            state.permits += n;
+            state.grant()
+        };
+        for task in tasks {
+            task.unpark();
+        }
+    }
+
+    /// Returns a future which resolves to a `Permit` once `n` permits have
+    /// been acquired.
+    ///
+    /// The permits are returned to the semaphore when the `Permit` is
+    /// dropped. Note that the returned future will never resolve to an
+    /// error, and will never resolve at all if `n` is larger than the number
+    /// of permits the semaphore will ever hold.
+    pub fn acquire(&self, n: usize) -> SemaphoreAcquire {
+        SemaphoreAcquire {
+            semaphore: Some(self.clone()),
+            permits: n,
+            id: None,
+        }
+    }
+
+    /// Attempts to acquire `n` permits without waiting.
+    ///
+    /// Returns `None` if not enough permits are available, or if there are
+    /// other tasks waiting for permits. This function doesn't need to be
+    /// called from within the context of a task.
+    pub fn try_acquire(&self, n: usize) -> Option<Permit> {
+        let mut state = self.inner.lock().unwrap();
+        if !state.waiters.is_empty() || state.permits < n {
+            return None
+        }
+        state.permits -= n;
+        Some(Permit {
+            semaphore: self.clone(),
+            permits: n,
+        })
+    }
+}
+
+impl Clone for Semaphore {
+    fn clone(&self) -> Semaphore {
+        Semaphore { inner: self.inner.clone() }
+    }
+}
+
+/// Future returned by `Semaphore::acquire` which will resolve once the
+/// requested permits have been acquired.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct SemaphoreAcquire {
+    semaphore: Option<Semaphore>,
+    permits: usize,
+    // Set once we're in the queue of waiters.
+    id: Option<usize>,
+}
+
+impl Future for SemaphoreAcquire {
+    type Item = Permit;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<Permit, ()> {
+        {
+            let semaphore = self.semaphore.as_ref()
+                .expect("cannot poll SemaphoreAcquire twice");
+            let mut state = semaphore.inner.lock().unwrap();
+            match self.id {
+                None => {
+                    if state.waiters.is_empty() && state.permits >= self.permits {
+                        state.permits -= self.permits;
+                    } else {
+                        self.id = Some(state.waiters.push(self.permits));
+                        return Ok(Async::NotReady)
+                    }
+                }
+                Some(id) => {
+                    if state.waiters.poll(id).is_none() {
+                        return Ok(Async::NotReady)
+                    }
+                }
+            }
+        }
+
+        self.id = None;
+        Ok(Async::Ready(Permit {
+            semaphore: self.semaphore.take().unwrap(),
+            permits: self.permits,
+        }))
+    }
+}
+
+impl Drop for SemaphoreAcquire {
+    fn drop(&mut self) {
+        let (semaphore, id) = match (self.semaphore.take(), self.id) {
+            (Some(semaphore), Some(id)) => (semaphore, id),
+            _ => return,
+        };
+
+        let tasks = {
+            let mut state = semaphore.inner.lock().unwrap();
+            // If we were handed our permits but are no longer interested in
+            // them, give them back.
+            if let Some(permits) = state.waiters.remove(id) {
+                state.permits += permits;
+            }
+            // Our departure from the queue may let others in as well.
+            state.grant()
+        };
+        for task in tasks {
+            task.unpark();
+        }
+    }
+}
+
+/// A number of permits acquired from a `Semaphore`.
+///
+/// The permits are returned to the semaphore when this is dropped.
+#[derive(Debug)]
+pub struct Permit {
+    semaphore: Semaphore,
+    permits: usize,
+}
+
+impl Permit {
+    /// Returns the number of permits held.
+    pub fn permits(&self) -> usize {
+        self.permits
+    }
+
+    /// Consumes this `Permit` without returning its permits to the
+    /// semaphore, permanently reducing the number of permits available.
+    pub fn forget(mut self) {
+        self.permits = 0;
+    }
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        if self.permits > 0 {
+            self.semaphore.add_permits(self.permits);
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! A single-producer, multi-consumer channel which only retains the latest
+//! value.
+//!
+//! A watch channel holds a single value, which the `Sender` can replace at
+//! any time. Each `Receiver` is a stream which yields the current value the
+//! first time it's polled, and after that yields the value again whenever it
+//! has changed. Receivers which don't keep up only see the most recent value,
+//! with intermediate updates being coalesced. This makes it a good fit for
+//! state which components want to observe without queuing every change,
+//! such as the current configuration or health of a service.
+//!
+//! The stream of values ends once the `Sender` has been dropped and the last
+//! value sent has been received.
+
+use std::collections::HashMap;
+use std::mem;
+use std::prelude::v1::*;
+use std::sync::{Arc, Mutex};
+
+use {Async, AsyncSink, Poll, StartSend, Sink, Stream};
+use sync_impl::send_error::send_error;
+use task::{self, Task};
+
+pub use sync::mpsc::SendError;
+
+/// The sending half of a watch channel.
+///
+/// This is created by the `channel` function.
+#[derive(Debug)]
+pub struct Sender<T> {
+    inner: Arc<Mutex<Inner<T>>>,
+}
+
+/// The receiving half of a watch channel, which implements `Stream`.
+///
+/// This is created by the `channel` function. Cloning a receiver creates a
+/// new one which has seen the same values.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Receiver<T> {
+    // Version of the last value this receiver yielded.
+    version: u64,
+    id: usize,
+    inner: Arc<Mutex<Inner<T>>>,
+}
+
+#[derive(Debug)]
+struct Inner<T> {
+    value: T,
+    // Incremented every time the value changes.
+    version: u64,
+    closed: bool,
+    receivers: usize,
+    // Receivers waiting for the value to change, keyed by their id. All of
+    // these are woken whenever it does.
+    blocked: HashMap<usize, Task>,
+    next_id: usize,
+}
+
+/// Creates a new watch channel holding the `initial` value.
+///
+/// # Examples
+///
+/// ```
+/// use futures::Stream;
+/// use futures::sync::watch;
+///
+/// let (tx, rx) = watch::channel("starting");
+/// let mut rx = rx.wait();
+/// assert_eq!(rx.next(), Some(Ok("starting")));
+///
+/// // Only the latest value is seen
+/// tx.broadcast("healthy").unwrap();
+/// tx.broadcast("degraded").unwrap();
+/// drop(tx);
+/// assert_eq!(rx.next(), Some(Ok("degraded")));
+/// assert_eq!(rx.next(), None);
+/// ```
+pub fn channel<T: Clone>(initial: T) -> (Sender<T>, Receiver<T>) {
+    let inner = Arc::new(Mutex::new(Inner {
+        value: initial,
+        version: 1,
+        closed: false,
+        receivers: 1,
+        blocked: HashMap::new(),
+        next_id: 1,
+    }));
+    let tx = Sender { inner: inner.clone() };
+    let rx = Receiver { version: 0, id: 0, inner: inner };
+    (tx, rx)
+}
+
+impl<T> Inner<T> {
+    fn unpark_blocked(&mut self) {
+        for (_, task) in mem::replace(&mut self.blocked, HashMap::new()) {
+            task.unpark();
+        }
+    }
+}
+
+impl<T> Sender<T> {
+    /// Replaces the value held by the channel, notifying every receiver.
+    ///
+    /// Unlike `Sink::send`, this replaces the value right away rather than
+    /// through a future, as it never needs to wait. An error is returned with
+    /// the value if there are no receivers left.
+    pub fn broadcast(&self, value: T) -> Result<(), SendError<T>> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.receivers == 0 {
+            return Err(send_error(value))
+        }
+        inner.value = value;
+        inner.version += 1;
+        inner.unpark_blocked();
+        Ok(())
+    }
+}
+
+impl<T> Sink for Sender<T> {
+    type SinkItem = T;
+    type SinkError = SendError<T>;
+
+    fn start_send(&mut self, value: T) -> StartSend<T, SendError<T>> {
+        try!(self.broadcast(value));
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), SendError<T>> {
+        Ok(Async::Ready(()))
+    }
+
+    fn close(&mut self) -> Poll<(), SendError<T>> {
+        Ok(Async::Ready(()))
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.closed = true;
+        inner.unpark_blocked();
+    }
+}
+
+impl<T: Clone> Receiver<T> {
+    /// Returns a copy of the value currently held by the channel.
+    ///
+    /// This doesn't count as having seen the value, so the stream will still
+    /// yield it if it hasn't done so already.
+    pub fn get(&self) -> T {
+        self.inner.lock().unwrap().value.clone()
+    }
+}
+
+impl<T: Clone> Stream for Receiver<T> {
+    type Item = T;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<Option<T>, ()> {
+        let mut inner = self.inner.lock().unwrap();
+        if self.version != inner.version {
+            self.version = inner.version;
+            return Ok(Async::Ready(Some(inner.value.clone())))
+        }
+        if inner.closed {
+            return Ok(Async::Ready(None))
+        }
+        // Replacing this receiver's previous task, if any, keeps repeated
+        // polls from piling up.
+        inner.blocked.insert(self.id, task::park());
+        Ok(Async::NotReady)
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Receiver<T> {
+        let mut inner = self.inner.lock().unwrap();
+        let id = inner.next_id;
+        inner.next_id = inner.next_id.wrapping_add(1);
+        inner.receivers += 1;
+        Receiver {
+            version: self.version,
+            id: id,
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.receivers -= 1;
+        inner.blocked.remove(&self.id);
+    }
+}
@@ -0,0 +1,150 @@
+//! The channel state behind `sync::broadcast` and `unsync::broadcast`.
+
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt;
+use std::mem;
+use std::prelude::v1::*;
+
+use {Async, Poll};
+use sync::mpsc::SendError;
+use sync_impl::send_error::send_error;
+use task::{self, Task};
+
+/// The messages buffered by a broadcast channel, and who's waiting for
+/// them.
+///
+/// The handles wrap this in a `std::sync::Mutex` or a `RefCell`.
+#[derive(Debug)]
+pub struct State<T> {
+    // The most recent messages, oldest first.
+    buffer: VecDeque<T>,
+    capacity: usize,
+    // Sequence number of the message at the front of `buffer`.
+    start: u64,
+    senders: usize,
+    receivers: usize,
+    // Receivers waiting for a new message, keyed by their id. All of these
+    // are woken whenever one is sent.
+    blocked: HashMap<usize, Task>,
+    next_id: usize,
+}
+
+/// Error yielded by a `Receiver`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RecvError {
+    /// The receiver fell behind, and the given number of messages were
+    /// pushed out of the buffer before it could see them.
+    Lagged(u64),
+}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RecvError::Lagged(n) => write!(fmt, "receiver lagged behind by {} messages", n),
+        }
+    }
+}
+
+impl Error for RecvError {
+    fn description(&self) -> &str {
+        match *self {
+            RecvError::Lagged(_) => "receiver lagged behind",
+        }
+    }
+}
+
+impl<T> State<T> {
+    /// Creates the state of a new channel with one sender and one receiver,
+    /// the latter of which has id 0.
+    pub fn new(capacity: usize) -> State<T> {
+        assert!(capacity > 0);
+
+        State {
+            buffer: VecDeque::with_capacity(capacity),
+            capacity: capacity,
+            start: 0,
+            senders: 1,
+            receivers: 1,
+            blocked: HashMap::new(),
+            next_id: 1,
+        }
+    }
+
+    /// Returns the sequence number the next message sent will get.
+    pub fn end(&self) -> u64 {
+        self.start + self.buffer.len() as u64
+    }
+
+    /// Returns the number of receivers currently subscribed.
+    pub fn receivers(&self) -> usize {
+        self.receivers
+    }
+
+    pub fn broadcast(&mut self, msg: T) -> Result<(), SendError<T>> {
+        if self.receivers == 0 {
+            return Err(send_error(msg))
+        }
+        if self.buffer.len() == self.capacity {
+            self.buffer.pop_front();
+            self.start += 1;
+        }
+        self.buffer.push_back(msg);
+        self.unpark_blocked();
+        Ok(())
+    }
+
+    pub fn add_sender(&mut self) {
+        self.senders += 1;
+    }
+
+    pub fn remove_sender(&mut self) {
+        self.senders -= 1;
+        if self.senders == 0 {
+            self.unpark_blocked();
+        }
+    }
+
+    /// Registers a new receiver, returning its id.
+    pub fn add_receiver(&mut self) -> usize {
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        self.receivers += 1;
+        id
+    }
+
+    pub fn remove_receiver(&mut self, id: usize) {
+        self.receivers -= 1;
+        self.blocked.remove(&id);
+    }
+
+    /// Polls the receiver `id` for the message numbered `pos`, moving `pos`
+    /// past whatever it yields.
+    pub fn poll(&mut self, id: usize, pos: &mut u64) -> Poll<Option<T>, RecvError>
+        where T: Clone,
+    {
+        if *pos < self.start {
+            let missed = self.start - *pos;
+            *pos = self.start;
+            return Err(RecvError::Lagged(missed))
+        }
+        if *pos < self.end() {
+            let msg = self.buffer[(*pos - self.start) as usize].clone();
+            *pos += 1;
+            return Ok(Async::Ready(Some(msg)))
+        }
+        if self.senders == 0 {
+            return Ok(Async::Ready(None))
+        }
+        // Replacing this receiver's previous task, if any, keeps repeated
+        // polls from piling up.
+        self.blocked.insert(id, task::park());
+        Ok(Async::NotReady)
+    }
+
+    fn unpark_blocked(&mut self) {
+        for (_, task) in mem::replace(&mut self.blocked, HashMap::new()) {
+            task.unpark();
+        }
+    }
+}
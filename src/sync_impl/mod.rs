@@ -0,0 +1,7 @@
+//! Internals shared by the synchronization primitives in `sync` and `unsync`.
+
+pub mod broadcast;
+pub mod mutex;
+pub mod rwlock;
+pub mod send_error;
+pub mod wait_queue;
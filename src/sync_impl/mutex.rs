@@ -0,0 +1,78 @@
+//! The bookkeeping behind `sync::Mutex` and `unsync::Mutex`.
+
+use sync_impl::wait_queue::WaitQueue;
+use task::Task;
+
+/// Whether a mutex is locked, and who's waiting for it.
+///
+/// The handles wrap this in a `std::sync::Mutex` or a `RefCell`, and wake up
+/// the tasks returned here once they've let go of it.
+#[derive(Debug)]
+pub struct State {
+    locked: bool,
+    waiters: WaitQueue<()>,
+}
+
+impl State {
+    pub fn new() -> State {
+        State {
+            locked: false,
+            waiters: WaitQueue::new(),
+        }
+    }
+
+    /// Takes the lock if it's free and nobody is waiting for it.
+    pub fn try_lock(&mut self) -> bool {
+        if self.locked || !self.waiters.is_empty() {
+            return false
+        }
+        self.locked = true;
+        true
+    }
+
+    /// Polls a `MutexAcquire` whose place in the queue is `id`, returning
+    /// `true` once it holds the lock.
+    pub fn poll_lock(&mut self, id: &mut Option<usize>) -> bool {
+        match *id {
+            None => {
+                if self.try_lock() {
+                    return true
+                }
+                *id = Some(self.waiters.push(()));
+                false
+            }
+            Some(waiter) => {
+                if self.waiters.poll(waiter).is_none() {
+                    return false
+                }
+                *id = None;
+                true
+            }
+        }
+    }
+
+    /// Releases the lock, returning the task of the next waiter if the lock
+    /// was handed straight to it.
+    pub fn unlock(&mut self) -> Option<Task> {
+        debug_assert!(self.locked);
+
+        // Hand the lock straight to the next waiter, if any, so that no one
+        // can take it in between.
+        if self.waiters.is_empty() {
+            self.locked = false;
+            return None
+        }
+        Some(self.waiters.grant(0))
+    }
+
+    /// Removes the waiter `id`, which is no longer interested in the lock.
+    pub fn cancel(&mut self, id: usize) -> Option<Task> {
+        // We were handed the lock but are no longer interested in it, so
+        // pass it on to the next waiter.
+        if self.waiters.remove(id).is_some() {
+            self.unlock()
+        } else {
+            None
+        }
+    }
+}
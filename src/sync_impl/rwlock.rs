@@ -0,0 +1,142 @@
+//! The bookkeeping behind `sync::RwLock` and `unsync::RwLock`.
+
+use std::prelude::v1::*;
+
+use sync_impl::wait_queue::WaitQueue;
+use task::Task;
+
+/// Who holds a reader-writer lock, and who's waiting for it.
+///
+/// The handles wrap this in a `std::sync::Mutex` or a `RefCell`, and wake up
+/// the tasks returned here once they've let go of it.
+#[derive(Debug)]
+pub struct State {
+    readers: usize,
+    writer: bool,
+    prefer_writers: bool,
+    // Tasks waiting for the lock, along with whether they want to write.
+    waiters: WaitQueue<bool>,
+}
+
+impl State {
+    pub fn new(prefer_writers: bool) -> State {
+        State {
+            readers: 0,
+            writer: false,
+            prefer_writers: prefer_writers,
+            waiters: WaitQueue::new(),
+        }
+    }
+
+    /// Acquires the lock for reading or writing if that's possible right
+    /// away.
+    pub fn try_acquire(&mut self, write: bool) -> bool {
+        if self.can_acquire(write) {
+            self.acquire(write);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Polls an acquisition whose place in the queue is `id`, returning
+    /// `true` once it holds the lock.
+    pub fn poll_acquire(&mut self, write: bool, id: &mut Option<usize>) -> bool {
+        match *id {
+            None => {
+                if self.try_acquire(write) {
+                    return true
+                }
+                *id = Some(self.waiters.push(write));
+                false
+            }
+            Some(waiter) => {
+                if self.waiters.poll(waiter).is_none() {
+                    return false
+                }
+                *id = None;
+                true
+            }
+        }
+    }
+
+    /// Releases the lock, returning the tasks of the waiters it was handed
+    /// over to.
+    pub fn release(&mut self, write: bool) -> Vec<Task> {
+        if write {
+            self.writer = false;
+        } else {
+            self.readers -= 1;
+        }
+        self.grant()
+    }
+
+    /// Removes the waiter `id`, which is no longer interested in the lock.
+    pub fn cancel(&mut self, write: bool, id: usize) -> Vec<Task> {
+        // If we were handed the lock but are no longer interested in it,
+        // pass it on.
+        if self.waiters.remove(id).is_some() {
+            return self.release(write)
+        }
+        // Our departure from the queue may let others in as well.
+        self.grant()
+    }
+
+    fn can_acquire(&self, write: bool) -> bool {
+        if self.writer {
+            return false
+        }
+        if write {
+            self.readers == 0 && self.waiters.is_empty()
+        } else if self.prefer_writers {
+            self.waiters.position(|&write| write).is_none()
+        } else {
+            self.waiters.is_empty()
+        }
+    }
+
+    fn acquire(&mut self, write: bool) {
+        if write {
+            self.writer = true;
+        } else {
+            self.readers += 1;
+        }
+    }
+
+    // Hands the lock over to as many waiters as possible, returning the
+    // tasks which need to be woken up.
+    fn grant(&mut self) -> Vec<Task> {
+        let mut tasks = Vec::new();
+        if self.writer {
+            return tasks
+        }
+
+        let first_writer = if self.prefer_writers {
+            self.waiters.position(|&write| write)
+        } else {
+            None
+        };
+        if let Some(i) = first_writer {
+            if self.readers == 0 {
+                tasks.push(self.grant_to(i, true));
+            }
+            return tasks
+        }
+
+        while let Some(write) = self.waiters.front().cloned() {
+            if write && self.readers > 0 {
+                break
+            }
+            tasks.push(self.grant_to(0, write));
+            if write {
+                break
+            }
+        }
+        tasks
+    }
+
+    fn grant_to(&mut self, index: usize, write: bool) -> Task {
+        self.acquire(write);
+        self.waiters.grant(index)
+    }
+}
@@ -0,0 +1,121 @@
+//! A queue of tasks waiting their turn for a lock, permits or a
+//! notification.
+
+use std::collections::VecDeque;
+use std::prelude::v1::*;
+
+use task::{self, Task};
+
+/// Tasks waiting for a resource, in the order they started waiting.
+///
+/// Every waiter is identified by the id handed out by `push`, and carries
+/// some data describing what it's waiting for. Granting the resource to a
+/// waiter takes it out of the queue, after which it finds out about the
+/// grant the next time it's polled, or hands the resource back if it's
+/// removed before that.
+#[derive(Debug)]
+pub struct WaitQueue<T> {
+    waiters: VecDeque<Waiter<T>>,
+    // Waiters which have been granted the resource but haven't been polled
+    // since.
+    granted: Vec<(usize, T)>,
+    next_id: usize,
+}
+
+#[derive(Debug)]
+struct Waiter<T> {
+    id: usize,
+    data: T,
+    task: Task,
+}
+
+impl<T> WaitQueue<T> {
+    pub fn new() -> WaitQueue<T> {
+        WaitQueue {
+            waiters: VecDeque::new(),
+            granted: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Returns `true` if nobody is waiting.
+    pub fn is_empty(&self) -> bool {
+        self.waiters.is_empty()
+    }
+
+    /// Returns the data of the waiter which has been waiting the longest.
+    pub fn front(&self) -> Option<&T> {
+        self.waiters.front().map(|w| &w.data)
+    }
+
+    /// Like `front`, but allows the data to be changed.
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.waiters.front_mut().map(|w| &mut w.data)
+    }
+
+    /// Returns the index of the first waiter whose data matches `f`.
+    pub fn position<F>(&self, mut f: F) -> Option<usize>
+        where F: FnMut(&T) -> bool,
+    {
+        self.waiters.iter().position(|w| f(&w.data))
+    }
+
+    /// Adds the current task to the back of the queue, returning the id of
+    /// the new waiter.
+    pub fn push(&mut self, data: T) -> usize {
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        self.waiters.push_back(Waiter {
+            id: id,
+            data: data,
+            task: task::park(),
+        });
+        id
+    }
+
+    /// Grants the resource to the waiter at `index`, returning its task,
+    /// which needs to be woken up.
+    pub fn grant(&mut self, index: usize) -> Task {
+        let waiter = self.waiters.remove(index).unwrap();
+        self.granted.push((waiter.id, waiter.data));
+        waiter.task
+    }
+
+    /// Grants the resource to every waiter, returning the tasks which need
+    /// to be woken up.
+    pub fn grant_all(&mut self) -> Vec<Task> {
+        let mut tasks = Vec::with_capacity(self.waiters.len());
+        while !self.waiters.is_empty() {
+            tasks.push(self.grant(0));
+        }
+        tasks
+    }
+
+    /// Checks on the waiter `id` when it's polled again, returning its data
+    /// if it has been granted the resource in the meantime.
+    pub fn poll(&mut self, id: usize) -> Option<T> {
+        if let Some(i) = self.granted.iter().position(|g| g.0 == id) {
+            return Some(self.granted.swap_remove(i).1)
+        }
+        // Update the task in case we've been moved to another task since we
+        // started waiting.
+        for waiter in self.waiters.iter_mut() {
+            if waiter.id == id {
+                waiter.task = task::park();
+            }
+        }
+        None
+    }
+
+    /// Removes the waiter `id`, which is no longer interested.
+    ///
+    /// If it had already been granted the resource its data is returned, and
+    /// the caller is responsible for passing the resource on.
+    pub fn remove(&mut self, id: usize) -> Option<T> {
+        if let Some(i) = self.granted.iter().position(|g| g.0 == id) {
+            return Some(self.granted.swap_remove(i).1)
+        }
+        self.waiters.retain(|w| w.id != id);
+        None
+    }
+}
@@ -432,6 +432,16 @@ impl<S: Sink> Spawn<S> {
         self.enter(unpark, |sink| sink.poll_complete())
     }
 
+    /// Invokes the underlying `close` method with this task in place.
+    ///
+    /// If the underlying operation returns `NotReady` then the `unpark` value
+    /// passed in will receive a notification when the operation is ready to be
+    /// attempted again.
+    pub fn poll_close(&mut self, unpark: &Arc<Unpark>)
+                      -> Poll<(), S::SinkError> {
+        self.enter(unpark, |sink| sink.close())
+    }
+
     /// Blocks the current thread until it's able to send `value` on this sink.
     ///
     /// This function will send the `value` on the sink that this task wraps. If
@@ -468,6 +478,24 @@ impl<S: Sink> Spawn<S> {
             unpark.park();
         }
     }
+
+    /// Blocks the current thread until it's able to close this sink.
+    ///
+    /// This function will call the underlying sink's `close` method until it
+    /// returns that it's ready, proxying out errors upwards to the caller if
+    /// one occurs.
+    ///
+    /// The thread will be blocked until `close` returns that it's ready.
+    pub fn wait_close(&mut self) -> Result<(), S::SinkError> {
+        let unpark = Arc::new(ThreadUnpark::new(thread::current()));
+        let unpark2 = unpark.clone() as Arc<Unpark>;
+        loop {
+            if try!(self.poll_close(&unpark2)).is_ready() {
+                return Ok(())
+            }
+            unpark.park();
+        }
+    }
 }
 
 impl<T> Spawn<T> {
@@ -0,0 +1,74 @@
+//! Timers for time-based combinators
+//!
+//! This crate does not provide a source of time itself, as that is typically
+//! the job of an event loop. Instead, the combinators which need to wait for
+//! some amount of time to pass, such as `Stream::debounce`, are generic over
+//! the `Timer` trait in this module. Event loops and timer libraries can
+//! implement this trait to plug into those combinators.
+
+use std::boxed::Box;
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use Future;
+
+/// A source of futures which complete after a given amount of time.
+///
+/// Each call to `sleep` creates a brand new future, which should resolve once
+/// the provided duration has elapsed. Combinators which need to "reset" a
+/// timeout will simply drop the previous future and request a new one, so
+/// creating and dropping sleep futures is expected to be relatively cheap.
+///
+/// Like all other futures, the returned future must arrange for the current
+/// task to be notified when it becomes ready.
+pub trait Timer {
+    /// The future returned by `sleep`, which resolves once the requested
+    /// amount of time has passed.
+    type Sleep: Future<Item = ()>;
+
+    /// Creates a future which will resolve after `duration` has elapsed.
+    fn sleep(&self, duration: Duration) -> Self::Sleep;
+}
+
+impl<'a, T: ?Sized + Timer> Timer for &'a T {
+    type Sleep = T::Sleep;
+
+    fn sleep(&self, duration: Duration) -> T::Sleep {
+        (**self).sleep(duration)
+    }
+}
+
+impl<T: ?Sized + Timer> Timer for Box<T> {
+    type Sleep = T::Sleep;
+
+    fn sleep(&self, duration: Duration) -> T::Sleep {
+        (**self).sleep(duration)
+    }
+}
+
+impl<T: ?Sized + Timer> Timer for Arc<T> {
+    type Sleep = T::Sleep;
+
+    fn sleep(&self, duration: Duration) -> T::Sleep {
+        (**self).sleep(duration)
+    }
+}
+
+/// Error returned by time-based combinators, such as `Stream::timeout`, when
+/// a deadline has passed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Elapsed;
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "deadline has elapsed")
+    }
+}
+
+impl Error for Elapsed {
+    fn description(&self) -> &str {
+        "deadline has elapsed"
+    }
+}
@@ -0,0 +1,172 @@
+//! A multi-producer, multi-consumer broadcast channel, for use between tasks
+//! on the same thread.
+//!
+//! This channel is the same as the one in `futures::sync::broadcast`, except
+//! it's not intended to be sent across threads, so it doesn't need any
+//! locking. That makes it a good fit for distributing events within a
+//! single-threaded program, such as a GUI or game loop.
+//!
+//! Every message sent on a broadcast channel is delivered to every
+//! `Receiver` subscribed to it. This is useful for messages which many parts
+//! of a program are interested in, such as configuration updates or shutdown
+//! signals.
+//!
+//! The channel keeps the last `capacity` messages in a ring buffer, and
+//! sending never waits for receivers to catch up. Instead, a receiver which
+//! falls so far behind that messages it hasn't seen yet are pushed out of the
+//! buffer yields a `RecvError::Lagged` error with the number of messages it
+//! missed, after which it carries on with the oldest message still buffered.
+//!
+//! The stream of messages ends once every `Sender` has been dropped and all
+//! messages still buffered have been received.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use {Async, AsyncSink, Poll, StartSend, Sink, Stream};
+use sync_impl::broadcast::State;
+
+pub use sync::broadcast::{SendError, RecvError};
+
+/// The sending half of a broadcast channel.
+///
+/// This is created by the `channel` function, and can be cloned to send
+/// from several places.
+#[derive(Debug)]
+pub struct Sender<T> {
+    inner: Rc<RefCell<State<T>>>,
+}
+
+/// The receiving half of a broadcast channel, which implements `Stream`.
+///
+/// This is created by the `channel` function or `Sender::subscribe`. Cloning
+/// a receiver creates a new one which starts out at the same position.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Receiver<T> {
+    // Sequence number of the next message this receiver will yield.
+    pos: u64,
+    id: usize,
+    inner: Rc<RefCell<State<T>>>,
+}
+
+/// Creates a new broadcast channel which buffers up to `capacity` messages.
+///
+/// # Panics
+///
+/// This function will panic if `capacity` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use futures::Stream;
+/// use futures::unsync::broadcast::{self, RecvError};
+///
+/// let (tx, rx) = broadcast::channel(2);
+/// let rx2 = tx.subscribe();
+/// for i in 0..3 {
+///     tx.broadcast(i).unwrap();
+/// }
+/// drop(tx);
+///
+/// let mut rx = rx.wait();
+/// assert_eq!(rx.next(), Some(Err(RecvError::Lagged(1))));
+/// assert_eq!(rx.next(), Some(Ok(1)));
+/// assert_eq!(rx.next(), Some(Ok(2)));
+/// assert_eq!(rx.next(), None);
+///
+/// let rest = rx2.wait().filter_map(Result::ok).collect::<Vec<_>>();
+/// assert_eq!(rest, vec![1, 2]);
+/// ```
+pub fn channel<T: Clone>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    let inner = Rc::new(RefCell::new(State::new(capacity)));
+    let tx = Sender { inner: inner.clone() };
+    let rx = Receiver { pos: 0, id: 0, inner: inner };
+    (tx, rx)
+}
+
+impl<T> Sender<T> {
+    /// Sends a message to every receiver currently subscribed.
+    ///
+    /// Unlike `Sink::send`, this sends the message right away rather than
+    /// through a future, as sending never needs to wait: if the buffer is
+    /// full, the oldest message is pushed out of it, and receivers which
+    /// haven't seen it yet will be told that they lagged behind. An error is
+    /// returned with the message if there are no receivers left.
+    pub fn broadcast(&self, msg: T) -> Result<(), SendError<T>> {
+        self.inner.borrow_mut().broadcast(msg)
+    }
+
+    /// Creates a new `Receiver` which will see every message sent after this
+    /// call.
+    pub fn subscribe(&self) -> Receiver<T> {
+        let mut inner = self.inner.borrow_mut();
+        Receiver {
+            id: inner.add_receiver(),
+            pos: inner.end(),
+            inner: self.inner.clone(),
+        }
+    }
+
+    /// Returns the number of receivers currently subscribed.
+    pub fn receiver_count(&self) -> usize {
+        self.inner.borrow_mut().receivers()
+    }
+}
+
+impl<T> Sink for Sender<T> {
+    type SinkItem = T;
+    type SinkError = SendError<T>;
+
+    fn start_send(&mut self, msg: T) -> StartSend<T, SendError<T>> {
+        try!(self.broadcast(msg));
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), SendError<T>> {
+        Ok(Async::Ready(()))
+    }
+
+    fn close(&mut self) -> Poll<(), SendError<T>> {
+        Ok(Async::Ready(()))
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Sender<T> {
+        self.inner.borrow_mut().add_sender();
+        Sender { inner: self.inner.clone() }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.inner.borrow_mut().remove_sender();
+    }
+}
+
+impl<T: Clone> Stream for Receiver<T> {
+    type Item = T;
+    type Error = RecvError;
+
+    fn poll(&mut self) -> Poll<Option<T>, RecvError> {
+        self.inner.borrow_mut().poll(self.id, &mut self.pos)
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Receiver<T> {
+        let id = self.inner.borrow_mut().add_receiver();
+        Receiver {
+            pos: self.pos,
+            id: id,
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.inner.borrow_mut().remove_receiver(self.id);
+    }
+}
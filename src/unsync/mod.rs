@@ -3,5 +3,15 @@
 //! This module contains similar abstractions to `sync`, for communications
 //! between tasks on the same thread only.
 
+pub mod broadcast;
 pub mod mpsc;
 pub mod oneshot;
+mod mutex;
+mod rwlock;
+mod shared;
+mod task_set;
+
+pub use self::mutex::{Mutex, MutexAcquire, MutexGuard};
+pub use self::rwlock::{RwLock, RwLockRead, RwLockWrite, RwLockReadGuard, RwLockWriteGuard};
+pub use self::shared::{shared, Shared, SharedItem, SharedError};
+pub use self::task_set::TaskSet;
@@ -251,8 +251,10 @@ impl<T> UnboundedSender<T> {
     ///
     /// This is an unbounded sender, so this function differs from `Sink::send`
     /// by ensuring the return type reflects that the channel is always ready to
-    /// receive messages.
-    pub fn send(&self, msg: T) -> Result<(), SendError<T>> {
+    /// receive messages. The only way for this to fail is if the receiving
+    /// half has gone away, in which case the message is handed back in the
+    /// error.
+    pub fn unbounded_send(&self, msg: T) -> Result<(), SendError<T>> {
         let shared = match self.0.shared.upgrade() {
             Some(shared) => shared,
             None => return Err(SendError(msg)),
@@ -265,6 +267,13 @@ impl<T> UnboundedSender<T> {
         }
         Ok(())
     }
+
+    #[deprecated(note = "renamed to `unbounded_send`", since = "0.1.14")]
+    #[doc(hidden)]
+    #[cfg(feature = "with-deprecated")]
+    pub fn send(&self, msg: T) -> Result<(), SendError<T>> {
+        self.unbounded_send(msg)
+    }
 }
 
 /// The receiving end of an unbounded channel.
@@ -116,6 +116,20 @@ impl<T> Sender<T> {
             None => Ok(().into()),
         }
     }
+
+    /// Tests to see whether this `Sender`'s corresponding `Receiver`
+    /// has gone away.
+    ///
+    /// This function can be used to learn about when the `Receiver` (consumer)
+    /// half has gone away and nothing will be able to receive a message sent
+    /// from `send`, so that any work to produce the message can be abandoned.
+    ///
+    /// Unlike `poll_cancel`, this function does not register interest in the
+    /// `Receiver` going away, so it may be called outside the context of a
+    /// task.
+    pub fn is_canceled(&self) -> bool {
+        self.inner.upgrade().is_none()
+    }
 }
 
 impl<T> Drop for Sender<T> {
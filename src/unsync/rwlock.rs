@@ -0,0 +1,269 @@
+use std::cell::{RefCell, UnsafeCell};
+use std::ops::{Deref, DerefMut};
+use std::prelude::v1::*;
+use std::rc::Rc;
+
+use {Async, Future, Poll};
+use sync_impl::rwlock::State;
+
+/// A futures-aware reader-writer lock, for use between tasks on the same
+/// thread.
+///
+/// This is the same as `futures::sync::RwLock`, except it's not intended to
+/// be sent across threads, so its bookkeeping uses a `RefCell` rather than
+/// a lock.
+///
+/// This lock allows any number of readers or a single writer to access the
+/// protected data at a time. Like `Mutex`, acquiring it never blocks the
+/// current thread: `read` and `write` return futures which resolve to guards,
+/// parking the current task until the lock is available. When a guard is
+/// dropped, the tasks which can now acquire the lock are woken up.
+///
+/// By default, the lock is handed out in the order in which tasks started
+/// waiting for it, with consecutive readers sharing it. A lock created with
+/// `with_writer_priority` instead lets waiting writers go before any waiting
+/// readers, and stops new readers from joining active ones while a writer is
+/// waiting, which suits data that's read often but must be updated promptly.
+///
+/// A `RwLock` is a handle to the protected data, and cloning it creates a new
+/// handle to the same data.
+///
+/// # Examples
+///
+/// ```
+/// use futures::Future;
+/// use futures::unsync::RwLock;
+///
+/// let lock = RwLock::new(1);
+/// {
+///     let a = lock.read().wait().unwrap();
+///     let b = lock.read().wait().unwrap();
+///     assert_eq!(*a + *b, 2);
+///     assert!(lock.try_write().is_none());
+/// }
+/// *lock.write().wait().unwrap() += 1;
+/// assert_eq!(*lock.try_read().unwrap(), 2);
+/// ```
+#[derive(Debug)]
+pub struct RwLock<T> {
+    inner: Rc<Inner<T>>,
+}
+
+#[derive(Debug)]
+struct Inner<T> {
+    state: RefCell<State>,
+    data: UnsafeCell<T>,
+}
+
+impl<T> RwLock<T> {
+    /// Creates a new reader-writer lock protecting the provided data, which
+    /// hands out the lock in the order in which tasks asked for it.
+    pub fn new(t: T) -> RwLock<T> {
+        RwLock::with_priority(t, false)
+    }
+
+    /// Creates a new reader-writer lock protecting the provided data, which
+    /// hands out the lock to waiting writers before any waiting readers.
+    ///
+    /// Note that readers may be starved if there's always a writer waiting.
+    pub fn with_writer_priority(t: T) -> RwLock<T> {
+        RwLock::with_priority(t, true)
+    }
+
+    fn with_priority(t: T, prefer_writers: bool) -> RwLock<T> {
+        RwLock {
+            inner: Rc::new(Inner {
+                state: RefCell::new(State::new(prefer_writers)),
+                data: UnsafeCell::new(t),
+            }),
+        }
+    }
+
+    /// Returns a future which resolves to a guard giving shared access to
+    /// the protected data once the lock has been acquired for reading.
+    ///
+    /// Note that the returned future will never resolve to an error.
+    pub fn read(&self) -> RwLockRead<T> {
+        RwLockRead { inner: Acquire::new(self, false) }
+    }
+
+    /// Returns a future which resolves to a guard giving exclusive access to
+    /// the protected data once the lock has been acquired for writing.
+    ///
+    /// Note that the returned future will never resolve to an error.
+    pub fn write(&self) -> RwLockWrite<T> {
+        RwLockWrite { inner: Acquire::new(self, true) }
+    }
+
+    /// Attempts to acquire this lock for reading without waiting.
+    ///
+    /// Returns `None` if the lock can't be acquired right away. This function
+    /// doesn't need to be called from within the context of a task.
+    pub fn try_read(&self) -> Option<RwLockReadGuard<T>> {
+        if self.try_acquire(false) {
+            Some(RwLockReadGuard { lock: self.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Attempts to acquire this lock for writing without waiting.
+    ///
+    /// Returns `None` if the lock can't be acquired right away. This function
+    /// doesn't need to be called from within the context of a task.
+    pub fn try_write(&self) -> Option<RwLockWriteGuard<T>> {
+        if self.try_acquire(true) {
+            Some(RwLockWriteGuard { lock: self.clone() })
+        } else {
+            None
+        }
+    }
+
+    fn try_acquire(&self, write: bool) -> bool {
+        self.inner.state.borrow_mut().try_acquire(write)
+    }
+
+    fn release(&self, write: bool) {
+        let tasks = self.inner.state.borrow_mut().release(write);
+        for task in tasks {
+            task.unpark();
+        }
+    }
+}
+
+impl<T> Clone for RwLock<T> {
+    fn clone(&self) -> RwLock<T> {
+        RwLock { inner: self.inner.clone() }
+    }
+}
+
+#[derive(Debug)]
+struct Acquire<T> {
+    lock: Option<RwLock<T>>,
+    write: bool,
+    // Set once we're in the queue of waiters.
+    id: Option<usize>,
+}
+
+impl<T> Acquire<T> {
+    fn new(lock: &RwLock<T>, write: bool) -> Acquire<T> {
+        Acquire {
+            lock: Some(lock.clone()),
+            write: write,
+            id: None,
+        }
+    }
+
+    fn poll(&mut self) -> Async<RwLock<T>> {
+        {
+            let lock = self.lock.as_ref().expect("cannot poll a lock future twice");
+            let mut state = lock.inner.state.borrow_mut();
+            if !state.poll_acquire(self.write, &mut self.id) {
+                return Async::NotReady
+            }
+        }
+        Async::Ready(self.lock.take().unwrap())
+    }
+}
+
+impl<T> Drop for Acquire<T> {
+    fn drop(&mut self) {
+        let (lock, id) = match (self.lock.take(), self.id) {
+            (Some(lock), Some(id)) => (lock, id),
+            _ => return,
+        };
+
+        let tasks = lock.inner.state.borrow_mut().cancel(self.write, id);
+        for task in tasks {
+            task.unpark();
+        }
+    }
+}
+
+/// Future returned by `RwLock::read` which will resolve when the lock is
+/// acquired for reading.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct RwLockRead<T> {
+    inner: Acquire<T>,
+}
+
+impl<T> Future for RwLockRead<T> {
+    type Item = RwLockReadGuard<T>;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<RwLockReadGuard<T>, ()> {
+        match self.inner.poll() {
+            Async::Ready(lock) => Ok(Async::Ready(RwLockReadGuard { lock: lock })),
+            Async::NotReady => Ok(Async::NotReady),
+        }
+    }
+}
+
+/// Future returned by `RwLock::write` which will resolve when the lock is
+/// acquired for writing.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct RwLockWrite<T> {
+    inner: Acquire<T>,
+}
+
+impl<T> Future for RwLockWrite<T> {
+    type Item = RwLockWriteGuard<T>;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<RwLockWriteGuard<T>, ()> {
+        match self.inner.poll() {
+            Async::Ready(lock) => Ok(Async::Ready(RwLockWriteGuard { lock: lock })),
+            Async::NotReady => Ok(Async::NotReady),
+        }
+    }
+}
+
+/// RAII guard giving shared access to the data protected by a `RwLock`.
+///
+/// The lock is released when this guard is dropped.
+#[derive(Debug)]
+pub struct RwLockReadGuard<T> {
+    lock: RwLock<T>,
+}
+
+impl<T> Deref for RwLockReadGuard<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.lock.inner.data.get() }
+    }
+}
+
+impl<T> Drop for RwLockReadGuard<T> {
+    fn drop(&mut self) {
+        self.lock.release(false);
+    }
+}
+
+/// RAII guard giving exclusive access to the data protected by a `RwLock`.
+///
+/// The lock is released when this guard is dropped.
+#[derive(Debug)]
+pub struct RwLockWriteGuard<T> {
+    lock: RwLock<T>,
+}
+
+impl<T> Deref for RwLockWriteGuard<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.lock.inner.data.get() }
+    }
+}
+
+impl<T> DerefMut for RwLockWriteGuard<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.lock.inner.data.get() }
+    }
+}
+
+impl<T> Drop for RwLockWriteGuard<T> {
+    fn drop(&mut self) {
+        self.lock.release(true);
+    }
+}
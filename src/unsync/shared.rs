@@ -0,0 +1,200 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+use std::mem;
+use std::ops;
+use std::rc::Rc;
+
+use {Future, Poll, Async};
+use task::{self, Task};
+
+/// A future that is cloneable and can be polled by several tasks on the same
+/// thread.
+///
+/// This is the same as `futures::future::Shared`, except it's not intended
+/// to be sent across threads, so it doesn't need any atomic operations. It's
+/// created with the `shared` function.
+#[must_use = "futures do nothing unless polled"]
+pub struct Shared<F: Future> {
+    inner: Rc<Inner<F>>,
+    waiter: usize,
+}
+
+struct Inner<F: Future> {
+    next_clone_id: Cell<usize>,
+    future: RefCell<Option<F>>,
+    result: RefCell<Option<Result<SharedItem<F::Item>, SharedError<F::Error>>>>,
+    waiters: RefCell<HashMap<usize, Task>>,
+    // The clone which last polled the future, and hence whose task will be
+    // notified when it can make progress.
+    driver: Cell<Option<usize>>,
+}
+
+/// Creates a future which can be cloned, and whose result is shared between
+/// all of its clones.
+///
+/// The result is wrapped in a `SharedItem` or `SharedError`, which are
+/// reference counted and dereference to the original value.
+///
+/// # Examples
+///
+/// ```
+/// use futures::Future;
+/// use futures::future;
+/// use futures::unsync;
+///
+/// let shared1 = unsync::shared(future::ok::<_, bool>(6));
+/// let shared2 = shared1.clone();
+/// assert_eq!(6, *shared1.wait().unwrap());
+/// assert_eq!(6, *shared2.wait().unwrap());
+/// ```
+pub fn shared<F: Future>(future: F) -> Shared<F> {
+    Shared {
+        inner: Rc::new(Inner {
+            next_clone_id: Cell::new(1),
+            future: RefCell::new(Some(future)),
+            result: RefCell::new(None),
+            waiters: RefCell::new(HashMap::new()),
+            driver: Cell::new(None),
+        }),
+        waiter: 0,
+    }
+}
+
+impl<F> Shared<F> where F: Future {
+    /// If any clone of this `Shared` has completed execution, returns its result immediately
+    /// without blocking. Otherwise, returns None without triggering the work represented by
+    /// this `Shared`.
+    pub fn peek(&self) -> Option<Result<SharedItem<F::Item>, SharedError<F::Error>>> {
+        match *self.inner.result.borrow() {
+            Some(Ok(ref item)) => Some(Ok(SharedItem { item: item.item.clone() })),
+            Some(Err(ref e)) => Some(Err(SharedError { error: e.error.clone() })),
+            None => None,
+        }
+    }
+
+    fn unpark_waiters(&self) {
+        let waiters = mem::replace(&mut *self.inner.waiters.borrow_mut(), HashMap::new());
+        for (_, waiter) in waiters {
+            waiter.unpark();
+        }
+    }
+}
+
+impl<F> Future for Shared<F>
+    where F: Future
+{
+    type Item = SharedItem<F::Item>;
+    type Error = SharedError<F::Error>;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        if let Some(result) = self.peek() {
+            return result.map(Async::Ready)
+        }
+
+        // The future is taken out while it's being polled, so that it can
+        // poll a clone of this `Shared` itself. Such a recursive poll finds
+        // no future and just waits for the outer one to make progress.
+        let mut future = match self.inner.future.borrow_mut().take() {
+            Some(future) => future,
+            None => {
+                self.inner.waiters.borrow_mut().insert(self.waiter, task::park());
+                return Ok(Async::NotReady)
+            }
+        };
+        let result = match future.poll() {
+            Ok(Async::NotReady) => {
+                *self.inner.future.borrow_mut() = Some(future);
+                self.inner.driver.set(Some(self.waiter));
+                self.inner.waiters.borrow_mut().insert(self.waiter, task::park());
+                return Ok(Async::NotReady)
+            }
+            Ok(Async::Ready(i)) => Ok(SharedItem { item: Rc::new(i) }),
+            Err(e) => Err(SharedError { error: Rc::new(e) }),
+        };
+        drop(future);
+
+        *self.inner.result.borrow_mut() = Some(result);
+        self.unpark_waiters();
+        self.peek().unwrap().map(Async::Ready)
+    }
+}
+
+impl<F> Clone for Shared<F> where F: Future {
+    fn clone(&self) -> Self {
+        let next_clone_id = self.inner.next_clone_id.get();
+        self.inner.next_clone_id.set(next_clone_id + 1);
+
+        Shared {
+            inner: self.inner.clone(),
+            waiter: next_clone_id,
+        }
+    }
+}
+
+impl<F> Drop for Shared<F> where F: Future {
+    fn drop(&mut self) {
+        self.inner.waiters.borrow_mut().remove(&self.waiter);
+
+        // If we were the ones being notified of the future's progress, make
+        // sure somebody else takes over polling it.
+        if self.inner.driver.get() == Some(self.waiter) {
+            self.inner.driver.set(None);
+            self.unpark_waiters();
+        }
+    }
+}
+
+impl<F> fmt::Debug for Shared<F>
+    where F: Future + fmt::Debug,
+          F::Item: fmt::Debug,
+          F::Error: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Shared")
+            .field("waiter", &self.waiter)
+            .finish()
+    }
+}
+
+/// A wrapped item of the original future that is clonable and implements Deref
+/// for ease of use.
+#[derive(Debug)]
+pub struct SharedItem<T> {
+    item: Rc<T>,
+}
+
+impl<T> Clone for SharedItem<T> {
+    fn clone(&self) -> SharedItem<T> {
+        SharedItem { item: self.item.clone() }
+    }
+}
+
+impl<T> ops::Deref for SharedItem<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.item
+    }
+}
+
+/// A wrapped error of the original future that is clonable and implements Deref
+/// for ease of use.
+#[derive(Debug)]
+pub struct SharedError<E> {
+    error: Rc<E>,
+}
+
+impl<E> Clone for SharedError<E> {
+    fn clone(&self) -> SharedError<E> {
+        SharedError { error: self.error.clone() }
+    }
+}
+
+impl<E> ops::Deref for SharedError<E> {
+    type Target = E;
+
+    fn deref(&self) -> &E {
+        &self.error
+    }
+}
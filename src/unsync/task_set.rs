@@ -0,0 +1,95 @@
+use std::fmt;
+use std::prelude::v1::*;
+
+use {Async, Future, Poll, Stream};
+use stream::FuturesUnordered;
+
+/// A group of futures which are run together as a single unit, for use on a
+/// single thread.
+///
+/// Futures added to the set with `spawn` don't need to be `Send`. The set is
+/// itself a future, which drives all of its futures and resolves once every
+/// one of them has completed, so the group as a whole can be spawned onto an
+/// executor or composed with other futures. Each future is only polled when
+/// it has been notified, rather than every time the set is.
+///
+/// A future which resolves to an error simply leaves the set, like one which
+/// completes successfully. When the set is dropped, every future it still
+/// contains is dropped too, canceling the whole group so that none of them
+/// can outlive it.
+///
+/// # Examples
+///
+/// ```
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+/// use futures::Future;
+/// use futures::future;
+/// use futures::unsync::TaskSet;
+///
+/// let count = Rc::new(Cell::new(0));
+/// let mut set = TaskSet::new();
+/// for _ in 0..3 {
+///     let count = count.clone();
+///     set.spawn(future::lazy(move || {
+///         count.set(count.get() + 1);
+///         Ok(())
+///     }));
+/// }
+///
+/// set.wait().unwrap();
+/// assert_eq!(count.get(), 3);
+/// ```
+#[must_use = "futures do nothing unless polled"]
+pub struct TaskSet {
+    tasks: FuturesUnordered<Box<Future<Item = (), Error = ()>>>,
+}
+
+impl TaskSet {
+    /// Creates a new, empty set.
+    pub fn new() -> TaskSet {
+        TaskSet { tasks: FuturesUnordered::new() }
+    }
+
+    /// Adds a future to the set.
+    ///
+    /// The future isn't polled right away, but the next time the set is.
+    pub fn spawn<F>(&mut self, future: F)
+        where F: Future<Item = (), Error = ()> + 'static,
+    {
+        self.tasks.push(Box::new(future));
+    }
+
+    /// Returns the number of futures in the set which haven't completed yet.
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Returns `true` if every future in the set has completed.
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+}
+
+impl Future for TaskSet {
+    type Item = ();
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<(), ()> {
+        loop {
+            match self.tasks.poll() {
+                Ok(Async::Ready(Some(()))) | Err(()) => {}
+                Ok(Async::Ready(None)) => return Ok(Async::Ready(())),
+                Ok(Async::NotReady) => return Ok(Async::NotReady),
+            }
+        }
+    }
+}
+
+impl fmt::Debug for TaskSet {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("TaskSet")
+            .field("len", &self.len())
+            .finish()
+    }
+}
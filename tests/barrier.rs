@@ -0,0 +1,72 @@
+extern crate futures;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use futures::{Async, Future};
+use futures::executor::{self, Spawn};
+use futures::sync::{Barrier, BarrierWait};
+
+mod support;
+use support::*;
+
+fn poll(f: &mut Spawn<BarrierWait>) -> Option<bool> {
+    match f.poll_future(unpark_noop()) {
+        Ok(Async::Ready(result)) => Some(result.is_leader()),
+        Ok(Async::NotReady) => None,
+        Err(()) => panic!("barrier failed"),
+    }
+}
+
+#[test]
+fn releases_all_at_once() {
+    let barrier = Barrier::new(3);
+    let mut a = executor::spawn(barrier.wait());
+    let mut b = executor::spawn(barrier.wait());
+    assert_eq!(poll(&mut a), None);
+    assert_eq!(poll(&mut b), None);
+    assert_eq!(poll(&mut a), None);
+
+    // The last one to arrive is the leader
+    assert_eq!(barrier.wait().wait().map(|r| r.is_leader()), Ok(true));
+    assert_eq!(poll(&mut a), Some(false));
+    assert_eq!(poll(&mut b), Some(false));
+
+    // The barrier can be reused
+    let mut c = executor::spawn(barrier.wait());
+    assert_eq!(poll(&mut c), None);
+}
+
+#[test]
+fn dropped_waiter() {
+    let barrier = Barrier::new(2);
+    let mut a = executor::spawn(barrier.wait());
+    assert_eq!(poll(&mut a), None);
+    drop(a);
+
+    let mut b = executor::spawn(barrier.wait());
+    assert_eq!(poll(&mut b), None);
+    assert_eq!(barrier.wait().wait().map(|r| r.is_leader()), Ok(true));
+    assert_eq!(poll(&mut b), Some(false));
+}
+
+#[test]
+fn zero() {
+    let barrier = Barrier::new(0);
+    assert_eq!(barrier.wait().wait().map(|r| r.is_leader()), Ok(true));
+}
+
+#[test]
+fn repeated_polls_keep_one_task() {
+    let barrier = Barrier::new(2);
+    let mut a = executor::spawn(barrier.wait());
+
+    let live = Arc::new(AtomicUsize::new(0));
+    for _ in 0..10 {
+        assert!(a.poll_future(unpark_counted(&live)).unwrap().is_not_ready());
+    }
+    assert_eq!(live.load(Ordering::SeqCst), 1);
+
+    drop(a);
+    assert_eq!(live.load(Ordering::SeqCst), 0);
+}
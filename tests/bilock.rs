@@ -99,3 +99,18 @@ fn concurrent() {
         }
     }
 }
+
+#[test]
+fn reunite() {
+    let (a, b) = BiLock::new(String::from("hello"));
+    let (c, d) = BiLock::new(String::from("world"));
+
+    // Halves of different locks are handed back
+    let err = a.reunite(c).unwrap_err();
+    let (a, c) = (err.0, err.1);
+    assert_eq!(c.reunite(d).unwrap(), "world");
+
+    // A lock that has been acquired and released can be reunited
+    let b = b.lock().wait().unwrap().unlock();
+    assert_eq!(b.reunite(a).unwrap(), "hello");
+}
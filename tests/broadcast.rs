@@ -0,0 +1,76 @@
+extern crate futures;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use futures::{Future, Sink, Stream};
+use futures::executor;
+use futures::sync::broadcast::{self, RecvError};
+
+mod support;
+use support::*;
+
+#[test]
+fn every_receiver_sees_every_message() {
+    let (tx, rx) = broadcast::channel(16);
+    let rx2 = rx.clone();
+
+    let threads = vec![rx, rx2].into_iter().map(|rx| {
+        thread::spawn(move || rx.collect().wait())
+    }).collect::<Vec<_>>();
+
+    tx.send_all(futures::stream::iter_ok(0..10)).wait().unwrap();
+    for thread in threads {
+        assert_eq!(thread.join().unwrap(), Ok((0..10).collect()));
+    }
+}
+
+#[test]
+fn lagged() {
+    let (tx, rx) = broadcast::channel(2);
+    let mut rx = rx.wait();
+
+    tx.broadcast(1).unwrap();
+    assert_eq!(rx.next(), Some(Ok(1)));
+    for i in 2..6 {
+        tx.broadcast(i).unwrap();
+    }
+    assert_eq!(rx.next(), Some(Err(RecvError::Lagged(2))));
+    assert_eq!(rx.next(), Some(Ok(4)));
+
+    // New subscribers only see new messages
+    let mut rx2 = tx.subscribe().wait();
+    tx.broadcast(6).unwrap();
+    drop(tx);
+    assert_eq!(rx2.next(), Some(Ok(6)));
+    assert_eq!(rx2.next(), None);
+    assert_eq!(rx.next(), Some(Ok(5)));
+    assert_eq!(rx.next(), Some(Ok(6)));
+    assert_eq!(rx.next(), None);
+}
+
+#[test]
+fn no_receivers() {
+    let (tx, rx) = broadcast::channel(1);
+    assert_eq!(tx.receiver_count(), 1);
+    drop(rx);
+    assert_eq!(tx.broadcast(1).unwrap_err().into_inner(), 1);
+}
+
+#[test]
+fn repeated_polls_keep_one_task() {
+    let (tx, rx) = broadcast::channel::<i32>(1);
+    let mut rx2 = executor::spawn(tx.subscribe());
+    let mut rx = executor::spawn(rx);
+
+    let live = Arc::new(AtomicUsize::new(0));
+    for _ in 0..10 {
+        assert!(rx.poll_stream(unpark_counted(&live)).unwrap().is_not_ready());
+        assert!(rx2.poll_stream(unpark_counted(&live)).unwrap().is_not_ready());
+    }
+    assert_eq!(live.load(Ordering::SeqCst), 2);
+
+    drop(rx2);
+    assert_eq!(live.load(Ordering::SeqCst), 1);
+}
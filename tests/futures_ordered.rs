@@ -0,0 +1,64 @@
+extern crate futures;
+
+use futures::sync::oneshot;
+use futures::stream::{futures_ordered, FuturesOrdered};
+
+mod support;
+
+#[test]
+fn works_1() {
+    let (a_tx, a_rx) = oneshot::channel::<u32>();
+    let (b_tx, b_rx) = oneshot::channel::<u32>();
+    let (c_tx, c_rx) = oneshot::channel::<u32>();
+
+    let stream = futures_ordered(vec![a_rx, b_rx, c_rx]);
+
+    let mut spawn = futures::executor::spawn(stream);
+    b_tx.send(99).unwrap();
+    assert!(spawn.poll_stream(support::unpark_noop()).unwrap().is_not_ready());
+
+    a_tx.send(33).unwrap();
+    c_tx.send(33).unwrap();
+    assert_eq!(Some(Ok(33)), spawn.wait_stream());
+    assert_eq!(Some(Ok(99)), spawn.wait_stream());
+    assert_eq!(Some(Ok(33)), spawn.wait_stream());
+    assert_eq!(None, spawn.wait_stream());
+}
+
+#[test]
+fn errors_are_ordered() {
+    let (a_tx, a_rx) = oneshot::channel::<u32>();
+    let (b_tx, b_rx) = oneshot::channel::<u32>();
+
+    let mut stream = FuturesOrdered::new();
+    stream.push(a_rx);
+    stream.push(b_rx);
+    assert_eq!(stream.len(), 2);
+
+    let mut spawn = futures::executor::spawn(stream);
+    drop(b_tx);
+    assert!(spawn.poll_stream(support::unpark_noop()).unwrap().is_not_ready());
+
+    a_tx.send(1).unwrap();
+    assert_eq!(Some(Ok(1)), spawn.wait_stream());
+    assert_eq!(Some(Err(oneshot::Canceled)), spawn.wait_stream());
+    assert_eq!(None, spawn.wait_stream());
+}
+
+#[test]
+fn push_while_running() {
+    let (a_tx, a_rx) = oneshot::channel::<u32>();
+    let (b_tx, b_rx) = oneshot::channel::<u32>();
+
+    let mut spawn = futures::executor::spawn(FuturesOrdered::new());
+    spawn.get_mut().push(a_rx);
+    assert!(spawn.poll_stream(support::unpark_noop()).unwrap().is_not_ready());
+    spawn.get_mut().push(b_rx);
+
+    b_tx.send(3).unwrap();
+    assert!(spawn.poll_stream(support::unpark_noop()).unwrap().is_not_ready());
+    a_tx.send(2).unwrap();
+    assert_eq!(Some(Ok(2)), spawn.wait_stream());
+    assert_eq!(Some(Ok(3)), spawn.wait_stream());
+    assert_eq!(None, spawn.wait_stream());
+}
@@ -3,7 +3,7 @@ extern crate futures;
 use std::any::Any;
 
 use futures::sync::oneshot;
-use futures::stream::futures_unordered;
+use futures::stream::{futures_unordered, FuturesUnordered};
 use futures::Future;
 
 mod support;
@@ -66,3 +66,34 @@ fn finished_future_ok() {
     assert!(spawn.poll_stream(support::unpark_noop()).unwrap().is_not_ready());
     assert!(spawn.poll_stream(support::unpark_noop()).unwrap().is_not_ready());
 }
+
+#[test]
+fn push_while_running() {
+    let (a_tx, a_rx) = oneshot::channel::<u32>();
+    let (b_tx, b_rx) = oneshot::channel::<u32>();
+
+    let mut stream = FuturesUnordered::new();
+    assert!(stream.is_empty());
+    stream.push(a_rx);
+    assert_eq!(stream.len(), 1);
+
+    let mut spawn = futures::executor::spawn(stream);
+    assert!(spawn.poll_stream(support::unpark_noop()).unwrap().is_not_ready());
+
+    spawn.get_mut().push(b_rx);
+    assert_eq!(spawn.get_ref().len(), 2);
+    b_tx.send(2).unwrap();
+    assert_eq!(Some(Ok(2)), spawn.wait_stream());
+    assert_eq!(spawn.get_ref().len(), 1);
+
+    a_tx.send(1).unwrap();
+    assert_eq!(Some(Ok(1)), spawn.wait_stream());
+    assert_eq!(None, spawn.wait_stream());
+
+    // the set can be reused after it's been drained
+    let (c_tx, c_rx) = oneshot::channel::<u32>();
+    spawn.get_mut().push(c_rx);
+    c_tx.send(3).unwrap();
+    assert_eq!(Some(Ok(3)), spawn.wait_stream());
+    assert_eq!(None, spawn.wait_stream());
+}
@@ -0,0 +1,446 @@
+#[macro_use]
+extern crate futures;
+
+use std::cmp;
+use std::collections::VecDeque;
+use std::io::{self as std_io, Read, Write};
+
+use futures::{Async, Future, Poll, Sink, Stream};
+use futures::executor;
+use futures::io::{self, AsyncRead, AsyncWrite};
+
+mod support;
+use support::*;
+
+/// A reader which hands out its data in the given chunks, returning
+/// `WouldBlock` for every empty one.
+#[derive(Debug)]
+struct MockReader {
+    chunks: VecDeque<Vec<u8>>,
+}
+
+impl MockReader {
+    fn new(chunks: Vec<&[u8]>) -> MockReader {
+        MockReader { chunks: chunks.into_iter().map(|c| c.to_vec()).collect() }
+    }
+}
+
+impl Read for MockReader {
+    fn read(&mut self, buf: &mut [u8]) -> std_io::Result<usize> {
+        let mut chunk = match self.chunks.pop_front() {
+            Some(chunk) => chunk,
+            None => return Ok(0),
+        };
+        if chunk.is_empty() {
+            return Err(std_io::Error::new(std_io::ErrorKind::WouldBlock, "would block"))
+        }
+        let n = cmp::min(buf.len(), chunk.len());
+        buf[..n].copy_from_slice(&chunk[..n]);
+        if n < chunk.len() {
+            self.chunks.push_front(chunk.split_off(n));
+        }
+        Ok(n)
+    }
+}
+
+impl AsyncRead for MockReader {}
+
+/// A writer which accepts at most `max` bytes per write, and returns
+/// `WouldBlock` on every other write and flush.
+#[derive(Debug)]
+struct MockWriter {
+    data: Vec<u8>,
+    max: usize,
+    block: bool,
+}
+
+impl MockWriter {
+    fn new(max: usize) -> MockWriter {
+        MockWriter { data: Vec::new(), max: max, block: true }
+    }
+
+    fn would_block(&mut self) -> bool {
+        self.block = !self.block;
+        !self.block
+    }
+}
+
+impl Write for MockWriter {
+    fn write(&mut self, buf: &[u8]) -> std_io::Result<usize> {
+        if self.would_block() {
+            return Err(std_io::Error::new(std_io::ErrorKind::WouldBlock, "would block"))
+        }
+        let n = cmp::min(buf.len(), self.max);
+        self.data.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std_io::Result<()> {
+        if self.would_block() {
+            return Err(std_io::Error::new(std_io::ErrorKind::WouldBlock, "would block"))
+        }
+        Ok(())
+    }
+}
+
+impl AsyncWrite for MockWriter {}
+
+#[test]
+fn poll_read_would_block() {
+    let mut reader = MockReader::new(vec![b"ab", b"", b"cd"]);
+    let mut buf = [0; 8];
+    assert_eq!(reader.poll_read(&mut buf).unwrap(), Async::Ready(2));
+    assert_eq!(&buf[..2], b"ab");
+    assert_eq!(reader.poll_read(&mut buf).unwrap(), Async::NotReady);
+    assert_eq!(reader.poll_read(&mut buf).unwrap(), Async::Ready(2));
+    assert_eq!(&buf[..2], b"cd");
+    assert_eq!(reader.poll_read(&mut buf).unwrap(), Async::Ready(0));
+}
+
+#[test]
+fn poll_write_would_block() {
+    let mut writer = MockWriter::new(2);
+    assert_eq!(writer.poll_write(b"abc").unwrap(), Async::NotReady);
+    assert_eq!(writer.poll_write(b"abc").unwrap(), Async::Ready(2));
+    assert_eq!(writer.poll_flush().unwrap(), Async::NotReady);
+    assert_eq!(writer.poll_flush().unwrap(), Async::Ready(()));
+    assert_eq!(writer.shutdown().unwrap(), Async::NotReady);
+    assert_eq!(writer.shutdown().unwrap(), Async::Ready(()));
+    assert_eq!(writer.data, b"ab");
+}
+
+#[test]
+fn errors_pass_through() {
+    struct Broken;
+
+    impl Read for Broken {
+        fn read(&mut self, _: &mut [u8]) -> std_io::Result<usize> {
+            Err(std_io::Error::new(std_io::ErrorKind::Other, "broken"))
+        }
+    }
+
+    impl AsyncRead for Broken {}
+
+    let err = Broken.poll_read(&mut [0; 4]).unwrap_err();
+    assert_eq!(err.kind(), std_io::ErrorKind::Other);
+}
+
+#[test]
+fn try_nb() {
+    fn read(reader: &mut MockReader) -> Poll<usize, std_io::Error> {
+        let mut buf = [0; 8];
+        Ok(Async::Ready(try_nb!(reader.read(&mut buf))))
+    }
+
+    let mut reader = MockReader::new(vec![b"", b"abc"]);
+    assert_eq!(read(&mut reader).unwrap(), Async::NotReady);
+    assert_eq!(read(&mut reader).unwrap(), Async::Ready(3));
+}
+
+#[test]
+fn std_impls() {
+    let mut slice: &[u8] = b"hello";
+    let mut buf = [0; 3];
+    assert_eq!(slice.poll_read(&mut buf).unwrap(), Async::Ready(3));
+    assert_eq!(&buf, b"hel");
+    assert_eq!(Box::new(slice).poll_read(&mut buf).unwrap(), Async::Ready(2));
+
+    let mut cursor = std_io::Cursor::new(Vec::new());
+    assert_eq!((&mut cursor).poll_write(b"abc").unwrap(), Async::Ready(3));
+    assert_eq!(cursor.shutdown().unwrap(), Async::Ready(()));
+    assert_eq!(cursor.into_inner(), b"abc");
+
+    let mut writer: Box<AsyncWrite> = Box::new(Vec::new());
+    assert_eq!(writer.poll_write(b"abc").unwrap(), Async::Ready(3));
+}
+
+#[test]
+fn copy_partial() {
+    let reader = MockReader::new(vec![b"hello ", b"", b"world", b"", b"!"]);
+    let mut copy = executor::spawn(io::copy(reader, MockWriter::new(3)));
+    let (amt, _, writer) = loop {
+        match copy.poll_future(unpark_noop()).unwrap() {
+            Async::Ready(res) => break res,
+            Async::NotReady => {}
+        }
+    };
+    assert_eq!(amt, 12);
+    assert_eq!(writer.data, b"hello world!");
+}
+
+#[test]
+fn copy_write_zero() {
+    let reader: &[u8] = b"hello";
+    let writer = std_io::Cursor::new(&mut [][..]);
+    let err = io::copy(reader, writer).wait().unwrap_err();
+    assert_eq!(err.kind(), std_io::ErrorKind::WriteZero);
+}
+
+fn run<F: Future>(f: F) -> Result<F::Item, F::Error> {
+    let mut f = executor::spawn(f);
+    loop {
+        match f.poll_future(unpark_noop()) {
+            Ok(Async::Ready(item)) => return Ok(item),
+            Ok(Async::NotReady) => {}
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[test]
+fn read_exact_partial() {
+    let reader = MockReader::new(vec![b"he", b"", b"llo", b" world"]);
+    let (mut reader, buf) = run(io::read_exact(reader, [0; 7])).unwrap();
+    assert_eq!(&buf, b"hello w");
+
+    let err = run(io::read_exact(&mut reader, vec![0; 8])).unwrap_err();
+    assert_eq!(err.kind(), std_io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn write_all_partial() {
+    let (writer, buf) = run(io::write_all(MockWriter::new(2), "hello")).unwrap();
+    assert_eq!(writer.data, b"hello");
+    assert_eq!(buf, "hello");
+
+    let mut small = [0; 3];
+    let writer = std_io::Cursor::new(&mut small[..]);
+    let err = run(io::write_all(writer, b"hello")).unwrap_err();
+    assert_eq!(err.kind(), std_io::ErrorKind::WriteZero);
+}
+
+#[test]
+fn read_to_end_partial() {
+    let data = (0..200).collect::<Vec<u8>>();
+    let reader = MockReader::new(vec![&data[..50], b"", &data[50..], b""]);
+    let (_, buf) = run(io::read_to_end(reader, vec![1, 2])).unwrap();
+    assert_eq!(&buf[..2], &[1, 2]);
+    assert_eq!(&buf[2..], &data[..]);
+}
+
+#[test]
+fn read_to_end_keeps_data_on_not_ready() {
+    let reader = MockReader::new(vec![b"hello", b"", b" world"]);
+    let mut read = executor::spawn(io::read_to_end(reader, Vec::new()));
+    assert!(read.poll_future(unpark_noop()).unwrap().is_not_ready());
+    match read.poll_future(unpark_noop()).unwrap() {
+        Async::Ready((_, buf)) => assert_eq!(buf, b"hello world"),
+        Async::NotReady => panic!("read_to_end should be done"),
+    }
+}
+
+#[test]
+fn split_halves_share_object() {
+    let (mut reader, mut writer) = io::split(std_io::Cursor::new(b"abcd".to_vec()));
+    let mut read = executor::spawn(io::read_exact(&mut reader, [0; 2]));
+    let mut write = executor::spawn(io::write_all(&mut writer, b"xy"));
+
+    // Both halves are usable from different tasks, one after the other.
+    match read.poll_future(unpark_noop()).unwrap() {
+        Async::Ready((_, buf)) => assert_eq!(&buf, b"ab"),
+        Async::NotReady => panic!("read should be done"),
+    }
+    assert!(write.poll_future(unpark_noop()).unwrap().is_ready());
+    drop((read, write));
+
+    let cursor = reader.unsplit(writer);
+    assert_eq!(cursor.into_inner(), b"abxy");
+}
+
+#[test]
+#[should_panic]
+fn unsplit_unrelated() {
+    let (reader, _) = io::split(std_io::Cursor::new(Vec::new()));
+    let (_, writer) = io::split(std_io::Cursor::new(Vec::new()));
+    reader.unsplit(writer);
+}
+
+#[test]
+fn buf_reader_fill_and_consume() {
+    let reader = MockReader::new(vec![b"hello", b"", b" world"]);
+    let mut reader = io::BufReader::with_capacity(4, reader);
+    assert_eq!(reader.poll_fill_buf().unwrap(), Async::Ready(&b"hell"[..]));
+    reader.consume(2);
+    assert_eq!(reader.buffer(), b"ll");
+    assert_eq!(reader.poll_fill_buf().unwrap(), Async::Ready(&b"ll"[..]));
+    reader.consume(2);
+    assert_eq!(reader.poll_fill_buf().unwrap(), Async::Ready(&b"o"[..]));
+    reader.consume(1);
+    assert_eq!(reader.poll_fill_buf().unwrap(), Async::NotReady);
+
+    let (_, rest) = run(io::read_to_end(reader, Vec::new())).unwrap();
+    assert_eq!(rest, b" world");
+}
+
+#[test]
+fn buf_reader_small_reads() {
+    struct Counting(MockReader, usize);
+
+    impl Read for Counting {
+        fn read(&mut self, buf: &mut [u8]) -> std_io::Result<usize> {
+            self.1 += 1;
+            self.0.read(buf)
+        }
+    }
+
+    impl AsyncRead for Counting {}
+
+    let reader = Counting(MockReader::new(vec![b"hello world"]), 0);
+    let mut reader = io::BufReader::new(reader);
+    let mut buf = [0; 1];
+    for &b in b"hello world" {
+        assert_eq!(reader.poll_read(&mut buf).unwrap(), Async::Ready(1));
+        assert_eq!(buf[0], b);
+    }
+    assert_eq!(reader.get_ref().1, 1);
+}
+
+#[test]
+fn buf_writer_batches_writes() {
+    let mut writer = io::BufWriter::with_capacity(4, MockWriter::new(3));
+    assert_eq!(writer.poll_write(b"ab").unwrap(), Async::Ready(2));
+    assert_eq!(writer.poll_write(b"c").unwrap(), Async::Ready(1));
+    assert!(writer.get_ref().data.is_empty());
+
+    // The buffer is full, so it's written out before taking more data.
+    assert_eq!(writer.poll_write(b"de").unwrap(), Async::NotReady);
+    assert_eq!(writer.buffer(), b"abc");
+    assert_eq!(writer.poll_write(b"de").unwrap(), Async::Ready(2));
+    assert_eq!(writer.get_ref().data, b"abc");
+    assert_eq!(writer.buffer(), b"de");
+
+    let (mut writer, _) = run(io::write_all(writer, b"fg")).unwrap();
+    while writer.poll_flush().unwrap().is_not_ready() {}
+    assert!(writer.buffer().is_empty());
+    assert_eq!(writer.into_inner().data, b"abcdefg");
+}
+
+#[test]
+fn buf_writer_large_write_bypasses_buffer() {
+    let mut writer = io::BufWriter::with_capacity(4, Vec::new());
+    assert_eq!(writer.poll_write(b"a").unwrap(), Async::Ready(1));
+    assert_eq!(writer.poll_write(b"bcdefg").unwrap(), Async::Ready(6));
+    assert!(writer.buffer().is_empty());
+    assert_eq!(writer.into_inner(), b"abcdefg");
+}
+
+#[test]
+fn lines_partial_and_crlf() {
+    let reader = MockReader::new(vec![b"hel", b"", b"lo\r", b"\n\nwor", b"", b"ld\r\n", b"!"]);
+    let lines = io::lines(io::BufReader::with_capacity(4, reader));
+    let lines = run(lines.collect()).unwrap();
+    assert_eq!(lines, ["hello", "", "world", "!"]);
+}
+
+#[test]
+fn lines_lone_cr() {
+    let reader: &[u8] = b"a\rb\n\r";
+    let lines = run(io::lines(io::BufReader::new(reader)).collect()).unwrap();
+    assert_eq!(lines, ["a\rb", "\r"]);
+}
+
+#[test]
+fn lines_invalid_utf8() {
+    let reader: &[u8] = b"ok\n\xff\n";
+    let mut lines = io::lines(io::BufReader::new(reader)).wait();
+    assert_eq!(lines.next().unwrap().unwrap(), "ok");
+    let err = lines.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), std_io::ErrorKind::InvalidData);
+}
+
+/// Newline-delimited UTF-8 frames.
+#[derive(Debug)]
+struct LineCodec;
+
+impl io::Decoder for LineCodec {
+    type Item = String;
+    type Error = std_io::Error;
+
+    fn decode(&mut self, src: &mut Vec<u8>) -> std_io::Result<Option<String>> {
+        match src.iter().position(|b| *b == b'\n') {
+            Some(i) => {
+                let line = src.drain(..i + 1).collect::<Vec<_>>();
+                Ok(Some(String::from_utf8_lossy(&line[..i]).into_owned()))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+impl io::Encoder for LineCodec {
+    type Item = String;
+    type Error = std_io::Error;
+
+    fn encode(&mut self, line: String, dst: &mut Vec<u8>) -> std_io::Result<()> {
+        dst.extend_from_slice(line.as_bytes());
+        dst.push(b'\n');
+        Ok(())
+    }
+}
+
+/// A transport reading from a `MockReader` and writing to a `MockWriter`.
+#[derive(Debug)]
+struct MockIo(MockReader, MockWriter);
+
+impl Read for MockIo {
+    fn read(&mut self, buf: &mut [u8]) -> std_io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl AsyncRead for MockIo {}
+
+impl Write for MockIo {
+    fn write(&mut self, buf: &[u8]) -> std_io::Result<usize> {
+        self.1.write(buf)
+    }
+
+    fn flush(&mut self) -> std_io::Result<()> {
+        self.1.flush()
+    }
+}
+
+impl AsyncWrite for MockIo {
+    // Flushing already blocks every other time, don't do it twice on close.
+    fn shutdown(&mut self) -> Poll<(), std_io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn framed_partial_frames() {
+    let reader = MockReader::new(vec![b"he", b"", b"llo\nwor", b"", b"ld\n"]);
+    let transport = io::framed(MockIo(reader, MockWriter::new(0)), LineCodec);
+    let lines = run(transport.collect()).unwrap();
+    assert_eq!(lines, ["hello", "world"]);
+}
+
+#[test]
+fn framed_bytes_remaining_at_eof() {
+    let reader = MockReader::new(vec![b"done\npartial"]);
+    let transport = io::framed(MockIo(reader, MockWriter::new(0)), LineCodec);
+    let mut transport = Stream::wait(transport);
+    assert_eq!(transport.next().unwrap().unwrap(), "done");
+    let err = transport.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), std_io::ErrorKind::Other);
+}
+
+#[test]
+fn framed_send_flushes() {
+    let io = MockIo(MockReader::new(vec![]), MockWriter::new(3));
+    let transport = io::framed(io, LineCodec);
+    let sent = futures::stream::iter_ok::<_, std_io::Error>(vec!["hello".to_string(),
+                                                                  "world".to_string()]);
+    let (transport, _) = run(transport.send_all(sent)).unwrap();
+    assert_eq!(transport.get_ref().1.data, b"hello\nworld\n");
+}
+
+#[test]
+fn framed_request_response() {
+    let reader = MockReader::new(vec![b"pi", b"", b"ng\n"]);
+    let transport = io::framed(MockIo(reader, MockWriter::new(2)), LineCodec);
+    let (line, transport) = run(transport.into_future()).ok().unwrap();
+    assert_eq!(line.unwrap(), "ping");
+    let transport = run(transport.send("pong".to_string())).unwrap();
+    assert_eq!(transport.into_inner().1.data, b"pong\n");
+}
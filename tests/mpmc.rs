@@ -0,0 +1,80 @@
+extern crate futures;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use futures::{Async, AsyncSink, Future, Sink, Stream};
+use futures::executor;
+use futures::future::lazy;
+use futures::sync::mpmc;
+
+mod support;
+use support::*;
+
+#[test]
+fn each_message_received_once() {
+    let (tx, rx) = mpmc::channel(4);
+
+    let threads = (0..4).map(|_| {
+        let rx = rx.clone();
+        thread::spawn(move || rx.collect().wait().unwrap())
+    }).collect::<Vec<_>>();
+    drop(rx);
+
+    tx.send_all(futures::stream::iter_ok(0..1000)).wait().unwrap();
+
+    let mut received = threads.into_iter()
+                              .flat_map(|t| t.join().unwrap())
+                              .collect::<Vec<_>>();
+    received.sort();
+    assert_eq!(received, (0..1000).collect::<Vec<_>>());
+}
+
+#[test]
+fn backpressure() {
+    let (mut tx, mut rx) = mpmc::channel::<i32>(0);
+
+    lazy(move || {
+        assert!(tx.start_send(1).unwrap().is_ready());
+        assert!(tx.start_send(2).unwrap().is_not_ready());
+
+        assert_eq!(rx.poll().unwrap(), Async::Ready(Some(1)));
+        assert!(tx.start_send(2).unwrap().is_ready());
+
+        drop(rx);
+        assert!(tx.start_send(3).is_err());
+
+        Ok::<(), ()>(())
+    }).wait().unwrap();
+}
+
+#[test]
+fn ends_when_senders_dropped() {
+    let (tx, rx) = mpmc::channel(4);
+    let rx2 = rx.clone();
+    let tx = tx.send(1).wait().unwrap();
+    drop(tx);
+
+    let mut rx = rx.wait();
+    assert_eq!(rx.next(), Some(Ok(1)));
+    assert_eq!(rx.next(), None);
+    assert_eq!(rx2.wait().next(), None);
+}
+
+#[test]
+fn retries_keep_one_task() {
+    let (tx, _rx) = mpmc::channel::<i32>(0);
+    let mut tx = executor::spawn(tx);
+    assert_eq!(tx.start_send(1, &unpark_noop()), Ok(AsyncSink::Ready));
+
+    let live = Arc::new(AtomicUsize::new(0));
+    for _ in 0..10 {
+        let unpark = unpark_counted(&live);
+        assert_eq!(tx.start_send(2, &unpark), Ok(AsyncSink::NotReady(2)));
+    }
+    assert_eq!(live.load(Ordering::SeqCst), 1);
+
+    drop(tx);
+    assert_eq!(live.load(Ordering::SeqCst), 0);
+}
@@ -3,6 +3,7 @@
 extern crate futures;
 
 use futures::{Future, Stream, Sink, Async, AsyncSink};
+use futures::executor;
 use futures::future::lazy;
 use futures::sync::mpsc;
 
@@ -11,6 +12,9 @@ use std::thread;
 use std::sync::{Arc, Mutex};
 use std::sync::atomic::{AtomicUsize, Ordering};
 
+mod support;
+use support::*;
+
 fn is_send<T: Send>() {}
 
 #[test]
@@ -59,6 +63,239 @@ fn send_recv_no_buffer() {
     }).wait().unwrap();
 }
 
+#[test]
+fn poll_ready() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(0);
+
+    lazy(move || {
+        assert!(tx.poll_ready().unwrap().is_ready());
+
+        // The sender's guaranteed slot is used up
+        assert!(is_ready(&tx.start_send(1).unwrap()));
+        assert!(tx.poll_ready().unwrap().is_not_ready());
+
+        // Taking the value frees it back up
+        assert_eq!(rx.poll().unwrap(), Async::Ready(Some(1)));
+        assert!(tx.poll_ready().unwrap().is_ready());
+
+        drop(rx);
+        assert!(tx.poll_ready().is_err());
+
+        Ok::<(), ()>(())
+    }).wait().unwrap();
+}
+
+#[test]
+fn reserve() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(0);
+
+    // The sender's guaranteed slot is free, so capacity is reserved right away
+    tx.reserve().wait().unwrap().send(1);
+
+    lazy(move || {
+        assert!(tx.reserve().poll().unwrap().is_not_ready());
+
+        assert_eq!(rx.poll().unwrap(), Async::Ready(Some(1)));
+        match tx.reserve().poll().unwrap() {
+            Async::Ready(permit) => permit.send(2),
+            Async::NotReady => panic!("capacity should be available"),
+        }
+        assert_eq!(rx.poll().unwrap(), Async::Ready(Some(2)));
+
+        drop(rx);
+        assert!(tx.reserve().poll().is_err());
+
+        Ok::<(), ()>(())
+    }).wait().unwrap();
+}
+
+#[test]
+fn try_send() {
+    let (mut tx, rx) = mpsc::channel::<i32>(0);
+
+    // No task is needed to send
+    assert!(tx.try_send(1).is_ok());
+    let err = tx.try_send(2).unwrap_err();
+    assert!(err.is_full());
+    assert_eq!(err.into_inner(), 2);
+
+    let rx = lazy(move || {
+        let mut rx = rx;
+        assert_eq!(rx.poll().unwrap(), Async::Ready(Some(1)));
+        Ok::<_, ()>(rx)
+    }).wait().unwrap();
+    assert!(tx.try_send(2).is_ok());
+
+    drop(rx);
+    let err = tx.try_send(3).unwrap_err();
+    assert!(err.is_disconnected());
+    assert_eq!(err.into_inner(), 3);
+}
+
+#[test]
+fn recv_many() {
+    let (tx, mut rx) = mpsc::channel::<i32>(16);
+    let tx = tx.send_all(futures::stream::iter_ok(0..5)).wait().unwrap().0;
+
+    let mut buf = Vec::new();
+    assert_eq!(rx.recv_many(&mut buf, 3).wait(), Ok(3));
+    assert_eq!(buf, vec![0, 1, 2]);
+    assert_eq!(rx.recv_many(&mut buf, 3).wait(), Ok(2));
+    assert_eq!(buf, vec![0, 1, 2, 3, 4]);
+
+    drop(tx);
+    assert_eq!(rx.recv_many(&mut buf, 3).wait(), Ok(0));
+}
+
+#[test]
+fn len_and_capacity() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(2);
+    assert!(tx.is_empty() && rx.is_empty());
+    assert_eq!(tx.capacity(), 3);
+    let tx2 = tx.clone();
+    assert_eq!(rx.capacity(), 4);
+    drop(tx2);
+
+    tx.try_send(1).unwrap();
+    tx.try_send(2).unwrap();
+    assert_eq!(tx.len(), 2);
+    assert_eq!(rx.len(), 2);
+
+    // Dropping the last sender doesn't count as a message
+    drop(tx);
+    assert_eq!(rx.len(), 2);
+    assert_eq!(rx.by_ref().collect().wait(), Ok(vec![1, 2]));
+    assert!(rx.is_empty());
+
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    tx.unbounded_send(1).unwrap();
+    assert_eq!(tx.len(), 1);
+    assert_eq!(rx.len(), 1);
+}
+
+#[test]
+fn weak_sender() {
+    let (tx, rx) = mpsc::channel::<i32>(16);
+    let weak = tx.downgrade();
+
+    let tx2 = weak.upgrade().unwrap();
+    tx2.send(1).wait().unwrap();
+    drop(tx);
+
+    // Weak handles don't keep the channel open
+    let weak2 = weak.clone();
+    assert_eq!(rx.collect().wait(), Ok(vec![1]));
+    assert!(weak2.upgrade().is_none());
+
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let weak = tx.downgrade();
+    weak.upgrade().unwrap().unbounded_send(1).unwrap();
+    drop(tx);
+    assert!(weak.upgrade().is_none());
+    assert_eq!(rx.collect().wait(), Ok(vec![1]));
+}
+
+#[test]
+fn priority_channel() {
+    let (mut tx, rx) = mpsc::priority_channel::<&str>(2);
+    tx.try_send(0, "a").unwrap();
+    tx.try_send(2, "b").unwrap();
+    tx.try_send(1, "c").unwrap();
+    assert!(tx.try_send(3, "d").unwrap_err().is_full());
+
+    let tx2 = tx.clone();
+    let tx2 = tx2.send((2, "e")).wait().unwrap();
+    drop((tx, tx2));
+
+    let msgs = rx.collect().wait().unwrap();
+    assert_eq!(msgs, vec!["b", "e", "c", "a"]);
+}
+
+#[test]
+fn priority_channel_backpressure() {
+    let (mut tx, mut rx) = mpsc::priority_channel::<i32>(0);
+
+    lazy(move || {
+        assert!(is_ready(&tx.start_send((0, 1)).unwrap()));
+        assert!(!is_ready(&tx.start_send((1, 2)).unwrap()));
+
+        assert_eq!(rx.poll().unwrap(), Async::Ready(Some(1)));
+        assert!(is_ready(&tx.start_send((1, 2)).unwrap()));
+
+        rx.close();
+        assert!(tx.start_send((0, 3)).is_err());
+        assert!(tx.try_send(0, 3).unwrap_err().is_disconnected());
+        assert_eq!(rx.poll().unwrap(), Async::Ready(Some(2)));
+        assert_eq!(rx.poll().unwrap(), Async::Ready(None));
+
+        Ok::<(), ()>(())
+    }).wait().unwrap();
+}
+
+#[test]
+fn priority_channel_retries_keep_one_task() {
+    let (mut tx, _rx) = mpsc::priority_channel::<i32>(0);
+    tx.try_send(0, 1).unwrap();
+    let mut tx = executor::spawn(tx);
+
+    let live = Arc::new(AtomicUsize::new(0));
+    for _ in 0..10 {
+        let unpark = unpark_counted(&live);
+        assert!(!is_ready(&tx.start_send((0, 2), &unpark).unwrap()));
+    }
+    assert_eq!(live.load(Ordering::SeqCst), 1);
+
+    drop(tx);
+    assert_eq!(live.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn lossy_channel() {
+    let (tx, mut rx) = mpsc::lossy_channel::<i32>(2);
+
+    lazy(move || {
+        assert!(rx.poll().unwrap().is_not_ready());
+
+        for i in 0..3 {
+            tx.lossy_send(i).unwrap();
+        }
+        assert_eq!(rx.poll().unwrap_err().dropped(), 1);
+        assert_eq!(rx.poll().unwrap(), Async::Ready(Some(1)));
+
+        // Nothing was dropped since the last error
+        tx.lossy_send(3).unwrap();
+        assert_eq!(rx.poll().unwrap(), Async::Ready(Some(2)));
+        assert_eq!(rx.poll().unwrap(), Async::Ready(Some(3)));
+
+        rx.close();
+        assert_eq!(tx.lossy_send(4).unwrap_err().into_inner(), 4);
+        assert_eq!(rx.poll().unwrap(), Async::Ready(None));
+
+        Ok::<(), ()>(())
+    }).wait().unwrap();
+}
+
+#[test]
+fn forward_pipeline() {
+    let (tx1, rx1) = mpsc::channel::<i32>(1);
+    let (tx2, rx2) = mpsc::channel::<i32>(1);
+
+    let producer = thread::spawn(move || {
+        tx1.send_all(futures::stream::iter_ok(0..100)).wait().unwrap();
+    });
+    let stage = thread::spawn(move || {
+        rx1.map(|x| x * 2)
+           .forward(tx2.sink_map_err(|_| ()))
+           .wait()
+           .unwrap();
+    });
+
+    let received = rx2.collect().wait().unwrap();
+    assert_eq!(received, (0..100).map(|x| x * 2).collect::<Vec<_>>());
+    producer.join().unwrap();
+    stage.join().unwrap();
+}
+
 #[test]
 fn send_shared_recv() {
     let (tx1, rx) = mpsc::channel::<i32>(16);
@@ -120,6 +357,24 @@ fn recv_close_gets_none() {
 }
 
 
+#[test]
+fn recv_close_drains_buffered() {
+    let (tx, mut rx) = mpsc::channel::<i32>(10);
+    let mut tx = tx.send(1).wait().unwrap();
+
+    lazy(move || {
+        rx.close();
+
+        // New sends are rejected, but the buffered message is still there
+        assert!(tx.start_send(2).is_err());
+        assert!(tx.try_send(2).unwrap_err().is_disconnected());
+        assert_eq!(rx.poll(), Ok(Async::Ready(Some(1))));
+        assert_eq!(rx.poll(), Ok(Async::Ready(None)));
+
+        Ok::<(), ()>(())
+    }).wait().unwrap();
+}
+
 #[test]
 fn tx_close_gets_none() {
     let (_, mut rx) = mpsc::channel::<i32>(10);
@@ -155,7 +410,7 @@ fn stress_shared_unbounded() {
 
         thread::spawn(move|| {
             for _ in 0..AMT {
-                mpsc::UnboundedSender::send(&mut tx, 1).unwrap();
+                mpsc::UnboundedSender::unbounded_send(&mut tx, 1).unwrap();
             }
         });
     }
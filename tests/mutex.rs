@@ -0,0 +1,75 @@
+extern crate futures;
+
+use std::thread;
+
+use futures::{Async, Future};
+use futures::executor;
+use futures::sync::Mutex;
+
+mod support;
+use support::*;
+
+#[test]
+fn smoke() {
+    let mutex = Mutex::new(1);
+    let mut guard = mutex.lock().wait().unwrap();
+    *guard = 2;
+    assert!(mutex.try_lock().is_none());
+    assert!(executor::spawn(mutex.lock()).poll_future(unpark_noop()).unwrap().is_not_ready());
+    drop(guard);
+    assert_eq!(*mutex.try_lock().unwrap(), 2);
+}
+
+#[test]
+fn fifo() {
+    let mutex = Mutex::new(Vec::new());
+    let guard = mutex.try_lock().unwrap();
+
+    let mut waiters = (0..3).map(|_| executor::spawn(mutex.lock())).collect::<Vec<_>>();
+    for waiter in waiters.iter_mut() {
+        assert!(waiter.poll_future(unpark_noop()).unwrap().is_not_ready());
+    }
+
+    // Waiting tasks can't be overtaken by `try_lock`
+    drop(guard);
+    assert!(mutex.try_lock().is_none());
+
+    // The lock is handed out in the order the waiters arrived
+    assert!(waiters[2].poll_future(unpark_noop()).unwrap().is_not_ready());
+    assert!(waiters[1].poll_future(unpark_noop()).unwrap().is_not_ready());
+    let mut guard = match waiters[0].poll_future(unpark_noop()) {
+        Ok(Async::Ready(guard)) => guard,
+        _ => panic!("lock wasn't acquired"),
+    };
+    guard.push(0);
+    drop(guard);
+
+    // A waiter which gives up passes the lock on, even once it's been
+    // handed the lock
+    drop(waiters.remove(1));
+    let guard = match waiters[1].poll_future(unpark_noop()) {
+        Ok(Async::Ready(guard)) => guard,
+        _ => panic!("lock wasn't acquired"),
+    };
+    drop(guard);
+    assert_eq!(*mutex.try_lock().unwrap(), vec![0]);
+}
+
+#[test]
+fn concurrent() {
+    const N: usize = 1000;
+
+    let mutex = Mutex::new(0);
+    let threads = (0..4).map(|_| {
+        let mutex = mutex.clone();
+        thread::spawn(move || {
+            for _ in 0..N {
+                *mutex.lock().wait().unwrap() += 1;
+            }
+        })
+    }).collect::<Vec<_>>();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    assert_eq!(*mutex.try_lock().unwrap(), 4 * N);
+}
@@ -0,0 +1,52 @@
+extern crate futures;
+
+use std::thread;
+
+use futures::{Future, Sink, Stream};
+use futures::sync::mux;
+
+#[test]
+fn routes_by_key() {
+    let (tx, subscriber) = mux::channel();
+
+    let threads = (0..4).map(|key| {
+        let rx = subscriber.subscribe(key).unwrap();
+        thread::spawn(move || rx.collect().wait().unwrap())
+    }).collect::<Vec<_>>();
+
+    let msgs = (0..100).map(|i| (i % 4, i)).collect::<Vec<_>>();
+    tx.send_all(futures::stream::iter_ok(msgs)).wait().unwrap();
+
+    for (key, thread) in threads.into_iter().enumerate() {
+        let expected = (0..100).filter(|i| i % 4 == key).collect::<Vec<_>>();
+        assert_eq!(thread.join().unwrap(), expected);
+    }
+}
+
+#[test]
+fn one_receiver_per_key() {
+    let (_tx, subscriber) = mux::channel::<&str, u32>();
+    let rx = subscriber.subscribe("a").unwrap();
+    assert_eq!(rx.key(), &"a");
+    assert!(subscriber.subscribe("a").is_none());
+    drop(rx);
+    assert!(subscriber.subscribe("a").is_some());
+}
+
+#[test]
+fn dropped_receiver_cleans_up_key() {
+    let (tx, subscriber) = mux::channel();
+    let rx = subscriber.subscribe(1).unwrap();
+    tx.send_to(1, "a").unwrap();
+    assert!(tx.is_subscribed(&1));
+
+    drop(rx);
+    assert!(!tx.is_subscribed(&1));
+    assert_eq!(tx.send_to(1, "b").unwrap_err().into_inner(), (1, "b"));
+
+    // Messages buffered for the old receiver are gone
+    let rx = subscriber.subscribe(1).unwrap();
+    tx.send_to(1, "c").unwrap();
+    drop(tx);
+    assert_eq!(rx.collect().wait(), Ok(vec!["c"]));
+}
@@ -0,0 +1,105 @@
+extern crate futures;
+
+use std::thread;
+
+use futures::{Async, Future};
+use futures::executor::{self, Spawn};
+use futures::sync::{Notify, Notified};
+
+mod support;
+use support::*;
+
+fn ready(f: &mut Spawn<Notified>) -> bool {
+    match f.poll_future(unpark_noop()) {
+        Ok(Async::Ready(())) => true,
+        Ok(Async::NotReady) => false,
+        Err(()) => panic!("notified failed"),
+    }
+}
+
+#[test]
+fn notify_one_wakes_in_order() {
+    let notify = Notify::new();
+    let mut a = executor::spawn(notify.notified());
+    let mut b = executor::spawn(notify.notified());
+    assert!(!ready(&mut a));
+    assert!(!ready(&mut b));
+
+    notify.notify_one();
+    assert!(!ready(&mut b));
+    assert!(ready(&mut a));
+
+    notify.notify_one();
+    assert!(ready(&mut b));
+}
+
+#[test]
+fn permit_is_stored() {
+    let notify = Notify::new();
+    notify.notify_one();
+    notify.notify_one();
+
+    // Only a single permit is kept
+    let mut a = executor::spawn(notify.notified());
+    let mut b = executor::spawn(notify.notified());
+    assert!(ready(&mut a));
+    assert!(!ready(&mut b));
+}
+
+#[test]
+fn notify_all() {
+    let notify = Notify::new();
+    let mut a = executor::spawn(notify.notified());
+    let mut b = executor::spawn(notify.notified());
+    assert!(!ready(&mut a));
+    assert!(!ready(&mut b));
+
+    notify.notify_all();
+
+    // Waiters arriving afterwards aren't notified
+    let mut c = executor::spawn(notify.notified());
+    assert!(!ready(&mut c));
+    assert!(ready(&mut a));
+    assert!(ready(&mut b));
+}
+
+#[test]
+fn dropped_waiter_passes_notification_on() {
+    let notify = Notify::new();
+    let mut a = executor::spawn(notify.notified());
+    let mut b = executor::spawn(notify.notified());
+    assert!(!ready(&mut a));
+    assert!(!ready(&mut b));
+
+    notify.notify_one();
+    drop(a);
+    assert!(ready(&mut b));
+
+    // With nobody left to pass it to, it's stored as a permit
+    let mut c = executor::spawn(notify.notified());
+    assert!(!ready(&mut c));
+    notify.notify_one();
+    drop(c);
+    assert!(notify.notified().wait().is_ok());
+}
+
+#[test]
+fn across_threads() {
+    let notify = Notify::new();
+    let done = Notify::new();
+    let t = {
+        let notify = notify.clone();
+        let done = done.clone();
+        thread::spawn(move || {
+            for _ in 0..100 {
+                notify.notified().wait().unwrap();
+                done.notify_one();
+            }
+        })
+    };
+    for _ in 0..100 {
+        notify.notify_one();
+        done.notified().wait().unwrap();
+    }
+    t.join().unwrap();
+}
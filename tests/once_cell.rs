@@ -0,0 +1,114 @@
+extern crate futures;
+
+use std::panic;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use futures::{Async, Future};
+use futures::executor;
+use futures::future;
+use futures::sync::{oneshot, OnceCell};
+
+mod support;
+use support::*;
+
+#[test]
+fn initializes_once() {
+    let cell = OnceCell::new();
+    let (tx, rx) = oneshot::channel::<u32>();
+    let mut rx = Some(rx);
+
+    let mut a = executor::spawn(cell.get_or_init(|| rx.take().unwrap()));
+    let mut b = executor::spawn(cell.get_or_init(|| -> future::FutureResult<u32, oneshot::Canceled> {
+        panic!("second initializer run")
+    }));
+    assert!(a.poll_future(unpark_noop()).unwrap().is_not_ready());
+    assert!(b.poll_future(unpark_noop()).unwrap().is_not_ready());
+    assert_eq!(cell.get(), None);
+
+    tx.send(3).unwrap();
+    assert_eq!(a.poll_future(unpark_noop()), Ok(Async::Ready(&3)));
+    assert_eq!(b.poll_future(unpark_noop()), Ok(Async::Ready(&3)));
+    drop((a, b));
+    assert_eq!(cell.get(), Some(&3));
+    assert_eq!(cell.into_inner(), Some(3));
+}
+
+#[test]
+fn failed_initializer_lets_another_run() {
+    let cell = OnceCell::new();
+    let (tx, rx) = oneshot::channel::<u32>();
+    let mut rx = Some(rx);
+
+    let mut a = executor::spawn(cell.get_or_init(|| rx.take().unwrap()));
+    let mut b = executor::spawn(cell.get_or_init(|| future::ok::<_, oneshot::Canceled>(4)));
+    assert!(a.poll_future(unpark_noop()).unwrap().is_not_ready());
+    assert!(b.poll_future(unpark_noop()).unwrap().is_not_ready());
+
+    drop(tx);
+    assert!(a.poll_future(unpark_noop()).is_err());
+    assert_eq!(b.poll_future(unpark_noop()), Ok(Async::Ready(&4)));
+}
+
+#[test]
+fn dropped_initializer_lets_another_run() {
+    let cell = OnceCell::new();
+    let (_tx, rx) = oneshot::channel::<u32>();
+    let mut rx = Some(rx);
+
+    let mut a = executor::spawn(cell.get_or_init(|| rx.take().unwrap()));
+    assert!(a.poll_future(unpark_noop()).unwrap().is_not_ready());
+    drop(a);
+    assert_eq!(cell.get_or_init(|| future::ok::<_, oneshot::Canceled>(5)).wait(), Ok(&5));
+}
+
+#[test]
+fn panicking_initializer_lets_another_run() {
+    let cell = OnceCell::<u32>::new();
+    let res = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        cell.get_or_init(|| -> future::FutureResult<u32, ()> { panic!("init failed") }).wait()
+    }));
+    assert!(res.is_err());
+    assert_eq!(cell.get_or_init(|| future::ok::<_, ()>(6)).wait(), Ok(&6));
+}
+
+#[test]
+fn repeated_polls_keep_one_task() {
+    let cell = OnceCell::new();
+    let (_tx, rx) = oneshot::channel::<u32>();
+    let mut rx = Some(rx);
+
+    let mut a = executor::spawn(cell.get_or_init(|| rx.take().unwrap()));
+    let mut b = executor::spawn(cell.get_or_init(|| future::ok::<_, oneshot::Canceled>(4)));
+    assert!(a.poll_future(unpark_noop()).unwrap().is_not_ready());
+
+    let live = Arc::new(AtomicUsize::new(0));
+    for _ in 0..10 {
+        assert!(b.poll_future(unpark_counted(&live)).unwrap().is_not_ready());
+    }
+    assert_eq!(live.load(Ordering::SeqCst), 1);
+
+    drop(b);
+    assert_eq!(live.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn across_threads() {
+    let cell = Arc::new(OnceCell::new());
+    let runs = Arc::new(AtomicUsize::new(0));
+    let threads = (0..8).map(|i| {
+        let cell = cell.clone();
+        let runs = runs.clone();
+        thread::spawn(move || {
+            *cell.get_or_init(|| {
+                runs.fetch_add(1, Ordering::SeqCst);
+                future::ok::<_, ()>(i)
+            }).wait().unwrap()
+        })
+    }).collect::<Vec<_>>();
+
+    let values = threads.into_iter().map(|t| t.join().unwrap()).collect::<Vec<_>>();
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+    assert!(values.iter().all(|&v| v == values[0]));
+}
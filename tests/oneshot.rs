@@ -1,5 +1,6 @@
 extern crate futures;
 
+use std::sync::Arc;
 use std::sync::mpsc;
 use std::thread;
 
@@ -24,6 +25,18 @@ fn smoke_poll() {
     assert!(task.poll_future(unpark_noop()).unwrap().is_ready());
 }
 
+#[test]
+fn is_canceled() {
+    let (tx, rx) = channel::<u32>();
+    assert!(!tx.is_canceled());
+    drop(rx);
+    assert!(tx.is_canceled());
+
+    let (tx, mut rx) = channel::<u32>();
+    rx.close();
+    assert!(tx.is_canceled());
+}
+
 #[test]
 fn cancel_notifies() {
     let (tx, rx) = channel::<u32>();
@@ -89,3 +102,42 @@ fn close_wakes() {
     tx2.send(()).unwrap();
     t.join().unwrap();
 }
+
+struct ThreadExecutor;
+
+impl futures::executor::Executor for ThreadExecutor {
+    fn execute(&self, run: futures::executor::Run) {
+        thread::spawn(move || run.run());
+    }
+}
+
+#[test]
+fn spawn_future_result() {
+    let rx = spawn_future(ok::<u32, ()>(3), Arc::new(ThreadExecutor));
+    assert_eq!(rx.wait(), Ok(Ok(3)));
+}
+
+#[test]
+fn spawn_future_cancels_when_receiver_dropped() {
+    struct Pending(mpsc::Sender<()>);
+
+    impl Future for Pending {
+        type Item = ();
+        type Error = ();
+
+        fn poll(&mut self) -> Poll<(), ()> {
+            Ok(futures::Async::NotReady)
+        }
+    }
+
+    impl Drop for Pending {
+        fn drop(&mut self) {
+            self.0.send(()).unwrap();
+        }
+    }
+
+    let (dropped_tx, dropped_rx) = mpsc::channel();
+    let rx = spawn_future(Pending(dropped_tx), Arc::new(ThreadExecutor));
+    drop(rx);
+    dropped_rx.recv().unwrap();
+}
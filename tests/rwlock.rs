@@ -0,0 +1,111 @@
+extern crate futures;
+
+use std::thread;
+
+use futures::{Async, Future};
+use futures::executor::{self, Spawn};
+use futures::sync::RwLock;
+
+mod support;
+use support::*;
+
+fn ready<F: Future>(f: &mut Spawn<F>) -> bool {
+    f.poll_future(unpark_noop()).ok().unwrap().is_ready()
+}
+
+#[test]
+fn readers_share() {
+    let lock = RwLock::new(1);
+    let a = lock.try_read().unwrap();
+    let b = lock.read().wait().unwrap();
+    assert_eq!(*a + *b, 2);
+
+    let mut write = executor::spawn(lock.write());
+    assert!(!ready(&mut write));
+    drop(a);
+    assert!(!ready(&mut write));
+    drop(b);
+    match write.poll_future(unpark_noop()) {
+        Ok(Async::Ready(mut guard)) => *guard = 3,
+        _ => panic!("lock wasn't acquired"),
+    }
+    assert_eq!(*lock.try_read().unwrap(), 3);
+}
+
+#[test]
+fn fair() {
+    let lock = RwLock::new(());
+    let read = lock.try_read().unwrap();
+
+    // Readers queued behind a writer wait for it
+    let mut write = executor::spawn(lock.write());
+    assert!(!ready(&mut write));
+    let mut read2 = executor::spawn(lock.read());
+    assert!(!ready(&mut read2));
+    assert!(lock.try_read().is_none());
+
+    drop(read);
+    assert!(!ready(&mut read2));
+    assert!(ready(&mut write));
+    assert!(ready(&mut read2));
+}
+
+#[test]
+fn writer_priority() {
+    let lock = RwLock::with_writer_priority(());
+    let write = lock.try_write().unwrap();
+
+    let mut read = executor::spawn(lock.read());
+    assert!(!ready(&mut read));
+    let mut write2 = executor::spawn(lock.write());
+    assert!(!ready(&mut write2));
+
+    // The writer goes first even though the reader was waiting longer
+    drop(write);
+    assert!(!ready(&mut read));
+    let guard = match write2.poll_future(unpark_noop()) {
+        Ok(Async::Ready(guard)) => guard,
+        _ => panic!("lock wasn't acquired"),
+    };
+    drop(guard);
+    assert!(ready(&mut read));
+}
+
+#[test]
+fn dropped_waiter() {
+    let lock = RwLock::new(());
+    let read = lock.try_read().unwrap();
+
+    let mut write = executor::spawn(lock.write());
+    assert!(!ready(&mut write));
+    let mut read2 = executor::spawn(lock.read());
+    assert!(!ready(&mut read2));
+
+    // Once the writer gives up, the reader behind it can go ahead
+    drop(write);
+    assert!(ready(&mut read2));
+    drop(read);
+}
+
+#[test]
+fn concurrent() {
+    const N: usize = 500;
+
+    let lock = RwLock::new(0);
+    let threads = (0..4).map(|i| {
+        let lock = lock.clone();
+        thread::spawn(move || {
+            for _ in 0..N {
+                if i % 2 == 0 {
+                    *lock.write().wait().unwrap() += 1;
+                } else {
+                    assert!(*lock.read().wait().unwrap() <= 2 * N);
+                }
+            }
+        })
+    }).collect::<Vec<_>>();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    assert_eq!(*lock.try_read().unwrap(), 2 * N);
+}
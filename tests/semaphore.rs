@@ -0,0 +1,86 @@
+extern crate futures;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use futures::{Async, Future};
+use futures::executor::{self, Spawn};
+use futures::sync::Semaphore;
+
+mod support;
+use support::*;
+
+fn ready<F: Future>(f: &mut Spawn<F>) -> bool {
+    f.poll_future(unpark_noop()).ok().unwrap().is_ready()
+}
+
+#[test]
+fn fifo() {
+    let semaphore = Semaphore::new(2);
+    let permit = semaphore.try_acquire(2).unwrap();
+    assert_eq!(permit.permits(), 2);
+
+    // A large request isn't overtaken by smaller ones
+    let mut large = executor::spawn(semaphore.acquire(2));
+    assert!(!ready(&mut large));
+    let mut small = executor::spawn(semaphore.acquire(1));
+    assert!(!ready(&mut small));
+    assert!(semaphore.try_acquire(1).is_none());
+
+    drop(permit);
+    assert!(!ready(&mut small));
+    let permit = match large.poll_future(unpark_noop()) {
+        Ok(Async::Ready(permit)) => permit,
+        _ => panic!("permits weren't acquired"),
+    };
+    drop(permit);
+    assert!(ready(&mut small));
+    assert_eq!(semaphore.available_permits(), 2);
+}
+
+#[test]
+fn dropped_waiter() {
+    let semaphore = Semaphore::new(1);
+    let permit = semaphore.try_acquire(1).unwrap();
+
+    let mut large = executor::spawn(semaphore.acquire(2));
+    assert!(!ready(&mut large));
+    let mut small = executor::spawn(semaphore.acquire(1));
+    assert!(!ready(&mut small));
+
+    // Once the large request gives up, the small one can go ahead
+    drop(large);
+    drop(permit);
+    let permit = match small.poll_future(unpark_noop()) {
+        Ok(Async::Ready(permit)) => permit,
+        _ => panic!("permits weren't acquired"),
+    };
+
+    permit.forget();
+    assert_eq!(semaphore.available_permits(), 0);
+    semaphore.add_permits(1);
+    assert_eq!(semaphore.available_permits(), 1);
+}
+
+#[test]
+fn bounds_concurrency() {
+    let semaphore = Semaphore::new(2);
+    let active = Arc::new(AtomicUsize::new(0));
+    let threads = (0..8).map(|_| {
+        let semaphore = semaphore.clone();
+        let active = active.clone();
+        thread::spawn(move || {
+            for _ in 0..100 {
+                let permit = semaphore.acquire(1).wait().unwrap();
+                assert!(active.fetch_add(1, Ordering::SeqCst) < 2);
+                active.fetch_sub(1, Ordering::SeqCst);
+                drop(permit);
+            }
+        })
+    }).collect::<Vec<_>>();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    assert_eq!(semaphore.available_permits(), 2);
+}
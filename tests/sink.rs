@@ -26,6 +26,49 @@ fn vec_sink() {
     assert_done(move || v.flush(), Ok(vec![0, 1]));
 }
 
+#[test]
+fn drain() {
+    let mut sink = futures::sink::drain();
+    assert_eq!(sink.start_send(0), Ok(AsyncSink::Ready));
+    assert_eq!(sink.start_send(1), Ok(AsyncSink::Ready));
+    assert_eq!(sink.poll_complete(), Ok(Async::Ready(())));
+    assert_eq!(sink.close(), Ok(Async::Ready(())));
+
+    let (_, mut s) = sink.send_all(stream::iter_ok(vec![2, 3])).wait().unwrap();
+    sassert_done(&mut s);
+}
+
+#[test]
+fn unfold() {
+    let (tx, rx) = oneshot::channel::<u32>();
+    let mut rx = Some(rx);
+    let sink = futures::sink::unfold(Vec::new(), move |mut seen, x: u32| {
+        seen.push(x);
+        let rx = rx.take().unwrap().map_err(|_| ());
+        rx.map(move |y| { seen.push(y); seen })
+    });
+
+    let mut task = executor::spawn(sink.send(1));
+    assert!(task.poll_future(unpark_noop()).unwrap().is_not_ready());
+    tx.send(2).unwrap();
+    let sink = match task.poll_future(unpark_noop()).unwrap() {
+        Async::Ready(sink) => sink,
+        Async::NotReady => panic!("unfold sink should be flushed"),
+    };
+    assert_eq!(sink.into_inner(), Some(vec![1, 2]));
+}
+
+#[test]
+fn vec_deque_sink() {
+    use std::collections::VecDeque;
+
+    let mut v = VecDeque::new();
+    assert_eq!(v.start_send(0), Ok(AsyncSink::Ready));
+    assert_eq!(v.start_send(1), Ok(AsyncSink::Ready));
+    assert_eq!(v, vec![0, 1].into_iter().collect::<VecDeque<_>>());
+    assert_done(move || v.flush(), Ok(vec![0, 1].into_iter().collect()));
+}
+
 #[test]
 fn send() {
     let v = Vec::new();
@@ -167,6 +210,19 @@ fn with_as_map() {
     assert_eq!(sink.get_ref(), &[0, 2, 4]);
 }
 
+#[test]
+// test that with_flat_map sends every item of each stream
+fn with_flat_map() {
+    let sink = Vec::new().with_flat_map(|item: usize| {
+        stream::iter_ok(vec![item; item])
+    });
+    let sink = sink.send(0).wait().unwrap();
+    let sink = sink.send(1).wait().unwrap();
+    let sink = sink.send(2).wait().unwrap();
+    let sink = sink.send(3).wait().unwrap();
+    assert_eq!(sink.get_ref(), &[1, 2, 2, 3, 3, 3]);
+}
+
 // Immediately accepts all requests to start pushing, but completion is managed
 // by manually flushing
 struct ManualFlush<T> {
@@ -217,6 +273,20 @@ impl<T> ManualFlush<T> {
     }
 }
 
+#[test]
+// test that feeding items into a sink doesn't wait for it to be flushed
+fn feed() {
+    let sink = ManualFlush::new();
+    let sink = sink.feed(Some(0)).wait().unwrap();
+    let sink = sink.feed(Some(1)).wait().unwrap();
+    assert_eq!(sink.data, vec![0, 1]);
+
+    let mut task = executor::spawn(sink.flush());
+    assert!(task.poll_future(unpark_noop()).unwrap().is_not_ready());
+    assert_eq!(task.get_mut().get_mut().force_flush(), vec![0, 1]);
+    assert!(task.poll_future(unpark_noop()).unwrap().is_ready());
+}
+
 #[test]
 // test that the `with` sink doesn't require the underlying sink to flush,
 // but doesn't claim to be flushed until the underlyig sink is
@@ -339,6 +409,144 @@ fn buffer() {
     }
 }
 
+#[test]
+// test that a buffer accepts exactly as many items as its capacity while the
+// underlying sink isn't ready
+fn buffer_capacity() {
+    let (sink, allow) = manual_allow::<i32>();
+    let mut sink = sink.buffer(2);
+
+    let flag = Flag::new();
+    for i in 0..2 {
+        let mut task = executor::spawn(StartSendFut::new(&mut sink, i));
+        assert!(task.poll_future(flag.clone()).unwrap().is_ready());
+    }
+    let mut task = executor::spawn(StartSendFut::new(&mut sink, 2));
+    assert!(task.poll_future(flag.clone()).unwrap().is_not_ready());
+    allow.start();
+    assert!(flag.get());
+    assert!(task.poll_future(flag.clone()).unwrap().is_ready());
+    drop(task);
+
+    let sink = sink.flush().wait().unwrap();
+    assert_eq!(sink.get_ref().data, vec![0, 1, 2]);
+}
+
+#[test]
+// test that with_flat_map waits for the whole stream to be sent before
+// accepting another value
+fn with_flat_map_backpressure() {
+    let (sink, allow) = manual_allow::<i32>();
+    let mut sink = sink.with_flat_map(|item| stream::iter_ok(vec![item, item]));
+
+    let flag = Flag::new();
+    let mut task = executor::spawn(StartSendFut::new(&mut sink, 1));
+    assert!(task.poll_future(flag.clone()).unwrap().is_ready());
+
+    let mut task = executor::spawn(StartSendFut::new(&mut sink, 2));
+    assert!(task.poll_future(flag.clone()).unwrap().is_not_ready());
+    assert!(!flag.get());
+    allow.start();
+    assert!(flag.get());
+    assert!(task.poll_future(flag.clone()).unwrap().is_ready());
+    drop(task);
+
+    let sink = sink.flush().wait().unwrap();
+    assert_eq!(sink.get_ref().data, vec![1, 1, 2, 2]);
+}
+
+#[test]
+fn fanout() {
+    let sink = Vec::new().fanout(Vec::new());
+    let sink = sink.send(0).wait().unwrap();
+    let sink = sink.send(1).wait().unwrap();
+    let (left, right) = sink.into_inner();
+    assert_eq!(left, vec![0, 1]);
+    assert_eq!(right, vec![0, 1]);
+}
+
+#[test]
+// test that a fanout sink waits for the slowest of its sinks
+fn fanout_backpressure() {
+    let (slow, allow) = manual_allow::<i32>();
+    let mut sink = Vec::new().fanout(slow);
+
+    let flag = Flag::new();
+    let mut task = executor::spawn(StartSendFut::new(&mut sink, 0));
+    assert!(task.poll_future(flag.clone()).unwrap().is_ready());
+
+    let mut task = executor::spawn(StartSendFut::new(&mut sink, 1));
+    assert!(task.poll_future(flag.clone()).unwrap().is_not_ready());
+    allow.start();
+    assert!(flag.get());
+    assert!(task.poll_future(flag.clone()).unwrap().is_ready());
+    drop(task);
+
+    let sink = sink.flush().wait().unwrap();
+    let (fast, slow) = sink.into_inner();
+    assert_eq!(fast, vec![0, 1]);
+    assert_eq!(slow.data, vec![0, 1]);
+}
+
+#[test]
+fn either() {
+    let sink = Vec::new().left_sink::<Drain<i32>>();
+    let sink = sink.send(0).wait().unwrap();
+    match sink {
+        futures::future::Either::A(v) => assert_eq!(v, vec![0]),
+        futures::future::Either::B(_) => panic!(),
+    }
+
+    let (sink, _) = manual_allow::<i32>();
+    let mut sink = sink.right_sink::<Vec<i32>>();
+    let mut task = executor::spawn(StartSendFut::new(&mut sink, 1));
+    assert!(task.poll_future(unpark_noop()).unwrap().is_not_ready());
+}
+
+// Records whether it has been closed
+struct CloseCheck {
+    data: Vec<i32>,
+    closed: Rc<Cell<bool>>,
+}
+
+impl Sink for CloseCheck {
+    type SinkItem = i32;
+    type SinkError = ();
+
+    fn start_send(&mut self, item: i32) -> StartSend<i32, ()> {
+        assert!(!self.closed.get());
+        self.data.push(item);
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), ()> {
+        Ok(Async::Ready(()))
+    }
+
+    fn close(&mut self) -> Poll<(), ()> {
+        self.closed.set(true);
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+// test that closing a chain of combinators closes the underlying sink
+fn close_propagates() {
+    let closed = Rc::new(Cell::new(false));
+    let sink = CloseCheck { data: Vec::new(), closed: closed.clone() };
+    let sink = sink.buffer(2)
+                   .with(|x: i32| -> Result<i32, ()> { Ok(x + 1) })
+                   .with_flat_map(|x| stream::iter_ok(vec![x, x]))
+                   .fanout(Vec::new())
+                   .sink_map_err(|()| ());
+
+    let mut sink = sink.wait();
+    sink.send(1).unwrap();
+    assert!(!closed.get());
+    sink.close().unwrap();
+    assert!(closed.get());
+}
+
 #[test]
 fn map_err() {
     {
@@ -373,3 +581,61 @@ fn from_err() {
     let tx = mpsc::channel(0).0;
     assert_eq!(tx.sink_from_err().start_send(()), Err(FromErrTest));
 }
+
+// Writes values to a shared log when flushed, or fails the flush if `fail`
+// is set
+struct Flaky {
+    log: Rc<RefCell<Vec<i32>>>,
+    pending: Vec<i32>,
+    fail: bool,
+}
+
+impl Sink for Flaky {
+    type SinkItem = i32;
+    type SinkError = &'static str;
+
+    fn start_send(&mut self, item: i32) -> StartSend<i32, &'static str> {
+        self.pending.push(item);
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), &'static str> {
+        if self.fail {
+            return Err("broken")
+        }
+        self.log.borrow_mut().extend(self.pending.drain(..));
+        Ok(Async::Ready(()))
+    }
+
+    fn close(&mut self) -> Poll<(), &'static str> {
+        self.poll_complete()
+    }
+}
+
+#[test]
+// test that a failed sink is rebuilt and unflushed values replayed into it
+fn retry_rebuilds_sink() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let connects = Rc::new(Cell::new(0));
+
+    let (log2, connects2) = (log.clone(), connects.clone());
+    let factory = move || {
+        connects2.set(connects2.get() + 1);
+        Ok(Flaky { log: log2.clone(), pending: Vec::new(), fail: connects2.get() == 1 })
+    };
+    let mut errors = Vec::new();
+    let sink = retry(factory, |e: &&'static str| { errors.push(*e); errors.len() < 2 });
+
+    // The first sink fails its flush, so the value is replayed into a new one
+    let sink = sink.send(1).wait().unwrap();
+    assert_eq!(connects.get(), 2);
+    assert_eq!(*log.borrow(), vec![1]);
+    let mut sink = sink.send_all(stream::iter_ok(vec![2, 3])).wait().unwrap().0;
+    assert_eq!(connects.get(), 2);
+    assert_eq!(*log.borrow(), vec![1, 2, 3]);
+
+    // Once the policy gives up the error is returned
+    sink.get_mut().unwrap().fail = true;
+    assert_eq!(sink.send(4).wait().err(), Some("broken"));
+    assert_eq!(connects.get(), 2);
+}
@@ -1,16 +1,39 @@
 #[macro_use]
 extern crate futures;
 
-use futures::{Poll, Future, Stream, Sink};
-use futures::executor;
-use futures::future::{ok, err};
-use futures::stream::{iter, Peekable, BoxStream};
+use std::cell::Cell;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use futures::{Async, Poll, Future, Stream, Sink};
+use futures::executor::{self, Unpark};
+use futures::future::{self, ok, err};
+use futures::stream::{self, iter, Peekable, BoxStream};
 use futures::sync::oneshot;
 use futures::sync::mpsc;
 
 mod support;
 use support::*;
 
+// An Unpark struct that records unpark events for inspection
+struct Flag(AtomicBool);
+
+impl Flag {
+    fn new() -> Arc<Flag> {
+        Arc::new(Flag(AtomicBool::new(false)))
+    }
+
+    fn take(&self) -> bool {
+        self.0.swap(false, Ordering::SeqCst)
+    }
+}
+
+impl Unpark for Flag {
+    fn unpark(&self) {
+        self.0.store(true, Ordering::SeqCst)
+    }
+}
+
 
 fn list() -> BoxStream<i32, u32> {
     let (tx, rx) = mpsc::channel(1);
@@ -58,6 +81,37 @@ fn from_err() {
 fn fold() {
     assert_done(|| list().fold(0, |a, b| ok::<i32, u32>(a + b)), Ok(6));
     assert_done(|| err_list().fold(0, |a, b| ok::<i32, u32>(a + b)), Err(3));
+    assert_done(|| list().fold(0, |a, b| if b < 2 { Ok(a + b) } else { Err(b as u32) }),
+                Err(2));
+}
+
+#[test]
+fn boxed_local() {
+    use std::rc::Rc;
+
+    // Neither `Send` nor `'static`
+    let data = vec![1, 2, 3];
+    let s: stream::LocalBoxStream<Rc<i32>, u32> =
+        stream::iter_ok(data.iter().map(|&x| Rc::new(x))).boxed_local();
+    let items = s.map(|x| *x).collect().wait().unwrap();
+    assert_eq!(items, data);
+}
+
+#[test]
+fn try_fold() {
+    assert_done(|| list().try_fold(0, |a, b| Ok(a + b)), Ok(6));
+    assert_done(|| err_list().try_fold(0, |a, b| ok(a + b)), Err(3));
+    assert_done(|| list().try_fold(0, |a, b| if b < 2 { Ok(a + b) } else { Err(b as u32) }),
+                Err(2));
+
+    // No more items are pulled after the closure fails
+    let pulled = Cell::new(0);
+    let s = stream::iter_ok::<_, i32>(vec![1, 2]).map(|x| {
+        pulled.set(pulled.get() + 1);
+        x
+    });
+    assert_eq!(s.try_fold(0, |_, b| Err(b)).wait(), Err(1));
+    assert_eq!(pulled.get(), 1);
 }
 
 #[test]
@@ -76,6 +130,56 @@ fn filter_map() {
     }).collect(), Ok(vec![12]));
 }
 
+#[test]
+fn filter_map_async() {
+    assert_done(|| list().filter_map_async(|x| {
+        ok(if x % 2 == 0 { Some(x + 10) } else { None })
+    }).collect(), Ok(vec![12]));
+    assert_done(|| list().filter_map_async(|x| {
+        if x < 2 { Ok(None) } else { Err(x as u32) }
+    }).collect(), Err::<Vec<i32>, _>(2));
+    assert_done(|| err_list().filter_map_async(|x| Ok(Some(x))).collect(), Err(3));
+}
+
+#[test]
+fn count() {
+    assert_done(|| list().count(), Ok(3));
+    assert_done(|| stream::empty::<i32, u32>().count(), Ok(0));
+    assert_done(|| err_list().count(), Err(3));
+}
+
+#[test]
+fn any() {
+    assert_done(|| list().any(|e| Ok(e == 2)), Ok(true));
+    assert_done(|| list().any(|e| ok(e > 3)), Ok(false));
+    assert_done(|| stream::empty::<i32, u32>().any(|_| Ok(true)), Ok(false));
+    assert_done(|| err_list().any(|e| Ok(e == 2)), Ok(true));
+    assert_done(|| err_list().any(|e| Ok(e > 3)), Err(3));
+    assert_done(|| list().any(|e| err::<bool, u32>(e as u32)), Err(1));
+}
+
+#[test]
+fn all() {
+    assert_done(|| list().all(|e| Ok(e < 2)), Ok(false));
+    assert_done(|| list().all(|e| ok(e < 4)), Ok(true));
+    assert_done(|| stream::empty::<i32, u32>().all(|_| Ok(false)), Ok(true));
+    assert_done(|| err_list().all(|e| Ok(e < 2)), Ok(false));
+    assert_done(|| err_list().all(|e| Ok(e < 4)), Err(3));
+}
+
+#[test]
+fn dedup() {
+    let mut s = iter(vec![Ok(1), Ok(1), Err(2), Ok(1), Ok(3), Ok(1)]).dedup().wait();
+    assert_eq!(s.next(), Some(Ok(1)));
+    assert_eq!(s.next(), Some(Err(2)));
+    assert_eq!(s.next(), Some(Ok(3)));
+    assert_eq!(s.next(), Some(Ok(1)));
+    assert_eq!(s.next(), None);
+
+    assert_done(|| list().dedup_by(|_, _| true).collect(), Ok(vec![1]));
+    assert_done(|| list().dedup_by(|a, b| a + 1 == *b).collect(), Ok(vec![1, 3]));
+}
+
 #[test]
 fn and_then() {
     assert_done(|| list().and_then(|a| Ok(a + 1)).collect(), Ok(vec![2, 3, 4]));
@@ -87,6 +191,11 @@ fn and_then() {
 fn then() {
     assert_done(|| list().then(|a| a.map(|e| e + 1)).collect(), Ok(vec![2, 3, 4]));
 
+    // Errors of the base stream are given to the closure, and can be recovered
+    assert_done(|| err_list().then(|a| ok::<i32, u32>(a.unwrap_or(0))).collect(),
+                Ok(vec![1, 2, 0]));
+    assert_done(|| list().then(|a| err::<i32, u32>(a.unwrap() as u32)).collect(),
+                Err(1));
 }
 
 #[test]
@@ -96,6 +205,24 @@ fn or_else() {
     }).collect(), Ok(vec![1, 2, 3]));
 }
 
+#[test]
+fn recover() {
+    let s = stream::iter_result(vec![Err(1), Ok(2), Err(3), Err(4), Ok(5)]);
+    let s = s.recover(|e| {
+        if e == 4 {
+            err(e + 1)
+        } else {
+            ok(if e == 1 { Some(10) } else { None })
+        }
+    });
+    let mut s = s.wait();
+    assert_eq!(s.next(), Some(Ok(10)));
+    assert_eq!(s.next(), Some(Ok(2)));
+    assert_eq!(s.next(), Some(Err(5)));
+    assert_eq!(s.next(), Some(Ok(5)));
+    assert_eq!(s.next(), None);
+}
+
 #[test]
 fn flatten() {
     assert_done(|| list().map(|_| list()).flatten().collect(),
@@ -103,6 +230,28 @@ fn flatten() {
 
 }
 
+#[test]
+fn flat_map() {
+    assert_done(|| list().flat_map(|_| list()).collect(),
+                Ok(vec![1, 2, 3, 1, 2, 3, 1, 2, 3]));
+    assert_done(|| {
+        list().flat_map(|e| stream::iter_ok::<_, u32>(vec![e; e as usize])).collect()
+    }, Ok(vec![1, 2, 2, 3, 3, 3]));
+    assert_done(|| err_list().flat_map(|e| stream::iter_ok::<_, u32>(vec![e])).collect(),
+                Err(3));
+}
+
+#[test]
+fn enumerate() {
+    assert_done(|| list().enumerate().collect(), Ok(vec![(0, 1), (1, 2), (2, 3)]));
+
+    let mut s = iter(vec![Ok(1), Err(2), Ok(3)]).enumerate().wait();
+    assert_eq!(s.next(), Some(Ok((0, 1))));
+    assert_eq!(s.next(), Some(Err(2)));
+    assert_eq!(s.next(), Some(Ok((1, 3))));
+    assert_eq!(s.next(), None);
+}
+
 #[test]
 fn skip() {
     assert_done(|| list().skip(2).collect(), Ok(vec![3]));
@@ -136,6 +285,52 @@ fn take_while() {
                 Ok(vec![1, 2]));
 }
 
+#[test]
+fn scan() {
+    assert_done(|| list().scan(0, |sum, e| {
+        *sum += e;
+        Ok(Some(*sum * 10))
+    }).collect(), Ok(vec![10, 30, 60]));
+    assert_done(|| list().scan((), |_, e| Ok(if e < 2 { Some(e) } else { None }))
+                         .collect(),
+                Ok(vec![1]));
+    assert_done(|| err_list().scan((), |_, e| Ok(Some(e))).collect(), Err(3));
+}
+
+#[test]
+fn take_until() {
+    let (tx, rx) = oneshot::channel::<()>();
+    let mut s = executor::spawn(iter(vec![Ok(1), Err(2), Ok(3)]).take_until(rx));
+    assert_eq!(s.wait_stream(), Some(Ok(1)));
+    assert_eq!(s.wait_stream(), Some(Err(2)));
+    tx.send(()).unwrap();
+    assert_eq!(s.wait_stream(), None);
+    assert!(s.get_ref().is_stopped());
+
+    // A canceled trigger also stops the stream
+    let (tx, rx) = oneshot::channel::<()>();
+    drop(tx);
+    let mut s = list().take_until(rx);
+    sassert_done(&mut s);
+    assert!(s.is_stopped());
+
+    // Otherwise the stream runs to completion
+    assert_done(|| list().take_until(future::empty::<(), ()>()).collect(),
+                Ok(vec![1, 2, 3]));
+
+    // and isn't polled again once it has finished
+    let mut done = false;
+    let s = stream::poll_fn(move || -> Poll<Option<i32>, u32> {
+        assert!(!done);
+        done = true;
+        Ok(Async::Ready(None))
+    });
+    let mut s = s.take_until(future::empty::<(), ()>());
+    assert_eq!(s.poll(), Ok(Async::Ready(None)));
+    assert!(s.is_stopped());
+    assert_eq!(s.poll(), Ok(Async::Ready(None)));
+}
+
 #[test]
 fn take_passes_errors_through() {
     let mut s = iter(vec![Err(1), Err(2), Ok(3), Ok(4), Err(4)])
@@ -167,6 +362,29 @@ fn fuse() {
     assert_eq!(stream.next(), None);
 }
 
+#[test]
+fn fuse_is_done() {
+    // The inner stream would panic if it were polled after finishing
+    let mut done = false;
+    let s = stream::poll_fn(move || -> Poll<Option<i32>, u32> {
+        assert!(!done);
+        done = true;
+        Ok(Async::Ready(None))
+    });
+    let mut s = s.fuse();
+    assert!(!s.is_done());
+    assert_eq!(s.poll(), Ok(Async::Ready(None)));
+    assert!(s.is_done());
+    assert_eq!(s.poll(), Ok(Async::Ready(None)));
+}
+
+#[test]
+fn by_ref() {
+    let mut s = list();
+    assert_done(|| s.by_ref().take(2).collect(), Ok(vec![1, 2]));
+    assert_done(|| s.collect(), Ok(vec![3]));
+}
+
 #[test]
 fn buffered() {
     let (tx, rx) = mpsc::channel(1);
@@ -336,6 +554,61 @@ fn forward() {
                 Ok::<_, ()>(vec![0, 1, 2, 3, 4, 5]));
 }
 
+#[test]
+fn collect_as() {
+    use std::collections::BTreeSet;
+
+    assert_done(|| list().collect_as::<BTreeSet<_>>(),
+                Ok(vec![1, 2, 3].into_iter().collect()));
+    assert_done(|| list().map(|i| i.to_string()).collect_as::<String>(),
+                Ok("123".to_string()));
+    assert_done(|| err_list().collect_as::<BTreeSet<_>>(), Err(3));
+}
+
+#[test]
+fn poll_fn() {
+    let mut n = 0;
+    let s = stream::poll_fn(move || {
+        n += 1;
+        match n {
+            1 | 2 => Ok(Async::Ready(Some(n))),
+            3 => Err(n),
+            _ => Ok(Async::Ready(None)),
+        }
+    });
+    let mut s = s.wait();
+    assert_eq!(s.next(), Some(Ok(1)));
+    assert_eq!(s.next(), Some(Ok(2)));
+    assert_eq!(s.next(), Some(Err(3)));
+    assert_eq!(s.next(), None);
+}
+
+#[test]
+fn windows() {
+    assert_done(|| list().windows(2).collect(), Ok(vec![vec![1, 2], vec![2, 3]]));
+    assert_done(|| list().windows(4).collect(), Ok(vec![]));
+
+    let mut s = iter(vec![Ok(1), Err(2), Ok(3), Ok(4)]).windows(2).wait();
+    assert_eq!(s.next(), Some(Err(2)));
+    assert_eq!(s.next(), Some(Ok(vec![1, 3])));
+    assert_eq!(s.next(), Some(Ok(vec![3, 4])));
+    assert_eq!(s.next(), None);
+}
+
+#[test]
+fn cycle() {
+    let s = iter(vec![Ok(1), Err(2)]);
+    let mut s = s.cycle().take(3).wait();
+    assert_eq!(s.next(), Some(Ok(1)));
+    assert_eq!(s.next(), Some(Err(2)));
+    assert_eq!(s.next(), Some(Ok(1)));
+    assert_eq!(s.next(), Some(Err(2)));
+    assert_eq!(s.next(), Some(Ok(1)));
+    assert_eq!(s.next(), None);
+
+    assert_done(|| stream::iter_ok::<Vec<i32>, u32>(vec![]).cycle().collect(), Ok(vec![]));
+}
+
 #[test]
 fn concat() {
     let a = iter(vec![Ok::<_, ()>(vec![1, 2, 3]), Ok(vec![4, 5, 6]), Ok(vec![7, 8, 9])]);
@@ -344,3 +617,624 @@ fn concat() {
     let b = iter(vec![Ok::<_, ()>(vec![1, 2, 3]), Err(()), Ok(vec![7, 8, 9])]);
     assert_done(move || b.concat(), Err(()));
 }
+
+#[test]
+fn concat2() {
+    let a = iter(vec![Ok::<_, ()>(vec![1, 2, 3]), Ok(vec![4, 5, 6]), Ok(vec![7, 8, 9])]);
+    assert_done(move || a.concat2(), Ok(vec![1, 2, 3, 4, 5, 6, 7, 8, 9]));
+
+    let b = iter(vec![Ok::<_, ()>(vec![1, 2, 3]), Err(()), Ok(vec![7, 8, 9])]);
+    assert_done(move || b.concat2(), Err(()));
+
+    let c = stream::empty::<Vec<u8>, ()>();
+    assert_done(move || c.concat2(), Ok(vec![]));
+}
+
+#[test]
+fn iter_ok() {
+    assert_done(|| stream::iter_ok::<_, u32>(vec![1, 2, 3]).collect(),
+                Ok(vec![1, 2, 3]));
+}
+
+#[test]
+fn iter_result() {
+    let mut s = stream::iter_result(vec![Ok(1), Err(2), Ok(3)]);
+    sassert_next(&mut s, 1);
+    sassert_err(&mut s, 2);
+    sassert_next(&mut s, 3);
+    sassert_done(&mut s);
+}
+
+#[test]
+fn repeat() {
+    assert_done(|| stream::repeat::<_, u32>(3).take(3).collect(),
+                Ok(vec![3, 3, 3]));
+}
+
+#[test]
+fn repeat_with() {
+    let mut n = 0;
+    assert_done(|| stream::repeat_with::<_, u32, _>(move || { n += 1; n }).take(3).collect(),
+                Ok(vec![1, 2, 3]));
+}
+
+#[test]
+fn once() {
+    assert_done(|| stream::once::<i32, u32>(Ok(1)).collect(), Ok(vec![1]));
+    assert_done(|| stream::once::<i32, u32>(Err(2)).collect(), Err(2));
+}
+
+#[test]
+fn empty() {
+    assert_done(|| stream::empty::<i32, u32>().collect(), Ok(vec![]));
+}
+
+#[test]
+fn select_does_not_starve() {
+    // Neither side can starve the other, even if both are always ready
+    let a = stream::repeat::<_, u32>(1);
+    let b = stream::repeat(2);
+    assert_done(|| a.select(b).take(6).collect(), Ok(vec![1, 2, 1, 2, 1, 2]));
+
+    // Errors are passed through from either side without ending the stream
+    let a = iter(vec![Ok(1), Err(3), Ok(5)]);
+    let b = iter(vec![Err(2), Ok(4)]);
+    let mut s = a.select(b);
+    sassert_next(&mut s, 1);
+    sassert_err(&mut s, 2);
+    sassert_err(&mut s, 3);
+    sassert_next(&mut s, 4);
+    sassert_next(&mut s, 5);
+    sassert_done(&mut s);
+}
+
+#[test]
+fn flatten_unordered() {
+    let (tx1, rx1) = mpsc::unbounded::<i32>();
+    let (tx2, rx2) = mpsc::unbounded::<i32>();
+    let (tx3, rx3) = mpsc::unbounded::<i32>();
+    let streams = stream::iter_ok::<_, ()>(vec![rx1, rx2, rx3]);
+    let mut s = executor::spawn(streams.flatten_unordered(2));
+
+    // Items from the second stream aren't held up by the first
+    tx2.unbounded_send(2).unwrap();
+    tx3.unbounded_send(3).unwrap();
+    assert_eq!(s.poll_stream(unpark_noop()), Ok(Async::Ready(Some(2))));
+    assert!(s.poll_stream(unpark_noop()).unwrap().is_not_ready());
+
+    // The third stream is only polled once one of the first two finishes
+    drop(tx2);
+    assert_eq!(s.poll_stream(unpark_noop()), Ok(Async::Ready(Some(3))));
+    tx1.unbounded_send(1).unwrap();
+    assert_eq!(s.poll_stream(unpark_noop()), Ok(Async::Ready(Some(1))));
+
+    drop(tx1);
+    drop(tx3);
+    assert_eq!(s.poll_stream(unpark_noop()), Ok(Async::Ready(None)));
+}
+
+#[test]
+fn group_by() {
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let mut groups = executor::spawn(rx.group_by(|i| i % 2));
+
+    tx.unbounded_send(1).unwrap();
+    tx.unbounded_send(3).unwrap();
+    tx.unbounded_send(2).unwrap();
+    let (key, odd) = match groups.poll_stream(unpark_noop()) {
+        Ok(Async::Ready(Some(pair))) => pair,
+        _ => panic!("expected a group"),
+    };
+    assert_eq!(key, 1);
+    assert_eq!(*odd.key(), 1);
+    let mut odd = executor::spawn(odd);
+
+    // The first item is held for the group, so no more groups can be created
+    // until it's been consumed.
+    assert!(groups.poll_stream(unpark_noop()).unwrap().is_not_ready());
+    assert_eq!(odd.poll_stream(unpark_noop()), Ok(Async::Ready(Some(1))));
+    assert_eq!(odd.poll_stream(unpark_noop()), Ok(Async::Ready(Some(3))));
+
+    // Now the next item starts a new group which blocks the odd group
+    assert!(odd.poll_stream(unpark_noop()).unwrap().is_not_ready());
+    let (key, even) = match groups.poll_stream(unpark_noop()) {
+        Ok(Async::Ready(Some(pair))) => pair,
+        _ => panic!("expected a group"),
+    };
+    assert_eq!(key, 0);
+    let mut even = executor::spawn(even);
+    assert_eq!(even.poll_stream(unpark_noop()), Ok(Async::Ready(Some(2))));
+
+    // Items for a dropped group start a new one
+    drop(even);
+    tx.unbounded_send(4).unwrap();
+    tx.unbounded_send(5).unwrap();
+    assert!(odd.poll_stream(unpark_noop()).unwrap().is_not_ready());
+    match groups.poll_stream(unpark_noop()) {
+        Ok(Async::Ready(Some((0, _)))) => {}
+        _ => panic!("expected a new even group"),
+    }
+    assert_eq!(odd.poll_stream(unpark_noop()), Ok(Async::Ready(Some(5))));
+
+    drop(tx);
+    assert_eq!(odd.poll_stream(unpark_noop()), Ok(Async::Ready(None)));
+    match groups.poll_stream(unpark_noop()) {
+        Ok(Async::Ready(None)) => {}
+        _ => panic!("expected the end of the stream"),
+    }
+}
+
+#[test]
+fn group_by_separate_tasks() {
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let mut groups = executor::spawn(rx.group_by(|i| i % 2));
+    let (outer_flag, group_flag) = (Flag::new(), Flag::new());
+
+    tx.unbounded_send(1).unwrap();
+    let mut odd = match groups.poll_stream(outer_flag.clone()) {
+        Ok(Async::Ready(Some((_, group)))) => executor::spawn(group),
+        _ => panic!("expected a group"),
+    };
+    assert_eq!(odd.poll_stream(group_flag.clone()), Ok(Async::Ready(Some(1))));
+
+    // Both block on the underlying stream, which only remembers the outer
+    // stream's task.
+    for _ in 0..3 {
+        assert!(odd.poll_stream(group_flag.clone()).unwrap().is_not_ready());
+    }
+    assert!(groups.poll_stream(outer_flag.clone()).unwrap().is_not_ready());
+
+    drop(tx);
+    assert!(outer_flag.take());
+    match groups.poll_stream(outer_flag.clone()) {
+        Ok(Async::Ready(None)) => {}
+        _ => panic!("expected the end of the stream"),
+    }
+    assert!(group_flag.take());
+    assert_eq!(odd.poll_stream(group_flag.clone()), Ok(Async::Ready(None)));
+}
+
+#[test]
+fn group_by_error_wakes_groups() {
+    let (tx, rx) = mpsc::unbounded::<Result<i32, u32>>();
+    let mut groups = executor::spawn(rx.then(|r| r.unwrap()).group_by(|i| i % 2));
+    let (outer_flag, group_flag) = (Flag::new(), Flag::new());
+
+    tx.unbounded_send(Ok(1)).unwrap();
+    let mut odd = match groups.poll_stream(outer_flag.clone()) {
+        Ok(Async::Ready(Some((_, group)))) => executor::spawn(group),
+        _ => panic!("expected a group"),
+    };
+    assert_eq!(odd.poll_stream(group_flag.clone()), Ok(Async::Ready(Some(1))));
+    assert!(odd.poll_stream(group_flag.clone()).unwrap().is_not_ready());
+    assert!(groups.poll_stream(outer_flag.clone()).unwrap().is_not_ready());
+
+    tx.unbounded_send(Err(7)).unwrap();
+    assert!(outer_flag.take());
+    assert!(groups.poll_stream(outer_flag.clone()).is_err());
+    assert!(group_flag.take());
+}
+
+#[test]
+fn group_by_outer_dropped_with_new_group_pending() {
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let mut groups = executor::spawn(rx.group_by(|i| i % 2));
+
+    tx.unbounded_send(1).unwrap();
+    let mut odd = match groups.poll_stream(unpark_noop()) {
+        Ok(Async::Ready(Some((_, group)))) => executor::spawn(group),
+        _ => panic!("expected a group"),
+    };
+    assert_eq!(odd.poll_stream(unpark_noop()), Ok(Async::Ready(Some(1))));
+
+    // The even item starts a group which is never handed out
+    tx.unbounded_send(2).unwrap();
+    assert!(odd.poll_stream(unpark_noop()).unwrap().is_not_ready());
+    drop(groups);
+
+    // so later even items are dropped rather than blocking the odd group
+    tx.unbounded_send(4).unwrap();
+    tx.unbounded_send(3).unwrap();
+    drop(tx);
+    assert_eq!(odd.poll_stream(unpark_noop()), Ok(Async::Ready(Some(3))));
+    assert_eq!(odd.poll_stream(unpark_noop()), Ok(Async::Ready(None)));
+}
+
+#[test]
+fn partition() {
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let (evens, odds) = rx.partition(|i| i % 2 == 0);
+    assert!(evens.is_matching());
+    assert!(!odds.is_matching());
+    let mut evens = executor::spawn(evens);
+    let mut odds = executor::spawn(odds);
+
+    tx.unbounded_send(1).unwrap();
+    tx.unbounded_send(2).unwrap();
+    tx.unbounded_send(3).unwrap();
+
+    // The odd item is held until the odd half takes it, which blocks the
+    // even half in the meantime.
+    assert!(evens.poll_stream(unpark_noop()).unwrap().is_not_ready());
+    assert!(evens.poll_stream(unpark_noop()).unwrap().is_not_ready());
+    assert_eq!(odds.poll_stream(unpark_noop()), Ok(Async::Ready(Some(1))));
+    assert!(odds.poll_stream(unpark_noop()).unwrap().is_not_ready());
+    assert_eq!(evens.poll_stream(unpark_noop()), Ok(Async::Ready(Some(2))));
+
+    // Once a half is dropped, its items are discarded
+    drop(odds);
+    tx.unbounded_send(4).unwrap();
+    assert_eq!(evens.poll_stream(unpark_noop()), Ok(Async::Ready(Some(4))));
+
+    drop(tx);
+    assert_eq!(evens.poll_stream(unpark_noop()), Ok(Async::Ready(None)));
+}
+
+#[test]
+fn partition_separate_tasks() {
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let (evens, odds) = rx.partition(|i| i % 2 == 0);
+    let mut evens = executor::spawn(evens);
+    let mut odds = executor::spawn(odds);
+    let (even_flag, odd_flag) = (Flag::new(), Flag::new());
+
+    // Both halves block on the underlying stream, which only remembers the
+    // odd half's task.
+    for _ in 0..3 {
+        assert!(evens.poll_stream(even_flag.clone()).unwrap().is_not_ready());
+    }
+    assert!(odds.poll_stream(odd_flag.clone()).unwrap().is_not_ready());
+
+    drop(tx);
+    assert!(odd_flag.take());
+    assert_eq!(odds.poll_stream(odd_flag.clone()), Ok(Async::Ready(None)));
+    assert!(even_flag.take());
+    assert_eq!(evens.poll_stream(even_flag.clone()), Ok(Async::Ready(None)));
+}
+
+#[test]
+fn partition_error_wakes_other_half() {
+    let (tx, rx) = mpsc::unbounded::<Result<i32, u32>>();
+    let rx = rx.then(|r| r.unwrap());
+    let (evens, odds) = rx.partition(|i| i % 2 == 0);
+    let mut evens = executor::spawn(evens);
+    let mut odds = executor::spawn(odds);
+    let (even_flag, odd_flag) = (Flag::new(), Flag::new());
+
+    assert!(evens.poll_stream(even_flag.clone()).unwrap().is_not_ready());
+    assert!(odds.poll_stream(odd_flag.clone()).unwrap().is_not_ready());
+
+    tx.unbounded_send(Err(7)).unwrap();
+    assert!(odd_flag.take());
+    assert_eq!(odds.poll_stream(odd_flag.clone()), Err(7));
+    assert!(even_flag.take());
+}
+
+#[test]
+fn fan_out_block() {
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let mut handles = rx.fan_out(2, 1, stream::LagPolicy::Block);
+    let mut b = executor::spawn(handles.pop().unwrap());
+    let mut a = executor::spawn(handles.pop().unwrap());
+
+    tx.unbounded_send(1).unwrap();
+    tx.unbounded_send(2).unwrap();
+    assert_eq!(a.poll_stream(unpark_noop()), Ok(Async::Ready(Some(1))));
+
+    // `b` hasn't taken its first item yet, so `a` is held back
+    assert_eq!(b.get_ref().len(), 1);
+    assert!(a.poll_stream(unpark_noop()).unwrap().is_not_ready());
+    assert_eq!(b.poll_stream(unpark_noop()), Ok(Async::Ready(Some(1))));
+    assert_eq!(a.poll_stream(unpark_noop()), Ok(Async::Ready(Some(2))));
+
+    // Once unsubscribed, `b` no longer holds anything up
+    drop(b);
+    tx.unbounded_send(3).unwrap();
+    tx.unbounded_send(4).unwrap();
+    drop(tx);
+    assert_eq!(a.poll_stream(unpark_noop()), Ok(Async::Ready(Some(3))));
+    assert_eq!(a.poll_stream(unpark_noop()), Ok(Async::Ready(Some(4))));
+    assert_eq!(a.poll_stream(unpark_noop()), Ok(Async::Ready(None)));
+}
+
+#[test]
+fn fan_out_repeated_polls_keep_one_task() {
+    let s = stream::poll_fn(|| -> Poll<Option<i32>, u32> { Ok(Async::NotReady) });
+    let mut handles = s.fan_out(2, 1, stream::LagPolicy::Block);
+    let mut a = executor::spawn(handles.pop().unwrap());
+
+    let live = Arc::new(AtomicUsize::new(0));
+    for _ in 0..10 {
+        assert!(a.poll_stream(unpark_counted(&live)).unwrap().is_not_ready());
+    }
+    assert_eq!(live.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn fan_out_drop_oldest() {
+    let items = iter(vec![Ok(1), Ok(2), Err(3), Ok(4)]);
+    let mut handles = items.fan_out(2, 2, stream::LagPolicy::DropOldest);
+    let b = handles.pop().unwrap();
+    let a = handles.pop().unwrap();
+
+    let mut a = a.wait();
+    assert_eq!(a.next(), Some(Ok(1)));
+    assert_eq!(a.next(), Some(Ok(2)));
+    assert_eq!(a.next(), Some(Err(3)));
+    assert_eq!(a.next(), Some(Ok(4)));
+    assert_eq!(a.next(), None);
+
+    // `b` lagged behind, so it only sees the latest items
+    let mut b = b.wait();
+    assert_eq!(b.next(), Some(Err(3)));
+    assert_eq!(b.next(), Some(Ok(4)));
+    assert_eq!(b.next(), None);
+}
+
+#[test]
+fn for_each_concurrent() {
+    let (tx1, rx1) = oneshot::channel::<()>();
+    let (tx2, rx2) = oneshot::channel::<()>();
+    let (tx3, rx3) = oneshot::channel::<()>();
+    let mut receivers = vec![rx1, rx2, rx3].into_iter();
+    let started = std::rc::Rc::new(std::cell::Cell::new(0));
+    let started2 = started.clone();
+    let work = stream::iter_ok::<_, oneshot::Canceled>(0..3).for_each_concurrent(2, move |_| {
+        started2.set(started2.get() + 1);
+        receivers.next().unwrap()
+    });
+    let mut work = executor::spawn(work);
+
+    assert!(work.poll_future(unpark_noop()).unwrap().is_not_ready());
+    assert_eq!(started.get(), 2);
+
+    // Completing one future makes room for the next one
+    tx2.send(()).unwrap();
+    assert!(work.poll_future(unpark_noop()).unwrap().is_not_ready());
+    assert_eq!(started.get(), 3);
+
+    tx1.send(()).unwrap();
+    assert!(work.poll_future(unpark_noop()).unwrap().is_not_ready());
+    tx3.send(()).unwrap();
+    assert_eq!(work.poll_future(unpark_noop()), Ok(Async::Ready(())));
+
+    assert_done(|| err_list().for_each_concurrent(2, |_| Ok(())), Err(3));
+}
+
+#[test]
+fn try_for_each_concurrent() {
+    let (tx1, rx1) = oneshot::channel::<()>();
+    let (tx2, rx2) = oneshot::channel::<()>();
+    let (_tx3, rx3) = oneshot::channel::<()>();
+    let mut receivers = vec![rx1, rx2, rx3].into_iter();
+    let started = std::rc::Rc::new(std::cell::Cell::new(0));
+    let started2 = started.clone();
+    let work = stream::iter_ok::<_, oneshot::Canceled>(0..3)
+        .try_for_each_concurrent(2, move |_| {
+            started2.set(started2.get() + 1);
+            receivers.next().unwrap()
+        });
+    let mut work = executor::spawn(work);
+
+    assert!(work.poll_future(unpark_noop()).unwrap().is_not_ready());
+    assert_eq!(started.get(), 2);
+
+    // After an error no more items are started, but the running future is
+    // waited for before resolving
+    drop(tx2);
+    assert!(work.poll_future(unpark_noop()).unwrap().is_not_ready());
+    assert_eq!(started.get(), 2);
+    tx1.send(()).unwrap();
+    assert_eq!(work.poll_future(unpark_noop()), Err(oneshot::Canceled));
+    assert_eq!(started.get(), 2);
+
+    assert_done(|| list().try_for_each_concurrent(2, |_| Ok(())), Ok(()));
+    assert_done(|| err_list().try_for_each_concurrent(2, |_| Ok(())), Err(3));
+}
+
+#[test]
+fn valve() {
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let (s, handle) = stream::valve(rx);
+    let mut s = executor::spawn(s);
+    assert!(handle.is_open());
+
+    tx.unbounded_send(1).unwrap();
+    tx.unbounded_send(2).unwrap();
+    assert_eq!(s.poll_stream(unpark_noop()), Ok(Async::Ready(Some(1))));
+
+    // While closed the stream isn't polled, even though it has an item ready
+    handle.clone().close();
+    assert!(!handle.is_open());
+    assert!(s.poll_stream(unpark_noop()).unwrap().is_not_ready());
+    assert!(s.poll_stream(unpark_noop()).unwrap().is_not_ready());
+
+    handle.open();
+    assert_eq!(s.poll_stream(unpark_noop()), Ok(Async::Ready(Some(2))));
+    drop(tx);
+    assert_eq!(s.poll_stream(unpark_noop()), Ok(Async::Ready(None)));
+}
+
+#[test]
+fn share() {
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let a = rx.share(2);
+    let b = a.clone();
+    let mut a = executor::spawn(a);
+    let mut b = executor::spawn(b);
+
+    assert!(a.poll_stream(unpark_noop()).unwrap().is_not_ready());
+    tx.unbounded_send(1).unwrap();
+    assert_eq!(b.poll_stream(unpark_noop()), Ok(Async::Ready(Some(1))));
+    assert_eq!(a.poll_stream(unpark_noop()), Ok(Async::Ready(Some(1))));
+
+    tx.unbounded_send(2).unwrap();
+    tx.unbounded_send(3).unwrap();
+    assert_eq!(a.poll_stream(unpark_noop()), Ok(Async::Ready(Some(2))));
+    assert_eq!(a.poll_stream(unpark_noop()), Ok(Async::Ready(Some(3))));
+
+    // Late subscribers replay the buffered items
+    let mut c = executor::spawn(a.get_ref().clone());
+    assert_eq!(c.poll_stream(unpark_noop()), Ok(Async::Ready(Some(2))));
+
+    drop(tx);
+    assert_eq!(a.poll_stream(unpark_noop()), Ok(Async::Ready(None)));
+    assert_eq!(b.poll_stream(unpark_noop()), Ok(Async::Ready(Some(2))));
+    assert_eq!(b.poll_stream(unpark_noop()), Ok(Async::Ready(Some(3))));
+    assert_eq!(b.poll_stream(unpark_noop()), Ok(Async::Ready(None)));
+    assert_eq!(c.poll_stream(unpark_noop()), Ok(Async::Ready(Some(3))));
+    assert_eq!(c.poll_stream(unpark_noop()), Ok(Async::Ready(None)));
+}
+
+#[test]
+fn share_repeated_polls_keep_one_task() {
+    let s = stream::poll_fn(|| -> Poll<Option<i32>, u32> { Ok(Async::NotReady) });
+    let a = s.share(1);
+    let mut b = executor::spawn(a.clone());
+    let mut a = executor::spawn(a);
+
+    let live = Arc::new(AtomicUsize::new(0));
+    for _ in 0..10 {
+        assert!(a.poll_stream(unpark_counted(&live)).unwrap().is_not_ready());
+        assert!(b.poll_stream(unpark_counted(&live)).unwrap().is_not_ready());
+    }
+    assert_eq!(live.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn merge_sorted() {
+    let (tx, rx) = mpsc::unbounded::<u32>();
+    let a = rx.map_err(|()| 0);
+    let b = stream::iter_ok::<_, u32>(vec![2, 3]).boxed();
+    let mut s = executor::spawn(stream::merge_sorted(vec![a.boxed(), b], |&x| x));
+
+    // Nothing can be yielded until every stream has an item
+    assert!(s.poll_stream(unpark_noop()).unwrap().is_not_ready());
+    tx.unbounded_send(1).unwrap();
+    tx.unbounded_send(3).unwrap();
+    assert_eq!(s.poll_stream(unpark_noop()), Ok(Async::Ready(Some(1))));
+    assert_eq!(s.poll_stream(unpark_noop()), Ok(Async::Ready(Some(2))));
+    assert_eq!(s.poll_stream(unpark_noop()), Ok(Async::Ready(Some(3))));
+    assert!(s.poll_stream(unpark_noop()).unwrap().is_not_ready());
+    drop(tx);
+    assert_eq!(s.poll_stream(unpark_noop()), Ok(Async::Ready(Some(3))));
+    assert_eq!(s.poll_stream(unpark_noop()), Ok(Async::Ready(None)));
+}
+
+#[test]
+fn join_by_key() {
+    let left = stream::iter_ok::<_, u32>(vec![1, 2, 3, 2]);
+    let right = stream::iter_ok(vec![2, 2, 4, 1]);
+    let joined = stream::join_by_key(left, right, |&x| x, 4);
+    assert_done(|| joined.collect(), Ok(vec![(2, 2), (2, 2), (1, 1)]));
+
+    // Unmatched items fall out of the window
+    let left = stream::iter_ok::<_, u32>(vec![1, 2, 3]);
+    let right = stream::iter_ok(vec![4, 5, 6, 1, 3]);
+    let joined = stream::join_by_key(left, right, |&x| x, 2);
+    assert_done(|| joined.collect(), Ok(vec![(3, 3)]));
+}
+
+#[test]
+fn batch_weighted() {
+    let s = stream::iter_ok::<_, u32>(vec![2, 3, 1, 9, 4, 4]);
+    assert_done(|| s.batch_weighted(5, |&x| x).collect(),
+                Ok(vec![vec![2, 3], vec![1], vec![9], vec![4], vec![4]]));
+
+    // Errors flush the current batch first
+    let s = stream::iter_result(vec![Ok(1), Ok(1), Err(7), Ok(2)]);
+    let mut s = s.batch_weighted(10, |&x| x).wait();
+    assert_eq!(s.next(), Some(Ok(vec![1, 1])));
+    assert_eq!(s.next(), Some(Err(7)));
+    assert_eq!(s.next(), Some(Ok(vec![2])));
+    assert_eq!(s.next(), None);
+}
+
+#[test]
+fn batch_weighted_overweight_item() {
+    let (tx, rx) = mpsc::unbounded::<usize>();
+    let mut s = executor::spawn(rx.batch_weighted(5, |&x| x));
+
+    // The overweight item starts a batch of its own, which is full already
+    // and mustn't wait for more items to arrive.
+    tx.unbounded_send(2).unwrap();
+    tx.unbounded_send(9).unwrap();
+    assert_eq!(s.poll_stream(unpark_noop()), Ok(Async::Ready(Some(vec![2]))));
+    assert_eq!(s.poll_stream(unpark_noop()), Ok(Async::Ready(Some(vec![9]))));
+    assert!(s.poll_stream(unpark_noop()).unwrap().is_not_ready());
+
+    drop(tx);
+    assert_eq!(s.poll_stream(unpark_noop()), Ok(Async::Ready(None)));
+}
+
+#[test]
+fn size_hint() {
+    let s = stream::iter_ok::<_, u32>(vec![1, 2, 3, 4, 5]);
+    assert_eq!(s.size_hint(), (5, Some(5)));
+    assert_eq!(s.map(|x| x + 1).size_hint(), (5, Some(5)));
+
+    let s = stream::iter_ok::<_, u32>(vec![1, 2, 3, 4, 5]);
+    assert_eq!(s.filter(|&x| x > 2).size_hint(), (0, Some(5)));
+
+    let s = stream::iter_ok::<_, u32>(vec![1, 2, 3, 4, 5]);
+    assert_eq!(s.chunks(2).size_hint(), (3, Some(5)));
+
+    let s = stream::iter_ok::<_, u32>(vec![1, 2, 3]);
+    let s = s.chain(stream::iter_ok(vec![4, 5])).skip(1).take(2);
+    assert_eq!(s.size_hint(), (2, Some(5)));
+
+    let mut s = stream::iter_ok::<_, u32>(vec![1, 2]).fuse();
+    assert_eq!(s.poll(), Ok(Async::Ready(Some(1))));
+    assert_eq!(s.size_hint(), (1, Some(1)));
+
+    // Streams which don't know their length use the default
+    let (_tx, rx) = mpsc::unbounded::<i32>();
+    assert_eq!(rx.size_hint(), (0, None));
+}
+
+#[test]
+fn inspect_drop() {
+    use std::cell::Cell;
+
+    let dropped = Cell::new(0);
+    {
+        let s = list().inspect_drop(|| dropped.set(dropped.get() + 1));
+        assert_done(|| s.take(2).collect(), Ok(vec![1, 2]));
+    }
+    assert_eq!(dropped.get(), 1);
+
+    // Finishing the stream disarms the closure
+    {
+        let s = list().inspect_drop(|| dropped.set(dropped.get() + 1));
+        assert_done(|| s.collect(), Ok(vec![1, 2, 3]));
+    }
+    assert_eq!(dropped.get(), 1);
+}
+
+#[test]
+fn select_with_strategy() {
+    use futures::stream::PollNext;
+
+    let (tx1, rx1) = mpsc::unbounded::<i32>();
+    let (tx2, rx2) = mpsc::unbounded::<i32>();
+    let mut left_first = true;
+    let s = stream::select_with_strategy(rx1, rx2, move || {
+        left_first = !left_first;
+        if left_first { PollNext::Right } else { PollNext::Left }
+    });
+    let mut s = executor::spawn(s);
+
+    tx1.unbounded_send(1).unwrap();
+    tx1.unbounded_send(2).unwrap();
+    tx2.unbounded_send(10).unwrap();
+    assert_eq!(s.poll_stream(unpark_noop()), Ok(Async::Ready(Some(1))));
+    assert_eq!(s.poll_stream(unpark_noop()), Ok(Async::Ready(Some(10))));
+    // The right side has nothing, so the left one is polled too
+    assert_eq!(s.poll_stream(unpark_noop()), Ok(Async::Ready(Some(2))));
+    assert!(s.poll_stream(unpark_noop()).unwrap().is_not_ready());
+
+    drop(tx1);
+    assert!(s.poll_stream(unpark_noop()).unwrap().is_not_ready());
+    drop(tx2);
+    assert_eq!(s.poll_stream(unpark_noop()), Ok(Async::Ready(None)));
+}
@@ -0,0 +1,60 @@
+extern crate futures;
+
+use futures::{Async, Future};
+use futures::sync::mpsc;
+use futures::stream::{self, select_all, SelectAll, Stream};
+
+mod support;
+
+#[test]
+fn works_1() {
+    let a = stream::iter_ok::<_, ()>(vec![1, 2]);
+    let b = stream::iter_ok::<_, ()>(vec![3]);
+    let c = stream::iter_ok::<_, ()>(vec![]);
+
+    let mut items = select_all(vec![a, b, c]).collect().wait().unwrap();
+    items.sort();
+    assert_eq!(items, vec![1, 2, 3]);
+}
+
+#[test]
+fn push_while_running() {
+    let (a_tx, a_rx) = mpsc::unbounded::<u32>();
+    let (b_tx, b_rx) = mpsc::unbounded::<u32>();
+
+    let mut set = SelectAll::new();
+    set.push(a_rx);
+    assert_eq!(set.len(), 1);
+
+    let mut spawn = futures::executor::spawn(set);
+    assert!(spawn.poll_stream(support::unpark_noop()).unwrap().is_not_ready());
+
+    spawn.get_mut().push(b_rx);
+    b_tx.unbounded_send(2).unwrap();
+    assert_eq!(spawn.poll_stream(support::unpark_noop()), Ok(Async::Ready(Some(2))));
+
+    a_tx.unbounded_send(1).unwrap();
+    assert_eq!(spawn.poll_stream(support::unpark_noop()), Ok(Async::Ready(Some(1))));
+
+    drop(a_tx);
+    assert!(spawn.poll_stream(support::unpark_noop()).unwrap().is_not_ready());
+    assert_eq!(spawn.get_ref().len(), 1);
+
+    drop(b_tx);
+    assert_eq!(spawn.poll_stream(support::unpark_noop()), Ok(Async::Ready(None)));
+    assert!(spawn.get_ref().is_empty());
+}
+
+#[test]
+fn errors_keep_stream() {
+    let (tx, rx) = mpsc::unbounded::<Result<u32, u32>>();
+    let s = rx.then(|r| r.unwrap());
+    let mut spawn = futures::executor::spawn(select_all(vec![s]));
+
+    tx.unbounded_send(Err(5)).unwrap();
+    assert_eq!(spawn.poll_stream(support::unpark_noop()), Err(5));
+    tx.unbounded_send(Ok(6)).unwrap();
+    assert_eq!(spawn.poll_stream(support::unpark_noop()), Ok(Async::Ready(Some(6))));
+    drop(tx);
+    assert_eq!(spawn.poll_stream(support::unpark_noop()), Ok(Async::Ready(None)));
+}
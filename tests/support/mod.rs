@@ -2,6 +2,7 @@
 
 use std::fmt;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::thread;
 
 use futures::{Future, IntoFuture, Async, Poll};
@@ -11,6 +12,7 @@ use futures::executor::{self, Unpark};
 use futures::task;
 
 pub mod local_executor;
+pub mod timer;
 
 pub fn f_ok(a: i32) -> FutureResult<i32, u32> { Ok(a).into_future() }
 pub fn f_err(a: u32) -> FutureResult<i32, u32> { Err(a).into_future() }
@@ -92,6 +94,26 @@ pub fn unpark_noop() -> Arc<Unpark> {
     Arc::new(Foo)
 }
 
+// Returns an `Unpark` which keeps `live` up to date with the number of
+// handles to it which are still around, such as the tasks a primitive holds
+// on to while it's blocked.
+pub fn unpark_counted(live: &Arc<AtomicUsize>) -> Arc<Unpark> {
+    struct Counted(Arc<AtomicUsize>);
+
+    impl Unpark for Counted {
+        fn unpark(&self) {}
+    }
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            self.0.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    live.fetch_add(1, Ordering::SeqCst);
+    Arc::new(Counted(live.clone()))
+}
+
 pub trait ForgetExt {
     fn forget(self);
 }
@@ -0,0 +1,61 @@
+//! A manually driven timer for testing time-based combinators
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use futures::{Future, Async, Poll};
+use futures::timer::Timer;
+
+/// A timer whose clock only moves forward when `advance` is called.
+///
+/// Sleep futures created by this timer never schedule notifications, so tests
+/// are expected to poll again after advancing the clock.
+#[derive(Clone, Debug)]
+pub struct ManualTimer {
+    now: Arc<AtomicUsize>,
+}
+
+impl ManualTimer {
+    pub fn new() -> ManualTimer {
+        ManualTimer { now: Arc::new(AtomicUsize::new(0)) }
+    }
+
+    pub fn advance(&self, dur: Duration) {
+        self.now.fetch_add(millis(dur), Ordering::SeqCst);
+    }
+}
+
+impl Timer for ManualTimer {
+    type Sleep = ManualSleep;
+
+    fn sleep(&self, dur: Duration) -> ManualSleep {
+        ManualSleep {
+            now: self.now.clone(),
+            deadline: self.now.load(Ordering::SeqCst) + millis(dur),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ManualSleep {
+    now: Arc<AtomicUsize>,
+    deadline: usize,
+}
+
+impl Future for ManualSleep {
+    type Item = ();
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<(), ()> {
+        if self.now.load(Ordering::SeqCst) >= self.deadline {
+            Ok(Async::Ready(()))
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+}
+
+fn millis(dur: Duration) -> usize {
+    (dur.as_secs() * 1000) as usize + (dur.subsec_nanos() / 1_000_000) as usize
+}
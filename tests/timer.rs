@@ -0,0 +1,252 @@
+extern crate futures;
+
+use std::time::Duration;
+
+use futures::{Async, Poll, Stream};
+use futures::executor;
+use futures::timer::Elapsed;
+use futures::sync::mpsc;
+
+mod support;
+use support::*;
+use support::timer::ManualTimer;
+
+fn ms(n: u64) -> Duration {
+    Duration::from_millis(n)
+}
+
+fn poll<S: Stream>(s: &mut S) -> Poll<Option<S::Item>, S::Error> {
+    executor::spawn(s).poll_stream(unpark_noop())
+}
+
+#[test]
+fn debounce_emits_latest_after_quiet_period() {
+    let timer = ManualTimer::new();
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let mut s = rx.debounce(ms(10), timer.clone());
+
+    tx.unbounded_send(1).unwrap();
+    tx.unbounded_send(2).unwrap();
+    assert_eq!(poll(&mut s), Ok(Async::NotReady));
+    timer.advance(ms(5));
+    tx.unbounded_send(3).unwrap();
+    assert_eq!(poll(&mut s), Ok(Async::NotReady));
+    timer.advance(ms(5));
+    assert_eq!(poll(&mut s), Ok(Async::NotReady));
+    timer.advance(ms(5));
+    assert_eq!(poll(&mut s), Ok(Async::Ready(Some(3))));
+    assert_eq!(poll(&mut s), Ok(Async::NotReady));
+}
+
+#[test]
+fn debounce_flushes_on_end() {
+    let timer = ManualTimer::new();
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let mut s = rx.debounce(ms(10), timer.clone());
+
+    tx.unbounded_send(1).unwrap();
+    drop(tx);
+    assert_eq!(poll(&mut s), Ok(Async::Ready(Some(1))));
+    assert_eq!(poll(&mut s), Ok(Async::Ready(None)));
+}
+
+#[test]
+fn sample_emits_latest_per_tick() {
+    let timer = ManualTimer::new();
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let mut s = rx.sample(ms(10), timer.clone());
+
+    assert_eq!(poll(&mut s), Ok(Async::NotReady));
+    tx.unbounded_send(1).unwrap();
+    tx.unbounded_send(2).unwrap();
+    assert_eq!(poll(&mut s), Ok(Async::NotReady));
+    timer.advance(ms(10));
+    assert_eq!(poll(&mut s), Ok(Async::Ready(Some(2))));
+
+    // a tick without any items yields nothing
+    timer.advance(ms(10));
+    assert_eq!(poll(&mut s), Ok(Async::NotReady));
+
+    tx.unbounded_send(3).unwrap();
+    timer.advance(ms(10));
+    assert_eq!(poll(&mut s), Ok(Async::Ready(Some(3))));
+
+    tx.unbounded_send(4).unwrap();
+    drop(tx);
+    assert_eq!(poll(&mut s), Ok(Async::Ready(Some(4))));
+    assert_eq!(poll(&mut s), Ok(Async::Ready(None)));
+}
+
+#[test]
+fn chunks_timeout_yields_full_chunks() {
+    let timer = ManualTimer::new();
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let mut s = rx.chunks_timeout(2, ms(10), timer.clone());
+
+    tx.unbounded_send(1).unwrap();
+    tx.unbounded_send(2).unwrap();
+    tx.unbounded_send(3).unwrap();
+    assert_eq!(poll(&mut s), Ok(Async::Ready(Some(vec![1, 2]))));
+    assert_eq!(poll(&mut s), Ok(Async::NotReady));
+
+    drop(tx);
+    assert_eq!(poll(&mut s), Ok(Async::Ready(Some(vec![3]))));
+    assert_eq!(poll(&mut s), Ok(Async::Ready(None)));
+}
+
+#[test]
+fn chunks_timeout_yields_partial_chunk_after_deadline() {
+    let timer = ManualTimer::new();
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let mut s = rx.chunks_timeout(10, ms(10), timer.clone());
+
+    // no deadline is running while the buffer is empty
+    timer.advance(ms(20));
+    assert_eq!(poll(&mut s), Ok(Async::NotReady));
+
+    tx.unbounded_send(1).unwrap();
+    assert_eq!(poll(&mut s), Ok(Async::NotReady));
+    timer.advance(ms(5));
+    tx.unbounded_send(2).unwrap();
+    assert_eq!(poll(&mut s), Ok(Async::NotReady));
+    timer.advance(ms(5));
+    assert_eq!(poll(&mut s), Ok(Async::Ready(Some(vec![1, 2]))));
+    assert_eq!(poll(&mut s), Ok(Async::NotReady));
+}
+
+#[test]
+fn rate_limit_allows_bursts() {
+    let timer = ManualTimer::new();
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let mut s = rx.rate_limit(ms(10), 2, timer.clone());
+
+    tx.unbounded_send(0).unwrap();
+    tx.unbounded_send(1).unwrap();
+    tx.unbounded_send(2).unwrap();
+    assert_eq!(poll(&mut s), Ok(Async::Ready(Some(0))));
+    assert_eq!(poll(&mut s), Ok(Async::Ready(Some(1))));
+    assert_eq!(poll(&mut s), Ok(Async::NotReady));
+
+    // One token comes back per period
+    timer.advance(ms(10));
+    assert_eq!(poll(&mut s), Ok(Async::Ready(Some(2))));
+    assert_eq!(poll(&mut s), Ok(Async::NotReady));
+
+    // While idle the bucket fills back up, but no further than `burst`
+    for _ in 0..3 {
+        timer.advance(ms(10));
+        assert_eq!(poll(&mut s), Ok(Async::NotReady));
+    }
+    tx.unbounded_send(3).unwrap();
+    tx.unbounded_send(4).unwrap();
+    tx.unbounded_send(5).unwrap();
+    assert_eq!(poll(&mut s), Ok(Async::Ready(Some(3))));
+    assert_eq!(poll(&mut s), Ok(Async::Ready(Some(4))));
+    assert_eq!(poll(&mut s), Ok(Async::NotReady));
+
+    drop(tx);
+    timer.advance(ms(10));
+    assert_eq!(poll(&mut s), Ok(Async::Ready(Some(5))));
+    timer.advance(ms(10));
+    assert_eq!(poll(&mut s), Ok(Async::Ready(None)));
+}
+
+#[derive(Debug, PartialEq)]
+enum TimeoutError {
+    Elapsed,
+    Timer,
+    Canceled,
+}
+
+impl From<Elapsed> for TimeoutError {
+    fn from(_: Elapsed) -> TimeoutError {
+        TimeoutError::Elapsed
+    }
+}
+
+impl From<()> for TimeoutError {
+    fn from(_: ()) -> TimeoutError {
+        TimeoutError::Timer
+    }
+}
+
+#[test]
+fn timeout_between_items() {
+    let timer = ManualTimer::new();
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let rx = rx.map_err(|()| TimeoutError::Canceled);
+    let mut s = rx.timeout(ms(10), timer.clone());
+
+    assert_eq!(poll(&mut s), Ok(Async::NotReady));
+    timer.advance(ms(5));
+    tx.unbounded_send(1).unwrap();
+    assert_eq!(poll(&mut s), Ok(Async::Ready(Some(1))));
+
+    // The clock restarts after every item
+    timer.advance(ms(5));
+    assert_eq!(poll(&mut s), Ok(Async::NotReady));
+    timer.advance(ms(5));
+    assert_eq!(poll(&mut s), Ok(Async::NotReady));
+    timer.advance(ms(5));
+    assert_eq!(poll(&mut s), Err(TimeoutError::Elapsed));
+
+    // and after a timeout, without ending the stream
+    assert_eq!(poll(&mut s), Ok(Async::NotReady));
+    tx.unbounded_send(2).unwrap();
+    drop(tx);
+    assert_eq!(poll(&mut s), Ok(Async::Ready(Some(2))));
+    assert_eq!(poll(&mut s), Ok(Async::Ready(None)));
+}
+
+#[test]
+fn batch_sends_full_or_overdue_batches() {
+    use futures::{AsyncSink, Sink};
+
+    let timer = ManualTimer::new();
+    let mut sink = Vec::new().batch(2, ms(10), timer.clone());
+    let flush = |sink: &mut _| executor::spawn(sink).poll_flush(&unpark_noop());
+
+    assert_eq!(sink.start_send(1), Ok(AsyncSink::Ready));
+    assert_eq!(sink.start_send(2), Ok(AsyncSink::Ready));
+    assert_eq!(sink.start_send(3), Ok(AsyncSink::Ready));
+    assert_eq!(sink.get_ref(), &vec![vec![1, 2]]);
+
+    // The partial batch is held back until its deadline
+    assert_eq!(flush(&mut sink), Ok(Async::NotReady));
+    timer.advance(ms(10));
+    assert_eq!(flush(&mut sink), Ok(Async::Ready(())));
+    assert_eq!(sink.get_ref(), &vec![vec![1, 2], vec![3]]);
+
+    // Closing sends whatever has been buffered
+    assert_eq!(sink.start_send(4), Ok(AsyncSink::Ready));
+    assert_eq!(sink.close(), Ok(Async::Ready(())));
+    assert_eq!(sink.get_ref(), &vec![vec![1, 2], vec![3], vec![4]]);
+}
+
+#[test]
+fn sink_rate_limit_applies_backpressure() {
+    use futures::{AsyncSink, Sink};
+
+    let timer = ManualTimer::new();
+    let mut sink = Vec::new().rate_limit(ms(10), 2, timer.clone());
+
+    assert_eq!(sink.start_send(1), Ok(AsyncSink::Ready));
+    assert_eq!(sink.start_send(2), Ok(AsyncSink::Ready));
+    assert_eq!(sink.start_send(3), Ok(AsyncSink::NotReady(3)));
+    assert_eq!(sink.get_ref(), &vec![1, 2]);
+
+    // One token comes back per period
+    timer.advance(ms(10));
+    assert_eq!(sink.start_send(3), Ok(AsyncSink::Ready));
+    assert_eq!(sink.start_send(4), Ok(AsyncSink::NotReady(4)));
+
+    // While idle the bucket fills back up, but no further than `burst`
+    for _ in 0..3 {
+        timer.advance(ms(10));
+        assert_eq!(sink.poll_complete(), Ok(Async::Ready(())));
+    }
+    assert_eq!(sink.start_send(4), Ok(AsyncSink::Ready));
+    assert_eq!(sink.start_send(5), Ok(AsyncSink::Ready));
+    assert_eq!(sink.start_send(6), Ok(AsyncSink::NotReady(6)));
+    assert_eq!(sink.get_ref(), &vec![1, 2, 3, 4, 5]);
+}
@@ -1,5 +1,7 @@
 extern crate futures;
 
+use std::rc::Rc;
+
 use futures::Future;
 use futures::future;
 use futures::unsync::oneshot::{channel, Canceled};
@@ -11,12 +13,27 @@ fn smoke() {
     assert_eq!(rx.wait().unwrap(), 33);
 }
 
+#[test]
+fn non_send_value() {
+    let (tx, rx) = channel();
+    tx.send(Rc::new(33)).unwrap();
+    assert_eq!(*rx.wait().unwrap(), 33);
+}
+
 #[test]
 fn canceled() {
     let (_, rx) = channel::<()>();
     assert_eq!(rx.wait().unwrap_err(), Canceled);
 }
 
+#[test]
+fn is_canceled() {
+    let (tx, rx) = channel::<()>();
+    assert!(!tx.is_canceled());
+    drop(rx);
+    assert!(tx.is_canceled());
+}
+
 #[test]
 fn poll_cancel() {
     let (mut tx, _) = channel::<()>();
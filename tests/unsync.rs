@@ -4,12 +4,19 @@ extern crate futures;
 
 mod support;
 
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
 use futures::{Future, Stream, Sink, Async};
 use futures::unsync::mpsc::{self, SendError};
+use futures::executor::{self, Unpark};
 use futures::future::lazy;
 use futures::stream::iter;
 
 use support::local_executor::Core;
+use support::{unpark_counted, unpark_noop};
 
 #[test]
 fn mpsc_send_recv() {
@@ -87,6 +94,19 @@ fn mpsc_unbounded() {
     }).wait().unwrap();
 }
 
+#[test]
+fn mpsc_unbounded_send() {
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    tx.unbounded_send(1).unwrap();
+    tx.unbounded_send(2).unwrap();
+    drop(tx);
+    assert_eq!(rx.collect().wait(), Ok(vec![1, 2]));
+
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    drop(rx);
+    assert_eq!(tx.unbounded_send(3).unwrap_err().into_inner(), 3);
+}
+
 #[test]
 fn mpsc_recv_unpark() {
     let mut core = Core::new();
@@ -106,3 +126,225 @@ fn mpsc_send_unpark() {
     core.spawn(lazy(move || { let _ = rx; Ok(()) }));
     core.wait();
 }
+
+#[test]
+fn shared_local_awaiters() {
+    let mut core = Core::new();
+    let (tx, rx) = futures::unsync::oneshot::channel::<Rc<u32>>();
+    let shared = futures::unsync::shared(rx);
+    let seen = Rc::new(Cell::new(0));
+    for _ in 0..3 {
+        let seen = seen.clone();
+        core.spawn(shared.clone().map(move |v| {
+            assert_eq!(**v, 6);
+            seen.set(seen.get() + 1);
+        }).map_err(|_| panic!()));
+    }
+    core.spawn(lazy(move || {
+        tx.send(Rc::new(6)).unwrap();
+        Ok(())
+    }));
+    core.wait();
+    assert_eq!(seen.get(), 3);
+    assert_eq!(**shared.peek().unwrap().unwrap(), 6);
+}
+
+#[test]
+fn shared_dropped_driver_hands_over() {
+    struct Flag(AtomicBool);
+
+    impl Unpark for Flag {
+        fn unpark(&self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    let (tx, rx) = futures::unsync::oneshot::channel::<u32>();
+    let shared = futures::unsync::shared(rx);
+    let mut a = executor::spawn(shared.clone());
+    let mut b = executor::spawn(shared);
+    let flag = Arc::new(Flag(AtomicBool::new(false)));
+
+    assert!(b.poll_future(flag.clone()).unwrap().is_not_ready());
+    assert!(a.poll_future(flag.clone()).unwrap().is_not_ready());
+    assert!(!flag.0.load(Ordering::SeqCst));
+
+    // `a` polled last, so the remaining waiters are told to take over
+    drop(a);
+    assert!(flag.0.load(Ordering::SeqCst));
+
+    tx.send(1).unwrap();
+    assert_eq!(b.poll_future(flag).unwrap().map(|v| *v), Async::Ready(1));
+}
+
+#[test]
+fn shared_recursive_poll() {
+    let mut core = Core::new();
+    let (tx0, rx0) = mpsc::unbounded::<Box<Future<Item=(),Error=()>>>();
+    let run_stream = rx0.for_each(|f| f);
+
+    let (tx1, rx1) = futures::unsync::oneshot::channel::<()>();
+
+    let f1 = futures::unsync::shared(run_stream);
+    let f2 = f1.clone();
+    let f3 = f1.clone();
+    tx0.unbounded_send(Box::new(
+        f1.map(|_|()).map_err(|_|())
+            .select(rx1.map_err(|_|()))
+            .map(|_| ()).map_err(|_|()))).unwrap();
+
+    core.spawn(f2.map(|_|()).map_err(|_|()));
+
+    // Call poll() on the spawned future. We want to be sure that this does not
+    // panic due to the future's cell already being borrowed.
+    core.run(futures::future::ok::<(),()>(())).unwrap();
+
+    tx1.send(()).unwrap(); // Break the cycle.
+    drop(tx0);
+    core.run(f3).unwrap();
+}
+
+#[test]
+fn shared_recursive_poll_with_unpark() {
+    use futures::task;
+
+    let mut core = Core::new();
+    let (tx0, rx0) = mpsc::unbounded::<Box<Future<Item=(),Error=()>>>();
+    let run_stream = rx0.for_each(|f| f);
+
+    let (tx1, rx1) = futures::unsync::oneshot::channel::<()>();
+
+    let f1 = futures::unsync::shared(run_stream);
+    let f2 = f1.clone();
+    let f3 = f1.clone();
+    tx0.unbounded_send(Box::new(lazy(move || {
+        task::park().unpark();
+        f1.map(|_|()).map_err(|_|())
+            .select(rx1.map_err(|_|()))
+            .map(|_| ()).map_err(|_|())
+    }))).unwrap();
+
+    core.spawn(f2.map(|_|()).map_err(|_|()));
+
+    // Call poll() on the spawned future. We want to be sure that this does not
+    // panic due to the future's cell already being borrowed.
+    core.run(futures::future::ok::<(),()>(())).unwrap();
+
+    tx1.send(()).unwrap(); // Break the cycle.
+    drop(tx0);
+    core.run(f3).unwrap();
+}
+
+#[test]
+fn mutex_interleaved_tasks() {
+    let mut core = Core::new();
+    let mutex = futures::unsync::Mutex::new(Vec::new());
+    for i in 0..3 {
+        let (tx, rx) = futures::unsync::oneshot::channel::<()>();
+        let mutex2 = mutex.clone();
+        // Each task holds the lock across a point where it waits for
+        // something else, so the others have to queue up behind it
+        core.spawn(mutex.lock().and_then(move |mut guard| {
+            guard.push(i);
+            rx.map(move |()| guard.push(i)).map_err(|_| ())
+        }));
+        core.spawn(lazy(move || {
+            assert!(mutex2.try_lock().is_none());
+            tx.send(()).unwrap();
+            Ok(())
+        }));
+    }
+    core.wait();
+    assert_eq!(*mutex.try_lock().unwrap(), vec![0, 0, 1, 1, 2, 2]);
+}
+
+#[test]
+fn rwlock_readers_and_writers() {
+    let lock = futures::unsync::RwLock::new(Rc::new(1));
+    let read = lock.try_read().unwrap();
+    let mut write = executor::spawn(lock.write());
+    assert!(write.poll_future(unpark_noop()).unwrap().is_not_ready());
+    assert!(lock.try_read().is_none());
+
+    drop(read);
+    match write.poll_future(unpark_noop()) {
+        Ok(Async::Ready(mut guard)) => *guard = Rc::new(2),
+        _ => panic!("lock wasn't acquired"),
+    }
+    let a = lock.read().wait().unwrap();
+    let b = lock.try_read().unwrap();
+    assert_eq!(**a + **b, 4);
+}
+
+#[test]
+fn broadcast_local_subscribers() {
+    let mut core = Core::new();
+    let (tx, rx) = futures::unsync::broadcast::channel::<Rc<u32>>(4);
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    for rx in vec![rx, tx.subscribe()] {
+        let seen = seen.clone();
+        core.spawn(rx.for_each(move |v| {
+            seen.borrow_mut().push(*v);
+            Ok(())
+        }).map_err(|e| panic!("{}", e)));
+    }
+    core.spawn(lazy(move || {
+        for i in 0..3 {
+            tx.broadcast(Rc::new(i)).unwrap();
+        }
+        Ok(())
+    }));
+    core.wait();
+
+    let mut seen = seen.borrow().clone();
+    seen.sort();
+    assert_eq!(seen, vec![0, 0, 1, 1, 2, 2]);
+}
+
+#[test]
+fn broadcast_repeated_polls_keep_one_task() {
+    let (tx, rx) = futures::unsync::broadcast::channel::<i32>(1);
+    let mut rx2 = executor::spawn(tx.subscribe());
+    let mut rx = executor::spawn(rx);
+
+    let live = Arc::new(AtomicUsize::new(0));
+    for _ in 0..10 {
+        assert!(rx.poll_stream(unpark_counted(&live)).unwrap().is_not_ready());
+        assert!(rx2.poll_stream(unpark_counted(&live)).unwrap().is_not_ready());
+    }
+    assert_eq!(live.load(Ordering::SeqCst), 2);
+
+    drop(rx2);
+    assert_eq!(live.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn task_set_runs_children() {
+    let mut core = Core::new();
+    let (tx, rx) = mpsc::channel::<Rc<u32>>(1);
+    let mut set = futures::unsync::TaskSet::new();
+    set.spawn(rx.map(|v| *v).collect().map(|xs| assert_eq!(xs, [1, 2, 3])));
+    set.spawn(iter(vec![1, 2, 3].into_iter().map(|i| Ok(Rc::new(i))))
+                  .forward(tx)
+                  .map(|_| ())
+                  .map_err(|_: SendError<Rc<u32>>| ()));
+    assert_eq!(set.len(), 2);
+    core.run(set).unwrap();
+}
+
+#[test]
+fn task_set_cancels_children_on_drop() {
+    let (tx, rx) = futures::unsync::oneshot::channel::<()>();
+    let mut tx = Some(tx);
+    let mut set = futures::unsync::TaskSet::new();
+    set.spawn(rx.map_err(|_| ()));
+    set.spawn(futures::future::err(()));
+
+    let mut set = executor::spawn(set);
+    assert!(set.poll_future(unpark_noop()).unwrap().is_not_ready());
+    assert_eq!(set.get_ref().len(), 1);
+    assert!(!tx.as_ref().unwrap().is_canceled());
+
+    drop(set);
+    assert!(tx.take().unwrap().is_canceled());
+}
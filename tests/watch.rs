@@ -0,0 +1,75 @@
+extern crate futures;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use futures::{Future, Stream};
+use futures::executor;
+use futures::sync::watch;
+
+mod support;
+use support::*;
+
+#[test]
+fn coalesces_updates() {
+    let (tx, rx) = watch::channel(0);
+    let mut rx2 = rx.clone();
+
+    let mut rx = rx.wait();
+    assert_eq!(rx.next(), Some(Ok(0)));
+    tx.broadcast(1).unwrap();
+    tx.broadcast(2).unwrap();
+    assert_eq!(rx.next(), Some(Ok(2)));
+
+    // Other receivers keep track of what they've seen separately
+    assert_eq!(rx2.get(), 2);
+    sassert_next(&mut rx2, 2);
+    sassert_empty(&mut rx2);
+
+    drop(tx);
+    assert_eq!(rx.next(), None);
+    sassert_done(&mut rx2);
+}
+
+#[test]
+fn wakes_receivers() {
+    let (tx, rx) = watch::channel(0);
+    let t = thread::spawn(move || {
+        rx.take_while(|&v| Ok(v < 3)).collect().wait()
+    });
+    for i in 1..4 {
+        tx.broadcast(i).unwrap();
+        thread::yield_now();
+    }
+    // Depending on timing some values may have been skipped
+    let seen = t.join().unwrap().unwrap();
+    assert!(!seen.is_empty());
+    assert!(seen.windows(2).all(|w| w[0] < w[1]));
+}
+
+#[test]
+fn no_receivers() {
+    let (tx, rx) = watch::channel(0);
+    drop(rx);
+    assert_eq!(tx.broadcast(1).unwrap_err().into_inner(), 1);
+}
+
+#[test]
+fn repeated_polls_keep_one_task() {
+    let (_tx, rx) = watch::channel(1);
+    let mut rx2 = executor::spawn(rx.clone());
+    let mut rx = executor::spawn(rx);
+    assert_eq!(rx.wait_stream(), Some(Ok(1)));
+    assert_eq!(rx2.wait_stream(), Some(Ok(1)));
+
+    let live = Arc::new(AtomicUsize::new(0));
+    for _ in 0..10 {
+        assert!(rx.poll_stream(unpark_counted(&live)).unwrap().is_not_ready());
+        assert!(rx2.poll_stream(unpark_counted(&live)).unwrap().is_not_ready());
+    }
+    assert_eq!(live.load(Ordering::SeqCst), 2);
+
+    drop(rx2);
+    assert_eq!(live.load(Ordering::SeqCst), 1);
+}